@@ -62,6 +62,12 @@ pub static STARLANE_CONTROL_PORT: Lazy<u16> = Lazy::new(|| {
         .unwrap_or(4343)
 });
 
+/// the unix domain socket local clients try before falling back to [`STARLANE_CONTROL_PORT`]
+pub static STARLANE_CONTROL_SOCKET: Lazy<String> = Lazy::new(|| {
+    std::env::var("STARLANE_CONTROL_SOCKET")
+        .unwrap_or(format!("{}/control.sock", STARLANE_HOME.as_str()).to_string())
+});
+
 #[cfg(not(test))]
 pub static STARLANE_HOME: Lazy<String> = Lazy::new(|| {
     std::env::var("STARLANE_HOME").unwrap_or_else(|e| {
@@ -98,6 +104,13 @@ pub(crate) static STARLANE_DATA_DIR: Lazy<String> = Lazy::new(|| {
     })
 });
 
+/// selects the FileStore the machine's services use: `cli` (the default) runs the
+/// `starlane-cli-filestore-service` executable for every operation and `native` works on
+/// the data dir in process
+pub static STARLANE_FILESTORE: Lazy<String> = Lazy::new(|| {
+    std::env::var("STARLANE_FILESTORE").unwrap_or_else(|_| "cli".to_string())
+});
+
 pub static STARLANE_CACHE_DIR: Lazy<String> = Lazy::new(|| {
     std::env::var("STARLANE_CACHE_DIR")
         .unwrap_or(format!("{}/cache", STARLANE_HOME.to_string()).to_string())
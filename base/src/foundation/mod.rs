@@ -40,15 +40,18 @@ use serde_yaml::Value;
 use std::fmt::{Debug, Display};
 use std::future::Future;
 use std::hash::Hash;
+use std::net::{IpAddr, Ipv4Addr};
 use std::ops::{Deref, DerefMut};
 use std::str::FromStr;
 use strum_macros::EnumDiscriminants;
 use starlane_hyperspace::base::config::BaseSubConfig;
-use starlane_hyperspace::registry::{Registry, RegistryConfig};
+use starlane_hyperspace::driver::web::WebConfig;
+use starlane_hyperspace::hyperlane::tcp::ClientCertAuth;
+use starlane_hyperspace::registry::{Registry, RegistryConfig, RegistryKind};
 use starlane_hyperspace::base::provider::{PostgresDatabaseKind, PostgresDatabaseKindDef, Provider, ProviderKindDisc, ProviderKind};
 use starlane_space::parse::CamelCase;
 use starlane_space::status::{ActionRequest, Status};
-use crate::env::{STARLANE_CONTROL_PORT, STARLANE_HOME};
+use crate::env::{STARLANE_CONTROL_PORT, STARLANE_DATA_DIR, STARLANE_HOME};
 
 #[derive(Clone, Debug, EnumDiscriminants, Serialize, Deserialize,Eq,PartialEq,Hash)]
 #[strum_discriminants(vis(pub))]
@@ -79,12 +82,49 @@ pub struct StarlaneConfig {
     pub can_nuke: bool,
     pub can_scorch: bool,
     pub control_port: u16,
+    /// the address the control port listens on
+    #[serde(default = "StarlaneConfig::default_control_bind")]
+    pub control_bind: IpAddr,
+    /// when set, control clients must present a certificate signed by `ca_bundle`
+    #[serde(default)]
+    pub control_client_auth: Option<ClientCertAuth>,
+    /// an embedded Sqlite registry in `STARLANE_DATA_DIR` unless configured otherwise
+    #[serde(default = "StarlaneConfig::default_registry")]
+    pub registry: RegistryKind,
+    /// base64 key that seals `CoreSecret` properties in the registry.  Generate one with
+    /// `SecretKey::generate().to_base64()`
+    #[serde(default)]
+    pub secret_key: Option<String>,
+    /// the HTTP ingress served by the Jump star
+    #[serde(default)]
+    pub web: WebConfig,
     //    pub foundation: ProtoFoundationSettings,
 }
 
+impl StarlaneConfig {
+    fn default_control_bind() -> IpAddr {
+        IpAddr::V4(Ipv4Addr::LOCALHOST)
+    }
+
+    /// a Postgres registry must be provisioned by the Foundation which can't be assumed
+    fn default_registry() -> RegistryKind {
+        RegistryKind::Sqlite {
+            path: format!("{}/registry.sqlite", STARLANE_DATA_DIR.as_str()),
+        }
+    }
+}
+
 impl BaseSubConfig for StarlaneConfig {}
 
-impl RegistryConfig for StarlaneConfig {}
+impl RegistryConfig for StarlaneConfig {
+    fn kind(&self) -> &RegistryKind {
+        &self.registry
+    }
+
+    fn secret_key(&self) -> Option<&String> {
+        self.secret_key.as_ref()
+    }
+}
 
 impl Default for StarlaneConfig {
     fn default() -> Self {
@@ -94,6 +134,11 @@ impl Default for StarlaneConfig {
             can_nuke: false,
             can_scorch: false,
             control_port: STARLANE_CONTROL_PORT.clone(),
+            control_bind: Self::default_control_bind(),
+            control_client_auth: None,
+            registry: Self::default_registry(),
+            secret_key: None,
+            web: Default::default(),
         }
     }
 }
@@ -1,120 +1,579 @@
+use aws_config::meta::region::RegionProviderChain;
+use aws_config::BehaviorVersion;
+use aws_sdk_s3::error::DisplayErrorContext;
+use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::types::{CompletedMultipartUpload, CompletedPart};
+use aws_sdk_s3::Client;
 use clap::Parser;
-use starlane::executor::dialect::filestore::FileStoreCli;
-use starlane::executor::dialect::filestore::FileStoreCommand;
-use std::fs::File;
-use std::io::{Read, Write};
-use std::path::{absolute, PathBuf, StripPrefixError};
-use std::{env, fs, io};
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use starlane_hyperspace::executor::dialect::filestore::FileStoreCommand;
+use starlane_hyperspace::executor::dialect::filestore::{
+    FileKind, FileStat, FileStoreCli, FileStoreErr, RootDir, FILE_STORE_ROOT,
+};
+use std::env;
 use std::env::VarError;
-use std::process::{ExitCode, Termination};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::{Duration, SystemTime};
 use thiserror::Error;
+use tokio::io;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite};
 
+/// the bucket this FileStore lives in.  [FILE_STORE_ROOT] is the key prefix within the bucket
+pub const FILE_STORE_S3_BUCKET: &str = "FILE_STORE_S3_BUCKET";
 
-pub const FILE_STORE_ROOT: &'static str = "FILE_STORE_ROOT";
+/// optional endpoint of an S3 compatible store (MinIO for example) which is then addressed path style
+pub const FILE_STORE_S3_ENDPOINT: &str = "FILE_STORE_S3_ENDPOINT";
 
-pub fn root_dir() -> Result<PathBuf,Error> {
-    Ok(absolute(env::var(FILE_STORE_ROOT)?)?)
-}
+/// size of each part of a multipart upload. S3 requires every part except the last to be at least 5 MiB
+pub const PART_SIZE: usize = 8 * 1024 * 1024;
 
+pub fn root_dir() -> Result<PathBuf, Error> {
+    Ok(PathBuf::from("/").join(env::var(FILE_STORE_ROOT)?))
+}
 
-fn main() -> ExitCode{
-    match run() {
+#[tokio::main]
+async fn main() -> ExitCode {
+    match run().await {
         Ok(_) => ExitCode::SUCCESS,
         Err(err) => {
+            eprintln!("{}", err);
             ExitCode::FAILURE
         }
     }
 }
 
-
-fn run() -> Result<(),Error> {
-
+async fn run() -> Result<(), Error> {
+    env::var(FILE_STORE_ROOT)
+        .map_err(|_| format!("'{}' environment variable is not set.", FILE_STORE_ROOT))?;
+    let bucket = env::var(FILE_STORE_S3_BUCKET).map_err(|_| {
+        format!(
+            "'{}' environment variable is not set.",
+            FILE_STORE_S3_BUCKET
+        )
+    })?;
 
     let cli = FileStoreCli::parse();
+    let store = S3Store::new(client().await, bucket, root_dir()?);
+
     if let FileStoreCommand::Init = cli.command {
-        ensure_dir(&root_dir()?);
-        return Ok(());
+        return store.init().await;
     }
 
+    if !store.bucket_exists().await? {
+        Err(format!(
+            "{} is set but bucket {} does not exist.  Run 'init' command first.",
+            FILE_STORE_S3_BUCKET, store.bucket
+        ))?;
+    }
 
     match cli.command {
-        FileStoreCommand::Init => {
-            todo!()
+        FileStoreCommand::Init => Ok(()),
+        FileStoreCommand::Write { path } => store.write(&path, io::stdin()).await,
+        FileStoreCommand::Append { path } => store.append(&path, io::stdin()).await,
+        FileStoreCommand::Read { path } => store.read(&path, &mut io::stdout()).await,
+        FileStoreCommand::ReadRange { path, offset, len } => {
+            store
+                .read_range(&path, offset, len, &mut io::stdout())
+                .await
         }
-        FileStoreCommand::Write { path } => {
-            todo!()
-
+        FileStoreCommand::Stat { path } => {
+            println!("{}", store.stat(&path).await?);
+            Ok(())
         }
-        FileStoreCommand::Read { path } => {
-            todo!()
+        FileStoreCommand::Rename { from, to } => store.rename(&from, &to).await,
+        FileStoreCommand::Copy { from, to } => store.copy(&from, &to).await,
+        FileStoreCommand::Mkdir { path } => store.mkdir(&path).await,
+        FileStoreCommand::Remove { path } => store.remove(&path).await,
+        FileStoreCommand::List { path } => {
+            for path in store.list(&path).await? {
+                println!("{}", path.display());
+            }
+            Ok(())
         }
-        FileStoreCommand::Mkdir { path } => {
-            todo!()
+        FileStoreCommand::Pwd => {
+            println!("{}", root_dir()?.display());
+            Ok(())
         }
-        FileStoreCommand::Remove { path } => {
-            todo!()
+        FileStoreCommand::Exists { path } => match store.exists(&path).await? {
+            true => Ok(()),
+            false => Err("file does not exist".into()),
+        },
+    }
+}
+
+/// credentials and region come from the standard aws environment
+async fn client() -> Client {
+    let region = RegionProviderChain::default_provider().or_else("us-east-1");
+    let config = aws_config::defaults(BehaviorVersion::latest())
+        .region(region)
+        .load()
+        .await;
+    let mut builder = aws_sdk_s3::config::Builder::from(&config);
+    if let Ok(endpoint) = env::var(FILE_STORE_S3_ENDPOINT) {
+        builder = builder.endpoint_url(endpoint).force_path_style(true);
+    }
+    Client::from_conf(builder.build())
+}
+
+/// maps FileStore paths onto the keys of an S3 bucket.  S3 has no directories: a directory is
+/// any key prefix ending in `/` and `Mkdir` creates an empty marker object so that it can exist
+/// before it has any files
+pub struct S3Store {
+    client: Client,
+    bucket: String,
+    root: RootDir,
+}
+
+impl S3Store {
+    pub fn new(client: Client, bucket: String, root: PathBuf) -> Self {
+        let root = RootDir::new(root);
+        Self {
+            client,
+            bucket,
+            root,
+        }
+    }
+
+    fn key(&self, path: &PathBuf) -> Result<String, Error> {
+        let normed = self.root.norm(path)?;
+        let normed = normed
+            .to_str()
+            .ok_or_else(|| format!("path '{}' is not valid utf-8", path.display()))?;
+        Ok(normed.trim_start_matches('/').to_string())
+    }
+
+    /// the prefix of everything inside the directory at `key`. The root of the bucket is the
+    /// empty key and its prefix is empty too
+    fn dir_key(key: &str) -> String {
+        match key.is_empty() {
+            true => String::new(),
+            false => format!("{}/", key),
+        }
+    }
+
+    pub async fn bucket_exists(&self) -> Result<bool, Error> {
+        match self.client.head_bucket().bucket(&self.bucket).send().await {
+            Ok(_) => Ok(true),
+            Err(err) if err.as_service_error().is_some_and(|e| e.is_not_found()) => Ok(false),
+            Err(err) => Err(s3(err)),
         }
-        FileStoreCommand::List { path: Option::Some(path)} => {
-            todo!()
+    }
+
+    pub async fn init(&self) -> Result<(), Error> {
+        if !self.bucket_exists().await? {
+            self.client
+                .create_bucket()
+                .bucket(&self.bucket)
+                .send()
+                .await
+                .map_err(s3)?;
         }
-        FileStoreCommand::List { path: Option::None } => {
-            todo!()
+        Ok(())
+    }
+
+    pub async fn write<R>(&self, path: &PathBuf, reader: R) -> Result<(), Error>
+    where
+        R: AsyncRead + Unpin,
+    {
+        let key = self.key(path)?;
+        self.upload(&key, reader).await
+    }
+
+    /// S3 objects cannot be appended to so the existing object is streamed into a new upload
+    /// followed by `reader`
+    pub async fn append<R>(&self, path: &PathBuf, reader: R) -> Result<(), Error>
+    where
+        R: AsyncRead + Unpin,
+    {
+        let key = self.key(path)?;
+        let existing: Box<dyn AsyncRead + Unpin + Send> = match self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(&key)
+            .send()
+            .await
+        {
+            Ok(out) => Box::new(out.body.into_async_read()),
+            Err(err) if err.as_service_error().is_some_and(|e| e.is_no_such_key()) => {
+                Box::new(io::empty())
+            }
+            Err(err) => return Err(s3(err)),
+        };
+        self.upload(&key, existing.chain(reader)).await
+    }
+
+    /// a body that fits in one part is a plain `PutObject`, anything larger is sent as a
+    /// multipart upload one [PART_SIZE] part at a time
+    async fn upload<R>(&self, key: &str, mut reader: R) -> Result<(), Error>
+    where
+        R: AsyncRead + Unpin,
+    {
+        let first = read_part(&mut reader).await?;
+        if first.len() < PART_SIZE {
+            self.client
+                .put_object()
+                .bucket(&self.bucket)
+                .key(key)
+                .body(ByteStream::from(first))
+                .send()
+                .await
+                .map_err(s3)?;
+            return Ok(());
         }
-        FileStoreCommand::Pwd =>  {
-            todo!()
+
+        let upload = self
+            .client
+            .create_multipart_upload()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+            .map_err(s3)?;
+        let upload_id = upload
+            .upload_id()
+            .ok_or("CreateMultipartUpload did not return an upload id")?;
+
+        match self.upload_parts(key, upload_id, first, &mut reader).await {
+            Ok(parts) => {
+                self.client
+                    .complete_multipart_upload()
+                    .bucket(&self.bucket)
+                    .key(key)
+                    .upload_id(upload_id)
+                    .multipart_upload(
+                        CompletedMultipartUpload::builder()
+                            .set_parts(Some(parts))
+                            .build(),
+                    )
+                    .send()
+                    .await
+                    .map_err(s3)?;
+                Ok(())
+            }
+            Err(err) => {
+                // the upload failed anyway, the parts are discarded on a best effort basis
+                self.client
+                    .abort_multipart_upload()
+                    .bucket(&self.bucket)
+                    .key(key)
+                    .upload_id(upload_id)
+                    .send()
+                    .await
+                    .ok();
+                Err(err)
+            }
         }
+    }
 
-        FileStoreCommand::Exists { path } => {
-            todo!()
+    async fn upload_parts<R>(
+        &self,
+        key: &str,
+        upload_id: &str,
+        first: Vec<u8>,
+        reader: &mut R,
+    ) -> Result<Vec<CompletedPart>, Error>
+    where
+        R: AsyncRead + Unpin,
+    {
+        let mut parts = vec![];
+        let mut part = first;
+        let mut number = 1;
+        loop {
+            let out = self
+                .client
+                .upload_part()
+                .bucket(&self.bucket)
+                .key(key)
+                .upload_id(upload_id)
+                .part_number(number)
+                .body(ByteStream::from(part))
+                .send()
+                .await
+                .map_err(s3)?;
+            parts.push(
+                CompletedPart::builder()
+                    .set_e_tag(out.e_tag().map(str::to_string))
+                    .part_number(number)
+                    .build(),
+            );
+
+            part = read_part(reader).await?;
+            if part.is_empty() {
+                return Ok(parts);
+            }
+            number += 1;
         }
     }
-}
 
-fn norm(orig: &PathBuf ) -> Result<PathBuf,Error> {
-    let path: PathBuf = match orig.starts_with("/") {
-        true => orig.strip_prefix("/")?.into(),
-        false => orig.clone()
-    };
-    let root_dir = root_dir()?;
-//    let normed= canonicalize(absolute(root_dir.join(path))?)?;
-    let normed : PathBuf = root_dir.join(path).into();
-    let parent = normed.parent().unwrap().canonicalize()?;
+    pub async fn read<W>(&self, path: &PathBuf, writer: &mut W) -> Result<(), Error>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let key = self.key(path)?;
+        let out = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+            .map_err(s3)?;
+        io::copy(&mut out.body.into_async_read(), writer).await?;
+        Ok(())
+    }
+
+    pub async fn read_range<W>(
+        &self,
+        path: &PathBuf,
+        offset: u64,
+        len: Option<u64>,
+        writer: &mut W,
+    ) -> Result<(), Error>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let range = match len {
+            Some(0) => return Ok(()),
+            Some(len) => format!("bytes={}-{}", offset, offset + len - 1),
+            None => format!("bytes={}-", offset),
+        };
+        let key = self.key(path)?;
+        let out = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .range(range)
+            .send()
+            .await
+            .map_err(s3)?;
+        io::copy(&mut out.body.into_async_read(), writer).await?;
+        Ok(())
+    }
 
-    if let Option::Some(root) = root_dir.parent() {
-        if parent == root {
-            return Err(Error::String(format!("illegal path '{}' escapes filesystem boundaries", orig.display())));
+    pub async fn stat(&self, path: &PathBuf) -> Result<FileStat, Error> {
+        let key = self.key(path)?;
+        match self
+            .client
+            .head_object()
+            .bucket(&self.bucket)
+            .key(&key)
+            .send()
+            .await
+        {
+            Ok(out) => Ok(FileStat {
+                kind: FileKind::File,
+                size: out.content_length().unwrap_or_default() as u64,
+                modified: out.last_modified().map(|modified| {
+                    SystemTime::UNIX_EPOCH + Duration::from_secs(modified.secs() as u64)
+                }),
+            }),
+            Err(err) if err.as_service_error().is_some_and(|e| e.is_not_found()) => {
+                match self.is_dir(&key).await? {
+                    true => Ok(FileStat {
+                        kind: FileKind::Dir,
+                        size: 0,
+                        modified: None,
+                    }),
+                    false => Err(format!("'{}' does not exist", path.display()).into()),
+                }
+            }
+            Err(err) => Err(s3(err)),
         }
     }
 
-    if !parent.starts_with(&root_dir){
-        return Err(Error::String(format!("illegal path '{}' escapes filesystem boundaries", orig.display())));
+    pub async fn exists(&self, path: &PathBuf) -> Result<bool, Error> {
+        let key = self.key(path)?;
+        match self
+            .client
+            .head_object()
+            .bucket(&self.bucket)
+            .key(&key)
+            .send()
+            .await
+        {
+            Ok(_) => Ok(true),
+            Err(err) if err.as_service_error().is_some_and(|e| e.is_not_found()) => {
+                self.is_dir(&key).await
+            }
+            Err(err) => Err(s3(err)),
+        }
     }
 
-    Ok(normed)
-}
+    /// a directory exists if its marker or anything below it does
+    async fn is_dir(&self, key: &str) -> Result<bool, Error> {
+        let out = self
+            .client
+            .list_objects_v2()
+            .bucket(&self.bucket)
+            .prefix(Self::dir_key(key))
+            .max_keys(1)
+            .send()
+            .await
+            .map_err(s3)?;
+        Ok(!out.contents().is_empty())
+    }
 
+    pub async fn copy(&self, from: &PathBuf, to: &PathBuf) -> Result<(), Error> {
+        let from = self.key(from)?;
+        let to = self.key(to)?;
+        self.client
+            .copy_object()
+            .bucket(&self.bucket)
+            .copy_source(format!(
+                "{}/{}",
+                self.bucket,
+                utf8_percent_encode(&from, COPY_SOURCE)
+            ))
+            .key(to)
+            .send()
+            .await
+            .map_err(s3)?;
+        Ok(())
+    }
 
-fn ensure_dir(dir: &PathBuf ) -> Result<(),Error> {
-   if dir.exists() && dir.is_dir(){
+    pub async fn rename(&self, from: &PathBuf, to: &PathBuf) -> Result<(), Error> {
+        self.copy(from, to).await?;
+        let from = self.key(from)?;
+        self.client
+            .delete_object()
+            .bucket(&self.bucket)
+            .key(from)
+            .send()
+            .await
+            .map_err(s3)?;
         Ok(())
-    } else {
-       fs::create_dir_all(dir)?;
-       Ok(())
+    }
+
+    pub async fn mkdir(&self, path: &PathBuf) -> Result<(), Error> {
+        let key = self.key(path)?;
+        // the root of the bucket always exists and has no marker
+        if key.is_empty() {
+            return Ok(());
+        }
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(Self::dir_key(&key))
+            .body(ByteStream::from_static(&[]))
+            .send()
+            .await
+            .map_err(s3)?;
+        Ok(())
+    }
+
+    /// removes a file or an empty directory
+    pub async fn remove(&self, path: &PathBuf) -> Result<(), Error> {
+        let key = self.key(path)?;
+        let dir = Self::dir_key(&key);
+        let remove = match self
+            .client
+            .head_object()
+            .bucket(&self.bucket)
+            .key(&key)
+            .send()
+            .await
+        {
+            Ok(_) => key,
+            Err(err) if err.as_service_error().is_some_and(|e| e.is_not_found()) => {
+                let out = self
+                    .client
+                    .list_objects_v2()
+                    .bucket(&self.bucket)
+                    .prefix(&dir)
+                    .max_keys(2)
+                    .send()
+                    .await
+                    .map_err(s3)?;
+                let mut keys = out.contents().iter().filter_map(|object| object.key());
+                match (keys.next(), keys.next()) {
+                    (None, _) => Err(format!("'{}' does not exist", path.display()))?,
+                    (Some(marker), None) if marker == dir => dir,
+                    _ => Err(format!("directory '{}' is not empty", path.display()))?,
+                }
+            }
+            Err(err) => return Err(s3(err)),
+        };
+        self.client
+            .delete_object()
+            .bucket(&self.bucket)
+            .key(remove)
+            .send()
+            .await
+            .map_err(s3)?;
+        Ok(())
+    }
+
+    /// the files and directories directly inside `path`
+    pub async fn list(&self, path: &PathBuf) -> Result<Vec<PathBuf>, Error> {
+        let key = self.key(path)?;
+        let dir = Self::dir_key(&key);
+        let mut pages = self
+            .client
+            .list_objects_v2()
+            .bucket(&self.bucket)
+            .prefix(&dir)
+            .delimiter("/")
+            .into_paginator()
+            .send();
+
+        let mut paths = vec![];
+        while let Some(page) = pages.next().await {
+            let page = page.map_err(s3)?;
+            let dirs = page.common_prefixes().iter().filter_map(|p| p.prefix());
+            let files = page
+                .contents()
+                .iter()
+                .filter_map(|object| object.key())
+                .filter(|key| *key != dir);
+            for key in dirs.chain(files) {
+                paths.push(PathBuf::from("/").join(key.trim_end_matches('/')));
+            }
+        }
+        Ok(paths)
     }
 }
 
+/// characters of a key that must be escaped in the `x-amz-copy-source` header.  `/` is left alone
+/// since it separates the key's segments
+const COPY_SOURCE: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'/')
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~');
+
+/// reads up to [PART_SIZE] bytes, a short part means the reader is exhausted
+async fn read_part<R>(reader: &mut R) -> Result<Vec<u8>, Error>
+where
+    R: AsyncRead + Unpin,
+{
+    let mut part = Vec::with_capacity(PART_SIZE);
+    reader.take(PART_SIZE as u64).read_to_end(&mut part).await?;
+    Ok(part)
+}
+
+fn s3<E>(err: E) -> Error
+where
+    E: std::error::Error + 'static,
+{
+    Error::S3(DisplayErrorContext(err).to_string())
+}
 
 #[derive(Error, Debug)]
-pub enum Error{
-    #[error("could not access local filesystem")]
-    FileSys(#[from] io::Error),
+pub enum Error {
+    #[error("could not access local io: {0}")]
+    Io(#[from] std::io::Error),
     #[error("{0}")]
-    String( String),
+    S3(String),
     #[error("{0}")]
-    Path(#[from] StripPrefixError),
+    String(String),
+    #[error("{0}")]
+    FileStore(#[from] FileStoreErr),
     #[error("{0}")]
     VarError(#[from] VarError),
-
 }
 
 impl From<String> for Error {
@@ -123,36 +582,432 @@ impl From<String> for Error {
     }
 }
 
-
 impl From<&str> for Error {
     fn from(value: &str) -> Self {
         Self::String(value.to_string())
     }
 }
 
-/*
-pub fn join( path: PathBuf, ext: PathBuf) -> Result<PathBuf, Error>{
+/// these tests run against a mock S3 endpoint standing in for MinIO or AWS
+#[cfg(test)]
+pub mod test {
+    use crate::{S3Store, PART_SIZE};
+    use aws_sdk_s3::config::{BehaviorVersion, Credentials, Region};
+    use aws_sdk_s3::Client;
+    use starlane_hyperspace::executor::dialect::filestore::FileKind;
+    use std::path::PathBuf;
+    use std::time::{Duration, SystemTime};
+    use wiremock::matchers::{header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn store(server: &MockServer) -> S3Store {
+        store_at(server, "/root")
+    }
 
-    let ext : PathBuf = match ext.starts_with("/") {
-        true => ext.strip_prefix("/")?.into(),
-        false => ext
-    };
-    let joined = path.join(ext);
+    fn store_at(server: &MockServer, root: &str) -> S3Store {
+        let config = aws_sdk_s3::Config::builder()
+            .behavior_version(BehaviorVersion::latest())
+            .region(Region::new("us-east-1"))
+            .credentials_provider(Credentials::new("test", "test", None, None, "test"))
+            .endpoint_url(server.uri())
+            .force_path_style(true)
+            .build();
+        S3Store::new(
+            Client::from_conf(config),
+            "bucket".to_string(),
+            PathBuf::from(root),
+        )
+    }
 
-    println!("JOINED {}", joined.display());
-    Ok(joined)
-}
+    /// a ListObjectsV2 response listing `keys`
+    fn list_result(keys: &[&str]) -> ResponseTemplate {
+        let contents: String = keys
+            .iter()
+            .map(|key| format!("<Contents><Key>{}</Key><Size>0</Size></Contents>", key))
+            .collect();
+        ResponseTemplate::new(200).set_body_string(format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Name>bucket</Name><KeyCount>{}</KeyCount><MaxKeys>1000</MaxKeys><IsTruncated>false</IsTruncated>
+  {}
+</ListBucketResult>"#,
+            keys.len(),
+            contents
+        ))
+    }
 
- */
+    fn copy_result() -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_string(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<CopyObjectResult><ETag>"etag"</ETag><LastModified>2024-01-01T00:00:00.000Z</LastModified></CopyObjectResult>"#,
+        )
+    }
 
+    #[tokio::test]
+    pub async fn test_write() {
+        let server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/bucket/root/small.txt"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let store = store(&server);
+        store
+            .write(&"/small.txt".into(), "hello".as_bytes())
+            .await
+            .unwrap();
+    }
 
+    #[tokio::test]
+    pub async fn test_write_multipart() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/bucket/root/big.bin"))
+            .and(query_param("uploads", ""))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<InitiateMultipartUploadResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Bucket>bucket</Bucket><Key>root/big.bin</Key><UploadId>upload-1</UploadId>
+</InitiateMultipartUploadResult>"#,
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/bucket/root/big.bin"))
+            .and(query_param("uploadId", "upload-1"))
+            .respond_with(ResponseTemplate::new(200).insert_header("ETag", "\"etag\""))
+            .expect(3)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/bucket/root/big.bin"))
+            .and(query_param("uploadId", "upload-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<CompleteMultipartUploadResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Bucket>bucket</Bucket><Key>root/big.bin</Key><ETag>"etag-3"</ETag>
+</CompleteMultipartUploadResult>"#,
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let store = store(&server);
+        let body = vec![7u8; PART_SIZE * 2 + 1024];
+        store
+            .write(&"/big.bin".into(), body.as_slice())
+            .await
+            .unwrap();
+    }
 
-#[cfg(test)]
-pub mod test {
-    #[test]
-    pub fn test() {
+    #[tokio::test]
+    pub async fn test_list() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/bucket/"))
+            .and(query_param("list-type", "2"))
+            .and(query_param("prefix", "root/dir/"))
+            .and(query_param("delimiter", "/"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Name>bucket</Name><Prefix>root/dir/</Prefix><Delimiter>/</Delimiter>
+  <KeyCount>3</KeyCount><MaxKeys>1000</MaxKeys><IsTruncated>false</IsTruncated>
+  <Contents><Key>root/dir/</Key><Size>0</Size></Contents>
+  <Contents><Key>root/dir/a.txt</Key><Size>5</Size></Contents>
+  <CommonPrefixes><Prefix>root/dir/sub/</Prefix></CommonPrefixes>
+</ListBucketResult>"#,
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let store = store(&server);
+        let paths = store.list(&"/dir".into()).await.unwrap();
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from("/root/dir/sub"),
+                PathBuf::from("/root/dir/a.txt")
+            ]
+        );
+    }
+
+    #[tokio::test]
+    pub async fn test_write_multipart_abort() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/bucket/root/big.bin"))
+            .and(query_param("uploads", ""))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<InitiateMultipartUploadResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Bucket>bucket</Bucket><Key>root/big.bin</Key><UploadId>upload-1</UploadId>
+</InitiateMultipartUploadResult>"#,
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/bucket/root/big.bin"))
+            .and(query_param("uploadId", "upload-1"))
+            .respond_with(ResponseTemplate::new(400).set_body_string(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<Error><Code>InvalidPart</Code><Message>rejected</Message></Error>"#,
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/bucket/root/big.bin"))
+            .and(query_param("uploadId", "upload-1"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let store = store(&server);
+        let body = vec![7u8; PART_SIZE + 1];
+        assert!(store
+            .write(&"/big.bin".into(), body.as_slice())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    pub async fn test_read() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/bucket/root/a.txt"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("hello"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let store = store(&server);
+        let mut out = vec![];
+        store.read(&"/a.txt".into(), &mut out).await.unwrap();
+        assert_eq!(out, b"hello");
+    }
+
+    #[tokio::test]
+    pub async fn test_read_range() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/bucket/root/a.txt"))
+            .and(header("range", "bytes=1-3"))
+            .respond_with(ResponseTemplate::new(206).set_body_string("ell"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let store = store(&server);
+        let mut out = vec![];
+        store
+            .read_range(&"/a.txt".into(), 1, Some(3), &mut out)
+            .await
+            .unwrap();
+        assert_eq!(out, b"ell");
+
+        // an empty range never reaches S3
+        store
+            .read_range(&"/a.txt".into(), 1, Some(0), &mut out)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    pub async fn test_stat() {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/bucket/root/a.txt"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-length", "5")
+                    .insert_header("last-modified", "Mon, 01 Jan 2024 00:00:00 GMT"),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("HEAD"))
+            .and(path("/bucket/root/dir"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/bucket/"))
+            .and(query_param("prefix", "root/dir/"))
+            .respond_with(list_result(&["root/dir/"]))
+            .mount(&server)
+            .await;
+
+        let store = store(&server);
+        let stat = store.stat(&"/a.txt".into()).await.unwrap();
+        assert_eq!(stat.kind, FileKind::File);
+        assert_eq!(stat.size, 5);
+        assert_eq!(
+            stat.modified,
+            Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1704067200))
+        );
+
+        let stat = store.stat(&"/dir".into()).await.unwrap();
+        assert_eq!(stat.kind, FileKind::Dir);
+    }
+
+    #[tokio::test]
+    pub async fn test_exists() {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/bucket/root/a.txt"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+        Mock::given(method("HEAD"))
+            .and(path("/bucket/root/missing"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/bucket/"))
+            .and(query_param("prefix", "root/missing/"))
+            .respond_with(list_result(&[]))
+            .mount(&server)
+            .await;
+
+        let store = store(&server);
+        assert!(store.exists(&"/a.txt".into()).await.unwrap());
+        assert!(!store.exists(&"/missing".into()).await.unwrap());
+    }
 
+    #[tokio::test]
+    pub async fn test_remove() {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/bucket/root/a.txt"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/bucket/root/a.txt"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+        for dir in ["empty", "full"] {
+            Mock::given(method("HEAD"))
+                .and(path(format!("/bucket/root/{}", dir)))
+                .respond_with(ResponseTemplate::new(404))
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("GET"))
+            .and(path("/bucket/"))
+            .and(query_param("prefix", "root/empty/"))
+            .respond_with(list_result(&["root/empty/"]))
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/bucket/root/empty/"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/bucket/"))
+            .and(query_param("prefix", "root/full/"))
+            .respond_with(list_result(&["root/full/", "root/full/a.txt"]))
+            .mount(&server)
+            .await;
+
+        let store = store(&server);
+        store.remove(&"/a.txt".into()).await.unwrap();
+        store.remove(&"/empty".into()).await.unwrap();
+        assert!(store.remove(&"/full".into()).await.is_err());
+    }
 
+    #[tokio::test]
+    pub async fn test_copy() {
+        let server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/bucket/root/b%20c.txt"))
+            .and(header("x-amz-copy-source", "bucket/root/a%20b.txt"))
+            .respond_with(copy_result())
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let store = store(&server);
+        store
+            .copy(&"/a b.txt".into(), &"/b c.txt".into())
+            .await
+            .unwrap();
     }
 
-}
\ No newline at end of file
+    #[tokio::test]
+    pub async fn test_rename() {
+        let server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/bucket/root/b.txt"))
+            .and(header("x-amz-copy-source", "bucket/root/a.txt"))
+            .respond_with(copy_result())
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/bucket/root/a.txt"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let store = store(&server);
+        store
+            .rename(&"/a.txt".into(), &"/b.txt".into())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    pub async fn test_mkdir() {
+        let server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/bucket/root/dir/"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let store = store(&server);
+        store.mkdir(&"/dir".into()).await.unwrap();
+    }
+
+    /// a store rooted at the top of the bucket lists with an empty prefix
+    #[tokio::test]
+    pub async fn test_list_bucket_root() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/bucket/"))
+            .and(query_param("list-type", "2"))
+            .respond_with(list_result(&["a.txt"]))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let store = store_at(&server, "/");
+        let paths = store.list(&"/".into()).await.unwrap();
+        assert_eq!(paths, vec![PathBuf::from("/a.txt")]);
+
+        let requests = server.received_requests().await.unwrap();
+        let prefix = requests
+            .first()
+            .unwrap()
+            .url
+            .query_pairs()
+            .find(|(name, _)| name == "prefix")
+            .map(|(_, prefix)| prefix.to_string())
+            .unwrap_or_default();
+        assert_eq!(prefix, "");
+    }
+}
@@ -1,14 +1,13 @@
 use clap::Parser;
-use starlane::executor::dialect::filestore::{FileStoreCli, RootDir, FILE_STORE_ROOT};
-use starlane::executor::dialect::filestore::FileStoreCommand;
-use std::fs::File;
-use std::io::{Read, Write};
+use starlane_hyperspace::executor::dialect::filestore::{FileStat, FileStoreCli, FileStoreErr, RootDir, FILE_STORE_ROOT};
+use starlane_hyperspace::executor::dialect::filestore::FileStoreCommand;
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom};
 use std::path::{absolute, PathBuf, StripPrefixError};
 use std::{env, fs, io};
 use std::env::VarError;
-use std::process::{ExitCode, Termination};
+use std::process::ExitCode;
 use thiserror::Error;
-use starlane::err::HypErr;
 
 
 
@@ -21,7 +20,7 @@ fn main() -> ExitCode{
     match run() {
         Ok(_) => ExitCode::SUCCESS,
         Err(err) => {
-            eprintln!("{}",err.to_string());
+            eprintln!("{}",err);
             ExitCode::FAILURE
         }
     }
@@ -34,7 +33,7 @@ fn run() -> Result<(),Error> {
 
     let cli = FileStoreCli::parse();
     if let FileStoreCommand::Init = cli.command {
-        ensure_dir(&root_dir()?);
+        ensure_dir(&root_dir()?)?;
         return Ok(());
     }
 
@@ -53,12 +52,41 @@ fn run() -> Result<(),Error> {
             io::copy(&mut io::stdin(), &mut file)?;
             Ok(())
         }
+        FileStoreCommand::Append { path } => {
+            let file = norm(&path)?;
+            let mut file = OpenOptions::new().create(true).append(true).open(file)?;
+            io::copy(&mut io::stdin(), &mut file)?;
+            Ok(())
+        }
         FileStoreCommand::Read { path } => {
             let file = norm(&path)?;
             let mut file = File::open(file)?;
             io::copy(&mut file, &mut io::stdout())?;
             Ok(())
         }
+        FileStoreCommand::ReadRange { path, offset, len } => {
+            let file = norm(&path)?;
+            let mut file = File::open(file)?;
+            file.seek(SeekFrom::Start(offset))?;
+            match len {
+                None => io::copy(&mut file, &mut io::stdout())?,
+                Some(len) => io::copy(&mut file.take(len), &mut io::stdout())?,
+            };
+            Ok(())
+        }
+        FileStoreCommand::Stat { path } => {
+            let file = norm(&path)?;
+            println!("{}", FileStat::from(&fs::metadata(file)?));
+            Ok(())
+        }
+        FileStoreCommand::Rename { from, to } => {
+            fs::rename(norm(&from)?, norm(&to)?)?;
+            Ok(())
+        }
+        FileStoreCommand::Copy { from, to } => {
+            fs::copy(norm(&from)?, norm(&to)?)?;
+            Ok(())
+        }
         FileStoreCommand::Mkdir { path } => {
             let dir = norm(&path)?;
             fs::create_dir_all(dir)?;
@@ -76,8 +104,8 @@ fn run() -> Result<(),Error> {
         }
         FileStoreCommand::List { path } => {
             let file = norm(&path)?;
-            for f in file.read_dir()?.into_iter().map(|r|r.unwrap()) {
-                println!("{}",f.path().display());
+            for f in file.read_dir()? {
+                println!("{}",f?.path().display());
             }
             Ok(())
         }
@@ -116,7 +144,7 @@ fn ensure_dir(dir: &PathBuf ) -> Result<(),Error> {
 
 #[derive(Error, Debug)]
 pub enum Error{
-    #[error("could not access local filesystem")]
+    #[error("could not access local filesystem: {0}")]
     FileSys(#[from] io::Error),
     #[error("{0}")]
     String( String),
@@ -125,7 +153,7 @@ pub enum Error{
     #[error("{0}")]
     VarError(#[from] VarError),
     #[error("{0}")]
-    ThisErr(#[from] HypErr)
+    FileStore(#[from] FileStoreErr)
 }
 
 impl From<String> for Error {
@@ -91,6 +91,7 @@ where Self: 'static,
                 builder.add_point("bin", true, true).unwrap();
                 builder.build().unwrap()
             }
+            BaseKind::User | BaseKind::UserBase => crate::properties::properties_config(kind),
             _ => builder.build().unwrap(),
         }
     }
@@ -99,7 +100,11 @@ where Self: 'static,
     async fn global_registry(&self) -> Result<&Registry, Self::Err>;
     async fn star_registry(&self, star: &StarKey) -> Result<Registry, Self::Err>;
     fn artifact_hub(&self) -> Artifacts;
-    async fn start_services(&self, gate: &Arc<HyperGateSelector>) {}
+    /// start any servers the platform exposes (for example a control port). Called once the
+    /// machine's gates exist, an error aborts the machine's startup
+    async fn start_services(&self, gate: &Arc<HyperGateSelector>) -> Result<(), Self::Err> {
+        Ok(())
+    }
     fn logger(&self) -> Logger {
         logger!()
     }
@@ -35,7 +35,10 @@ pub enum ProviderKind {
     PostgresService,
     /// depends upon a readied [ProviderKind::PostgresService]
     PostgresDatabase(PostgresDatabaseKind),
-    /// depends upon [ProviderKind::PostgresDatabase]::[PostgresDatabaseKindDef::Registry]
+    /// depends upon [ProviderKind::PostgresDatabase]::[PostgresDatabaseKindDef::Registry] when
+    /// [crate::registry::RegistryKind::Postgres] is selected.  The embedded
+    /// [crate::registry::RegistryKind::Sqlite] and [crate::registry::RegistryKind::Memory]
+    /// registries have no dependencies
     Registry,
     /// [ProviderKind::_Ext] defines a new [ProviderKindDisc] that is not builtin to Starlane
     _Ext(CamelCase),
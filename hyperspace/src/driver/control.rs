@@ -3,9 +3,10 @@ use crate::driver::{
     HyperSkel, Particle, ParticleErr, ParticleRouter, ParticleSphere, ParticleSphereInner,
 };
 use crate::hyperlane::{
-    AnonHyperAuthenticatorAssignEndPoint, FromTransform, HopTransform, HyperClient, HyperGreeter,
-    Hyperway, HyperwayConfigurator, HyperwayEndpointFactory, HyperwayInterchange, HyperwayStub,
-    InterchangeGate, TransportTransform,
+    AnonHyperAuthenticatorAssignEndPoint, CredentialHyperAuthenticator, FromTransform,
+    HopTransform, HyperAuthenticator, HyperClient, HyperGreeter, Hyperway, HyperwayConfigurator,
+    HyperwayEndpointFactory, HyperwayInterchange, HyperwayStub, InterchangeGate,
+    TransportTransform,
 };
 use crate::base::Platform;
 use crate::star::{HyperStarSkel, LayerInjectionRouter};
@@ -22,7 +23,7 @@ use starlane_space::command::direct::create::{
 use starlane_space::command::RawCommand;
 use starlane_space::config::bind::BindConfig;
 use starlane_space::err::{CoreReflector, SpaceErr};
-use starlane_space::hyper::{ControlPattern, Greet, InterchangeKind};
+use starlane_space::hyper::{ControlPattern, Greet, InterchangeKind, Knock};
 use starlane_space::kind::{BaseKind, Kind, StarSub};
 use starlane_space::loc::{Layer, PointFactory, Surface, ToSurface};
 use starlane_space::log::{Logger, Tracker};
@@ -37,6 +38,7 @@ use starlane_space::wave::exchange::asynch::{
     Exchanger, ProtoTransmitter, ProtoTransmitterBuilder, Router, TraversalRouter,
 };
 use starlane_space::wave::exchange::SetStrategy;
+use starlane_space::HYPERUSER;
 use starlane_space::wave::{Agent, DirectedProto, PongCore, ToRecipients, Wave, WaveVariantDef};
 use std::marker::PhantomData;
 use std::str::FromStr;
@@ -158,10 +160,18 @@ impl Driver for ControlDriver {
             self.fabric_routers.clone(),
             ctx,
         ));
-        let auth = AnonHyperAuthenticatorAssignEndPoint::new(
-            remote_point_factory,
-            self.skel.driver.logger.clone(),
-        );
+        let auth = ControlAuthenticator {
+            anon: AnonHyperAuthenticatorAssignEndPoint::new(
+                remote_point_factory.clone(),
+                self.skel.driver.logger.clone(),
+            ),
+            credentials: CredentialHyperAuthenticator::new(
+                HYPERUSER.parent().unwrap(),
+                self.skel.star.registry.clone(),
+                remote_point_factory,
+                self.skel.driver.logger.clone(),
+            ),
+        };
         let point = skel.point.clone();
         let mut interchange = HyperwayInterchange::new(point, self.skel.driver.logger.clone());
         let hyperway = Hyperway::new(
@@ -278,6 +288,24 @@ impl Driver for ControlDriver {
     }
 }
 
+/// a knock carrying credentials connects as the `User` they belong to (see
+/// [CredentialHyperAuthenticator]) while a knock without any auth is let in anonymously
+#[derive(Clone)]
+pub struct ControlAuthenticator {
+    anon: AnonHyperAuthenticatorAssignEndPoint,
+    credentials: CredentialHyperAuthenticator,
+}
+
+#[async_trait]
+impl HyperAuthenticator for ControlAuthenticator {
+    async fn auth(&self, knock: Knock) -> Result<HyperwayStub, SpaceErr> {
+        match *knock.auth {
+            Substance::Empty => self.anon.auth(knock).await,
+            _ => self.credentials.auth(knock).await,
+        }
+    }
+}
+
 pub struct ControlCreator {
     pub skel: HyperSkel,
     pub fabric_routers: Arc<DashMap<Point, LayerInjectionRouter>>,
@@ -359,6 +387,7 @@ impl HyperGreeter for ControlGreeter {
             agent: stub.agent.clone(),
             hop: self.skel.driver.point.clone().to_surface(),
             transport: stub.remote.clone().with_layer(Layer::Portal),
+            scope: stub.scope.clone(),
         })
     }
 }
@@ -1,17 +1,27 @@
 use crate::driver::{
-    Driver, DriverAvail, DriverCtx, DriverErr, DriverSkel, HyperDriverFactory, Particle,
-    ParticleSphere, StdParticleErr,
+    Driver, DriverAvail, DriverCtx, DriverErr, DriverHandler, DriverSkel, DriverStatus,
+    HyperDriverFactory, Particle, ParticleDriverErr, ParticleSphere,
 };
 
-use crate::base::Platform;
+use crate::executor::dialect::filestore::{FileKind, FileStoreApi};
+use crate::service::ServiceKind;
 use crate::star::HyperStarSkel;
 use async_trait::async_trait;
-use starlane_macros::{handler, DirectedHandler};
-use starlane_space::kind::{BaseKind, Kind};
+use starlane_macros::{handler, route, DirectedHandler};
+use starlane_space::command::common::StateSrc;
+use starlane_space::err::SpaceErr;
+use starlane_space::hyper::HyperSubstance;
+use starlane_space::kind::{BaseKind, FileSubKind, Kind};
 use starlane_space::point::Point;
 use starlane_space::selector::KindSelector;
-use starlane_space::wave::exchange::asynch::DirectedHandler;
-use std::str::FromStr;
+use starlane_space::substance::{Bin, Substance, SubstanceList};
+use starlane_space::wave::exchange::asynch::{DirectedHandler, InCtx};
+use std::path::PathBuf;
+
+/// the directory of the FileStore particle at `point` within the FileStore service
+fn filestore_dir(point: &Point) -> PathBuf {
+    point.to_md5().into()
+}
 
 pub struct FileStoreDriverFactory {
     pub avail: DriverAvail,
@@ -33,23 +43,30 @@ impl HyperDriverFactory for FileStoreDriverFactory {
         KindSelector::from_base(BaseKind::FileStore)
     }
 
+    fn avail(&self) -> DriverAvail {
+        self.avail.clone()
+    }
+
     async fn create(
         &self,
         _: HyperStarSkel,
-        _: DriverSkel,
+        skel: DriverSkel,
         _: DriverCtx,
     ) -> Result<Box<dyn Driver>, DriverErr> {
-        Ok(Box::new(FileStoreDriver::new(self.avail.clone())))
+        let service = skel.select_service(ServiceKind::FileStore).await?;
+        let api = service.filestore()?.api();
+        Ok(Box::new(FileStoreDriver::new(self.avail.clone(), api)))
     }
 }
 
 pub struct FileStoreDriver {
     pub avail: DriverAvail,
+    api: FileStoreApi,
 }
 
 impl FileStoreDriver {
-    pub fn new(avail: DriverAvail) -> Self {
-        Self { avail }
+    pub fn new(avail: DriverAvail, api: FileStoreApi) -> Self {
+        Self { avail, api }
     }
 }
 
@@ -59,24 +76,78 @@ impl Driver for FileStoreDriver {
         Kind::FileStore
     }
 
+    fn avail(&self) -> DriverAvail {
+        self.avail.clone()
+    }
+
+    async fn init(&mut self, skel: DriverSkel, _: DriverCtx) -> Result<(), DriverErr> {
+        skel.logger
+            .result(skel.status_tx.send(DriverStatus::Init).await)
+            .unwrap_or_default();
+
+        self.api.init().await?;
+
+        skel.logger
+            .result(skel.status_tx.send(DriverStatus::Ready).await)
+            .unwrap_or_default();
+        Ok(())
+    }
+
     async fn particle(&self, point: &Point) -> Result<ParticleSphere, DriverErr> {
-        let base = FileStore::restore((), (), ());
+        let filestore = FileStore::restore(point.clone(), (), self.api.clone());
+        Ok(filestore.sphere().map_err(ParticleDriverErr::unwrap)?)
+    }
 
-        Ok(base.sphere()?)
+    async fn handler(&self) -> Box<dyn DriverHandler> {
+        Box::new(FileStoreDriverHandler::restore(self.api.clone()))
     }
 }
 
 #[derive(DirectedHandler)]
-pub struct FileStore;
+pub struct FileStoreDriverHandler {
+    api: FileStoreApi,
+}
+
+impl FileStoreDriverHandler {
+    fn restore(api: FileStoreApi) -> Self {
+        Self { api }
+    }
+}
+
+impl DriverHandler for FileStoreDriverHandler {}
+
+#[handler]
+impl FileStoreDriverHandler {
+    #[route("Hyp<Assign>")]
+    async fn assign(&self, ctx: InCtx<'_, HyperSubstance>) -> Result<(), ParticleDriverErr> {
+        if let HyperSubstance::Assign(assign) = ctx.input {
+            let dir = filestore_dir(&assign.details.stub.point);
+            self.api.mkdir(&dir).await.map_err(DriverErr::from)?;
+            Ok(())
+        } else {
+            Err(DriverErr::String(
+                "FileStoreDriver expected Assign".to_string(),
+            ))?
+        }
+    }
+}
+
+/// a FileStore particle owns a directory of the FileStore service that holds every [File]
+/// below its `:/` root
+#[derive(DirectedHandler)]
+pub struct FileStore {
+    point: Point,
+    api: FileStoreApi,
+}
 
 impl Particle for FileStore {
-    type Skel = ();
+    type Skel = Point;
     type Ctx = ();
-    type State = ();
-    type Err = StdParticleErr;
+    type State = FileStoreApi;
+    type Err = ParticleDriverErr;
 
-    fn restore(_: Self::Skel, _: Self::Ctx, _: Self::State) -> Self {
-        FileStore
+    fn restore(point: Self::Skel, _: Self::Ctx, api: Self::State) -> Self {
+        FileStore { point, api }
     }
 
     fn sphere(self) -> Result<ParticleSphere, Self::Err> {
@@ -85,4 +156,290 @@ impl Particle for FileStore {
 }
 
 #[handler]
-impl FileStore {}
+impl FileStore {
+    /// removes the FileStore's directory which fails unless its files were deleted first
+    #[route("Cmd<Delete>")]
+    pub async fn delete(&self, _: InCtx<'_, ()>) -> Result<(), ParticleDriverErr> {
+        self.api
+            .remove(&filestore_dir(&self.point))
+            .await
+            .map_err(DriverErr::from)?;
+        Ok(())
+    }
+}
+
+pub struct FileDriverFactory;
+
+impl FileDriverFactory {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl HyperDriverFactory for FileDriverFactory {
+    fn kind(&self) -> Kind {
+        Kind::File(FileSubKind::File)
+    }
+
+    fn selector(&self) -> KindSelector {
+        KindSelector::from_base(BaseKind::File)
+    }
+
+    async fn create(
+        &self,
+        _: HyperStarSkel,
+        skel: DriverSkel,
+        _: DriverCtx,
+    ) -> Result<Box<dyn Driver>, DriverErr> {
+        let service = skel.select_service(ServiceKind::FileStore).await?;
+        let api = service.filestore()?.api();
+        Ok(Box::new(FileDriver::new(api)))
+    }
+}
+
+/// drives both `File<File>` and `File<Dir>`
+pub struct FileDriver {
+    api: FileStoreApi,
+}
+
+impl FileDriver {
+    pub fn new(api: FileStoreApi) -> Self {
+        Self { api }
+    }
+}
+
+#[async_trait]
+impl Driver for FileDriver {
+    fn kind(&self) -> Kind {
+        Kind::File(FileSubKind::File)
+    }
+
+    async fn particle(&self, point: &Point) -> Result<ParticleSphere, DriverErr> {
+        Ok(File::of(&self.api, point)?
+            .sphere()
+            .map_err(ParticleDriverErr::unwrap)?)
+    }
+
+    async fn handler(&self) -> Box<dyn DriverHandler> {
+        Box::new(FileDriverHandler::restore(self.api.clone()))
+    }
+}
+
+#[derive(DirectedHandler)]
+pub struct FileDriverHandler {
+    api: FileStoreApi,
+}
+
+impl FileDriverHandler {
+    fn restore(api: FileStoreApi) -> Self {
+        Self { api }
+    }
+}
+
+impl DriverHandler for FileDriverHandler {}
+
+#[handler]
+impl FileDriverHandler {
+    /// creates the backing directory of a `File<Dir>` or stores the state of a `File<File>`
+    #[route("Hyp<Assign>")]
+    async fn assign(&self, ctx: InCtx<'_, HyperSubstance>) -> Result<(), ParticleDriverErr> {
+        if let HyperSubstance::Assign(assign) = ctx.input {
+            let file = File::of(&self.api, &assign.details.stub.point)?;
+            match &assign.details.stub.kind {
+                Kind::File(FileSubKind::Dir) => file.mkdir().await?,
+                _ => {
+                    let content = match &assign.state {
+                        StateSrc::None => vec![],
+                        StateSrc::Subst(substance) => content(*substance.clone())?,
+                    };
+                    file.write(content).await?
+                }
+            }
+            Ok(())
+        } else {
+            Err(DriverErr::String("FileDriver expected Assign".to_string()))?
+        }
+    }
+}
+
+/// the bytes of a `File<File>`.  Text is stored as utf-8
+fn content(substance: Substance) -> Result<Bin, DriverErr> {
+    match substance {
+        Substance::Empty => Ok(vec![]),
+        Substance::Bin(bin) => Ok(bin),
+        Substance::Text(text) => Ok(text.into_bytes()),
+        other => Err(SpaceErr::bad_request(format!(
+            "File content must be Bin or Text not {}",
+            other.kind().to_string()
+        )))?,
+    }
+}
+
+/// a `File<File>` or `File<Dir>` stored at its filepath below the directory of the
+/// FileStore particle it belongs to
+#[derive(DirectedHandler)]
+pub struct File {
+    point: Point,
+    api: FileStoreApi,
+}
+
+impl File {
+    /// the File at `point` which is stored in the FileStore service `api`
+    pub fn of(api: &FileStoreApi, point: &Point) -> Result<File, DriverErr> {
+        let api = api.sub_root(filestore_dir(&point.non_file_parent()))?;
+        Ok(File::restore(point.clone(), (), api))
+    }
+
+    fn filepath(&self) -> String {
+        self.point.filepath().unwrap_or("/".to_string())
+    }
+
+    fn path(&self) -> PathBuf {
+        self.filepath().into()
+    }
+
+    /// the filepath of a `File<Dir>` (including the FileStore's `:/` root) ends in a `/`
+    pub fn is_dir(&self) -> bool {
+        self.filepath().ends_with('/')
+    }
+
+    pub async fn mkdir(&self) -> Result<(), DriverErr> {
+        Ok(self.api.mkdir(&self.path()).await?)
+    }
+
+    pub async fn write(&self, content: Bin) -> Result<(), DriverErr> {
+        if self.is_dir() {
+            Err(SpaceErr::bad_request(format!(
+                "cannot write content to Dir '{}'",
+                self.point.to_string()
+            )))?;
+        }
+        Ok(self.api.write(&self.path(), content.into()).await?)
+    }
+
+    pub async fn content(&self) -> Result<Bin, DriverErr> {
+        Ok(self.api.read(&self.path()).await?)
+    }
+
+    /// the points of the files and directories in this Dir
+    pub async fn children(&self) -> Result<Vec<Point>, DriverErr> {
+        let mut children = vec![];
+        for entry in self.api.list(&self.path()).await? {
+            let name =
+                entry
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .ok_or(DriverErr::String(format!(
+                        "illegal FileStore entry '{}'",
+                        entry.display()
+                    )))?;
+            let segment = match self.api.stat(&self.path().join(name)).await?.kind {
+                FileKind::Dir => format!("{}/", name),
+                FileKind::File => name.to_string(),
+            };
+            children.push(self.point.push_file(segment).map_err(SpaceErr::from)?);
+        }
+        Ok(children)
+    }
+
+    /// removes the backing file or (empty) directory.  The `:/` root belongs to the FileStore
+    /// particle and is left for it to remove
+    pub async fn remove(&self) -> Result<(), DriverErr> {
+        if self.filepath() != "/" {
+            self.api.remove(&self.path()).await?;
+        }
+        Ok(())
+    }
+}
+
+impl Particle for File {
+    type Skel = Point;
+    type Ctx = ();
+    type State = FileStoreApi;
+    type Err = ParticleDriverErr;
+
+    fn restore(point: Self::Skel, _: Self::Ctx, api: Self::State) -> Self {
+        File { point, api }
+    }
+
+    fn sphere(self) -> Result<ParticleSphere, Self::Err> {
+        Ok(ParticleSphere::new_handler(self))
+    }
+}
+
+#[handler]
+impl File {
+    /// the content of a `File<File>` or the points of a `File<Dir>`'s children
+    #[route("Cmd<Read>")]
+    pub async fn read(&self, _: InCtx<'_, ()>) -> Result<Substance, ParticleDriverErr> {
+        if self.is_dir() {
+            let mut list = SubstanceList::new();
+            for child in self.children().await? {
+                list.list.push(Box::new(Substance::Point(child)));
+            }
+            Ok(Substance::List(list))
+        } else {
+            Ok(Substance::Bin(self.content().await?))
+        }
+    }
+
+    #[route("Cmd<Update>")]
+    pub async fn update(&self, ctx: InCtx<'_, Substance>) -> Result<(), ParticleDriverErr> {
+        self.write(content(ctx.input.clone())?).await?;
+        Ok(())
+    }
+
+    #[route("Cmd<Delete>")]
+    pub async fn delete(&self, _: InCtx<'_, ()>) -> Result<(), ParticleDriverErr> {
+        self.remove().await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use crate::driver::filestore::File;
+    use crate::executor::dialect::filestore::{FileStore, FileStoreApi, NativeFileStore};
+    use crate::test_util::temp_dir;
+    use starlane_space::point::Point;
+    use std::path::PathBuf;
+    use std::str::FromStr;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    pub async fn test_files() {
+        let root = temp_dir("files");
+        let store = FileStore::Native(NativeFileStore::new(root.path().to_path_buf()));
+        let api = FileStoreApi::new(PathBuf::from("/"), Arc::new(store));
+        api.init().await.unwrap();
+
+        let dir = File::of(&api, &Point::from_str("my-space:files:/").unwrap()).unwrap();
+        let sub = File::of(&api, &Point::from_str("my-space:files:/sub/").unwrap()).unwrap();
+        let file = File::of(&api, &Point::from_str("my-space:files:/hello.txt").unwrap()).unwrap();
+
+        dir.mkdir().await.unwrap();
+        sub.mkdir().await.unwrap();
+        file.write("hello".as_bytes().to_vec()).await.unwrap();
+
+        assert!(dir.is_dir());
+        assert!(!file.is_dir());
+        assert_eq!(file.content().await.unwrap(), "hello".as_bytes().to_vec());
+        assert!(sub.write(vec![]).await.is_err());
+
+        let mut children = dir.children().await.unwrap();
+        children.sort_by_key(|point| point.to_string());
+        assert_eq!(
+            children,
+            vec![
+                Point::from_str("my-space:files:/hello.txt").unwrap(),
+                Point::from_str("my-space:files:/sub/").unwrap(),
+            ]
+        );
+
+        file.remove().await.unwrap();
+        sub.remove().await.unwrap();
+        dir.remove().await.unwrap();
+        assert!(dir.children().await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,229 @@
+use crate::driver::{
+    Driver, DriverAvail, DriverCtx, DriverErr, DriverHandler, DriverSkel, HyperDriverFactory,
+    HyperSkel, Particle, ParticleSphere, ParticleStarErr,
+};
+use crate::star::HyperStarSkel;
+use async_trait::async_trait;
+use dashmap::DashMap;
+use starlane_macros::{handler, route, DirectedHandler};
+use starlane_space::artifact::asynch::ArtErr;
+use starlane_space::config::mechtron::MechtronConfig;
+use starlane_space::err::{CoreReflector, SpaceErr};
+use starlane_space::hyper::HyperSubstance;
+use starlane_space::kind::{BaseKind, Kind};
+use starlane_space::point::Point;
+use starlane_space::selector::KindSelector;
+use starlane_space::wave::core::{CoreBounce, ReflectedCore};
+use starlane_space::wave::exchange::asynch::{DirectedHandler, InCtx, RootInCtx};
+use starlane_space::wave::DirectedWave;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// provisions the runtime that executes the guest code of a Mechtron
+#[async_trait]
+pub trait MechtronHostFactory: Send + Sync {
+    async fn provision(
+        &self,
+        point: &Point,
+        config: &MechtronConfig,
+    ) -> Result<Arc<dyn MechtronHost>, DriverErr>;
+}
+
+/// a running Mechtron guest. Every directed wave that reaches the Mechtron's
+/// [`starlane_space::loc::Layer::Core`] is handed to the guest and the guest's
+/// reflection is sent back out
+#[async_trait]
+pub trait MechtronHost: Send + Sync {
+    async fn handle(&self, wave: DirectedWave) -> Result<ReflectedCore, DriverErr>;
+}
+
+/// used when this build of Starlane cannot run WebAssembly
+pub struct NoDiceMechtronHostFactory;
+
+#[async_trait]
+impl MechtronHostFactory for NoDiceMechtronHostFactory {
+    async fn provision(
+        &self,
+        point: &Point,
+        _: &MechtronConfig,
+    ) -> Result<Arc<dyn MechtronHost>, DriverErr> {
+        Err(DriverErr::MechtronHostNotAvailable(point.clone()))
+    }
+}
+
+pub struct MechtronDriverFactory {
+    hosts: Arc<dyn MechtronHostFactory>,
+}
+
+impl MechtronDriverFactory {
+    pub fn new(hosts: Arc<dyn MechtronHostFactory>) -> Self {
+        Self { hosts }
+    }
+}
+
+#[async_trait]
+impl HyperDriverFactory for MechtronDriverFactory {
+    fn kind(&self) -> Kind {
+        Kind::Mechtron
+    }
+
+    fn selector(&self) -> KindSelector {
+        KindSelector::from_base(BaseKind::Mechtron)
+    }
+
+    fn avail(&self) -> DriverAvail {
+        DriverAvail::External
+    }
+
+    async fn create(
+        &self,
+        star: HyperStarSkel,
+        driver: DriverSkel,
+        _: DriverCtx,
+    ) -> Result<Box<dyn Driver>, DriverErr> {
+        let skel = HyperSkel::new(star, driver);
+        Ok(Box::new(MechtronDriver::new(skel, self.hosts.clone())))
+    }
+}
+
+pub struct MechtronDriver {
+    skel: HyperSkel,
+    hosts: Arc<dyn MechtronHostFactory>,
+    mechtrons: Arc<DashMap<Point, Arc<dyn MechtronHost>>>,
+}
+
+impl MechtronDriver {
+    pub fn new(skel: HyperSkel, hosts: Arc<dyn MechtronHostFactory>) -> Self {
+        Self {
+            skel,
+            hosts,
+            mechtrons: Arc::new(DashMap::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl Driver for MechtronDriver {
+    fn kind(&self) -> Kind {
+        Kind::Mechtron
+    }
+
+    async fn particle(&self, point: &Point) -> Result<ParticleSphere, DriverErr> {
+        let host = match self.mechtrons.get(point) {
+            Some(host) => host.clone(),
+            // a Mechtron assigned before this star restarted has not been provisioned yet
+            None => provision(&self.skel, &self.hosts, &self.mechtrons, point).await?,
+        };
+        let mechtron = Mechtron::restore((), (), host);
+        Ok(mechtron.sphere()?)
+    }
+
+    async fn handler(&self) -> Box<dyn DriverHandler> {
+        Box::new(MechtronDriverHandler::restore(
+            self.skel.clone(),
+            self.hosts.clone(),
+            self.mechtrons.clone(),
+        ))
+    }
+}
+
+/// load the [`MechtronConfig`] referenced by the Mechtron's `config` property and provision
+/// a host for the Wasm it names
+async fn provision(
+    skel: &HyperSkel,
+    hosts: &Arc<dyn MechtronHostFactory>,
+    mechtrons: &DashMap<Point, Arc<dyn MechtronHost>>,
+    point: &Point,
+) -> Result<Arc<dyn MechtronHost>, DriverErr> {
+    let properties = skel.star.registry.get_properties(point).await?;
+    let config = properties.get("config").ok_or(DriverErr::String(format!(
+        "Mechtron '{}' is missing required property 'config'",
+        point.to_string()
+    )))?;
+    let config = Point::from_str(config.value.as_str()).map_err(SpaceErr::from)?;
+    let config = skel
+        .driver
+        .artifacts()
+        .get_mechtron(&config)
+        .await
+        .ok_or(ArtErr::not_found(&config))??;
+
+    let host = hosts.provision(point, &config).await?;
+    mechtrons.insert(point.clone(), host.clone());
+    Ok(host)
+}
+
+#[derive(DirectedHandler)]
+pub struct MechtronDriverHandler {
+    skel: HyperSkel,
+    hosts: Arc<dyn MechtronHostFactory>,
+    mechtrons: Arc<DashMap<Point, Arc<dyn MechtronHost>>>,
+}
+
+impl MechtronDriverHandler {
+    fn restore(
+        skel: HyperSkel,
+        hosts: Arc<dyn MechtronHostFactory>,
+        mechtrons: Arc<DashMap<Point, Arc<dyn MechtronHost>>>,
+    ) -> Self {
+        Self {
+            skel,
+            hosts,
+            mechtrons,
+        }
+    }
+}
+
+impl DriverHandler for MechtronDriverHandler {}
+
+#[handler]
+impl MechtronDriverHandler {
+    #[route("Hyp<Assign>")]
+    async fn assign(&self, ctx: InCtx<'_, HyperSubstance>) -> Result<(), ParticleStarErr> {
+        if let HyperSubstance::Assign(assign) = ctx.input {
+            provision(
+                &self.skel,
+                &self.hosts,
+                &self.mechtrons,
+                &assign.details.stub.point,
+            )
+            .await?;
+            Ok(())
+        } else {
+            Err(DriverErr::String(
+                "MechtronDriver expected Assign".to_string(),
+            ))?
+        }
+    }
+}
+
+pub struct Mechtron {
+    host: Arc<dyn MechtronHost>,
+}
+
+impl Particle for Mechtron {
+    type Skel = ();
+    type Ctx = ();
+    type State = Arc<dyn MechtronHost>;
+    type Err = ParticleStarErr;
+
+    fn restore(_: Self::Skel, _: Self::Ctx, host: Self::State) -> Self {
+        Self { host }
+    }
+
+    fn sphere(self) -> Result<ParticleSphere, Self::Err> {
+        Ok(ParticleSphere::new_handler(self))
+    }
+}
+
+#[async_trait]
+impl DirectedHandler for Mechtron {
+    async fn handle(&self, ctx: RootInCtx) -> CoreBounce {
+        let signal = ctx.wave.is_signal();
+        match self.host.handle(ctx.wave).await {
+            Ok(_) if signal => CoreBounce::Absorbed,
+            Ok(core) => CoreBounce::Reflected(core),
+            Err(err) => CoreBounce::Reflected(ParticleStarErr::from(err).as_reflected_core()),
+        }
+    }
+}
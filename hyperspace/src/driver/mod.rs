@@ -3,9 +3,11 @@
 pub mod base;
 //pub mod cli;
 pub mod control;
+pub mod mechtron;
 pub mod root;
 pub mod space;
 pub mod star;
+pub mod web;
 
 pub mod artifact;
 
@@ -1260,7 +1262,13 @@ impl DriverRunner {
                     }
                     DriverRunnerCall::InitParticle { point, rtn } => {
                         let particle = self.driver.particle(&point).await.unwrap();
-                        rtn.send(particle.init().await);
+                        let status = particle.init().await;
+                        if let Ok(status) = &status {
+                            self.logger
+                                .result(self.star_skel.set_status(&point, status).await)
+                                .unwrap_or_default();
+                        }
+                        rtn.send(status);
                     }
                     DriverRunnerCall::GetPoint(rtn) => {
                         rtn.send(self.skel.point.clone());
@@ -1996,6 +2004,8 @@ pub enum DriverErr {
     ExpectEnvVar(String),
     #[error("DriverApi is not associated with point: '{0}'")]
     DriverApiNotFound(Point),
+    #[error("no Wasm host is available to run Mechtron '{0}'")]
+    MechtronHostNotAvailable(Point),
     #[error("'{0}'")]
     String(String),
     #[error("{0}")]
@@ -428,6 +428,42 @@ impl Star {
         }
     }
 
+    #[route("Hyp<Watch>")]
+    pub async fn watch(
+        &self,
+        ctx: InCtx<'_, HyperSubstance>,
+    ) -> Result<ReflectedCore, <Self as Particle>::Err> {
+        if let HyperSubstance::Watch(watcher) = ctx.input {
+            self.skel
+                .watchers
+                .watch(watcher.watch.clone(), watcher.surface.clone());
+            Ok(ReflectedCore::ok())
+        } else {
+            Err(SpaceErr::expected_substance(
+                SubstanceKind::Hyper(HyperSubstanceKind::Watch),
+                ctx.input.kind().into(),
+            ))?
+        }
+    }
+
+    #[route("Hyp<Unwatch>")]
+    pub async fn unwatch(
+        &self,
+        ctx: InCtx<'_, HyperSubstance>,
+    ) -> Result<ReflectedCore, <Self as Particle>::Err> {
+        if let HyperSubstance::Watch(watcher) = ctx.input {
+            self.skel
+                .watchers
+                .unwatch(&watcher.watch, &watcher.surface);
+            Ok(ReflectedCore::ok())
+        } else {
+            Err(SpaceErr::expected_substance(
+                SubstanceKind::Hyper(HyperSubstanceKind::Watch),
+                ctx.input.kind().into(),
+            ))?
+        }
+    }
+
     #[route("Hyp<Transport>")]
     pub async fn transport(&self, ctx: InCtx<'_, Wave>) {
         self.skel.logger.track(ctx.wave(), || {
@@ -707,6 +743,16 @@ impl Wrangler {
     }
 
     pub async fn wrangle(&self, track: bool) -> Result<Discoveries, SpaceErr> {
+        let mut discoveries = Discoveries::new();
+        for (_, discovery) in self.discover(track).await? {
+            discoveries.push(discovery);
+        }
+        Ok(discoveries)
+    }
+
+    /// search the adjacent stars returning each [`Discovery`] paired with the
+    /// adjacent star whose echo reported it
+    pub async fn discover(&self, track: bool) -> Result<Vec<(Point, Discovery)>, SpaceErr> {
         let mut ripple = DirectedProto::ripple();
         ripple.track = track;
         ripple.method(HypMethod::Search);
@@ -717,17 +763,18 @@ impl Wrangler {
         let mut adjacents = self.skel.adjacents.clone();
         adjacents.retain(|point, _| !self.history.contains(point));
         if adjacents.is_empty() {
-            return Ok(Discoveries::new());
+            return Ok(vec![]);
         }
         ripple.bounce_backs = Some(BounceBacks::Count(adjacents.len()));
         ripple.to(Recipients::Stars);
         let echoes: Echoes = self.transmitter.direct(ripple).await?;
-        let mut discoveries = Discoveries::new();
+        let mut discoveries = vec![];
         for echo in echoes {
             if echo.core.status.is_success() {
+                let via = echo.from.point.clone();
                 if let Substance::Hyper(HyperSubstance::Discoveries(new)) = echo.variant.core.body {
                     for discovery in new.vec.into_iter() {
-                        discoveries.push(discovery);
+                        discoveries.push((via.clone(), discovery));
                     }
                 } else {
                     // this is not good, but it's not breaking anything, and I cant deal with all the errors right now -- Scott
@@ -0,0 +1,591 @@
+use crate::driver::{
+    Driver, DriverAvail, DriverCtx, DriverErr, DriverSkel, DriverStatus, HyperDriverFactory,
+    Particle, ParticleSphere, StdParticleErr,
+};
+use crate::star::HyperStarSkel;
+use async_trait::async_trait;
+use http_body_util::{BodyExt, Full, LengthLimitError, Limited};
+use hyper::body::{Body, Bytes};
+use hyper::header::{
+    HeaderName, HeaderValue, CONNECTION, CONTENT_LENGTH, CONTENT_TYPE, HOST, PROXY_AUTHENTICATE,
+    PROXY_AUTHORIZATION, TE, TRAILER, TRANSFER_ENCODING, UPGRADE,
+};
+use hyper::http::uri::Authority;
+use hyper::service::service_fn;
+use hyper::{Request, Response};
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use hyper_util::server::conn::auto;
+use serde::{Deserialize, Serialize};
+use starlane_macros::{handler, DirectedHandler};
+use starlane_space::err::{LegacyStatusErr, SpaceErr};
+use starlane_space::kind::{BaseKind, Kind};
+use starlane_space::loc::ToSurface;
+use starlane_space::log::Logger;
+use starlane_space::point::Point;
+use starlane_space::selector::KindSelector;
+use starlane_space::substance::Substance;
+use starlane_space::wave::core::http2::HttpMethod;
+use starlane_space::wave::core::{HeaderMap, ReflectedCore};
+use starlane_space::wave::exchange::asynch::{DirectedHandler, ProtoTransmitter};
+use starlane_space::wave::{Agent, DirectedProto};
+use std::convert::Infallible;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use url::Url;
+
+/// the HTTP ingress of a Jump star: which address and port to listen on, the largest request
+/// body it accepts and which point each request is directed to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebConfig {
+    #[serde(default = "WebConfig::default_address")]
+    pub address: String,
+    #[serde(default = "WebConfig::default_port")]
+    pub port: u16,
+    /// a request with a larger body is refused with `413 Payload Too Large`
+    #[serde(default = "WebConfig::default_max_body")]
+    pub max_body: usize,
+    #[serde(default)]
+    pub routes: Vec<HttpRoute>,
+}
+
+impl WebConfig {
+    fn default_address() -> String {
+        "0.0.0.0".to_string()
+    }
+
+    fn default_port() -> u16 {
+        8080u16
+    }
+
+    fn default_max_body() -> usize {
+        16 * 1024 * 1024
+    }
+}
+
+impl Default for WebConfig {
+    fn default() -> Self {
+        Self {
+            address: Self::default_address(),
+            port: Self::default_port(),
+            max_body: Self::default_max_body(),
+            routes: vec![],
+        }
+    }
+}
+
+/// requests for `host` (any host when `None`) whose path is under `path` are directed to `point`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HttpRoute {
+    #[serde(default)]
+    pub host: Option<String>,
+    pub path: String,
+    pub point: String,
+}
+
+impl HttpRoute {
+    pub fn new<P: ToString>(host: Option<String>, path: P, point: &Point) -> Self {
+        Self {
+            host,
+            path: path.to_string(),
+            point: point.to_string(),
+        }
+    }
+}
+
+/// selects the point an HTTP request is directed to.  The route with the longest matching
+/// path prefix wins and a route for a specific host beats one for any host
+#[derive(Debug, Clone)]
+pub struct HttpRouter {
+    routes: Vec<(Option<String>, String, Point)>,
+}
+
+impl HttpRouter {
+    pub fn new(routes: &Vec<HttpRoute>) -> Result<Self, SpaceErr> {
+        let mut rtn = vec![];
+        for route in routes {
+            let host = route.host.as_deref().map(Self::host_name);
+            let path = Self::normalize(route.path.as_str());
+            let point = Point::from_str(route.point.as_str())?;
+            rtn.push((host, path, point));
+        }
+        Ok(Self { routes: rtn })
+    }
+
+    fn normalize(path: &str) -> String {
+        let path = path.trim_end_matches('/');
+        if path.starts_with('/') {
+            path.to_string()
+        } else {
+            format!("/{}", path)
+        }
+    }
+
+    /// IPv6 hosts are compared without their brackets so a route may name `::1` or `[::1]`
+    fn host_name(host: &str) -> String {
+        host.trim_start_matches('[')
+            .trim_end_matches(']')
+            .to_lowercase()
+    }
+
+    fn under(prefix: &str, path: &str) -> bool {
+        prefix == "/"
+            || path == prefix
+            || (path.starts_with(prefix) && path[prefix.len()..].starts_with('/'))
+    }
+
+    pub fn select(&self, host: Option<&str>, path: &str) -> Option<&Point> {
+        // the `Host` header may carry a port.  One that is not a valid authority only matches
+        // routes for any host
+        let host = host
+            .and_then(|host| Authority::from_str(host).ok())
+            .map(|authority| Self::host_name(authority.host()));
+        self.routes
+            .iter()
+            .filter(|(route_host, _, _)| match route_host {
+                None => true,
+                Some(route_host) => host.as_ref() == Some(route_host),
+            })
+            .filter(|(_, prefix, _)| Self::under(prefix.as_str(), path))
+            .max_by_key(|(route_host, prefix, _)| (prefix.len(), route_host.is_some()))
+            .map(|(_, _, point)| point)
+    }
+}
+
+pub struct WebDriverFactory {
+    config: WebConfig,
+}
+
+impl WebDriverFactory {
+    pub fn new(config: WebConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl HyperDriverFactory for WebDriverFactory {
+    fn kind(&self) -> Kind {
+        Kind::WebServer
+    }
+
+    fn selector(&self) -> KindSelector {
+        KindSelector::from_base(BaseKind::WebServer)
+    }
+
+    fn avail(&self) -> DriverAvail {
+        DriverAvail::Internal
+    }
+
+    async fn create(
+        &self,
+        star: HyperStarSkel,
+        driver: DriverSkel,
+        ctx: DriverCtx,
+    ) -> Result<Box<dyn Driver>, DriverErr> {
+        let router = HttpRouter::new(&self.config.routes)?;
+        Ok(Box::new(WebDriver::new(self.config.clone(), router)))
+    }
+}
+
+pub struct WebDriver {
+    config: WebConfig,
+    router: HttpRouter,
+}
+
+impl WebDriver {
+    fn new(config: WebConfig, router: HttpRouter) -> Self {
+        Self { config, router }
+    }
+}
+
+/// the first pause after a failed accept, doubled on each consecutive failure
+static ACCEPT_BACKOFF: Duration = Duration::from_millis(10);
+
+/// the longest pause between failed accepts
+static MAX_ACCEPT_BACKOFF: Duration = Duration::from_secs(1);
+
+#[async_trait]
+impl Driver for WebDriver {
+    fn kind(&self) -> Kind {
+        Kind::WebServer
+    }
+
+    async fn init(&mut self, skel: DriverSkel, ctx: DriverCtx) -> Result<(), DriverErr> {
+        skel.status_tx
+            .send(DriverStatus::Init)
+            .await
+            .unwrap_or_default();
+
+        let address = (self.config.address.as_str(), self.config.port);
+        let listener = TcpListener::bind(address).await.map_err(|err| {
+            DriverErr::String(format!(
+                "web address {}:{}: {}",
+                self.config.address, self.config.port, err
+            ))
+        })?;
+
+        let gateway = Arc::new(HttpGateway::new(
+            self.router.clone(),
+            self.config.max_body,
+            ctx.transmitter,
+            skel.logger.clone(),
+        ));
+        let logger = skel.logger.clone();
+        tokio::spawn(async move {
+            let mut backoff = ACCEPT_BACKOFF;
+            loop {
+                let stream = match listener.accept().await {
+                    Ok((stream, _)) => {
+                        backoff = ACCEPT_BACKOFF;
+                        stream
+                    }
+                    Err(err) => {
+                        // errors such as running out of file descriptors persist for a while
+                        // so give them a chance to clear instead of spinning
+                        logger.warn(format!("web accept: {}", err));
+                        tokio::time::sleep(backoff).await;
+                        backoff = (backoff * 2).min(MAX_ACCEPT_BACKOFF);
+                        continue;
+                    }
+                };
+                let gateway = gateway.clone();
+                let logger = logger.clone();
+                tokio::spawn(async move {
+                    let service = service_fn(move |request| {
+                        let gateway = gateway.clone();
+                        async move { Ok::<_, Infallible>(gateway.handle(request).await) }
+                    });
+                    // serves both HTTP/1.1 and HTTP/2 (prior knowledge)
+                    if let Err(err) = auto::Builder::new(TokioExecutor::new())
+                        .serve_connection(TokioIo::new(stream), service)
+                        .await
+                    {
+                        logger.warn(format!("web connection: {}", err));
+                    }
+                });
+            }
+        });
+
+        skel.status_tx
+            .send(DriverStatus::Ready)
+            .await
+            .unwrap_or_default();
+        Ok(())
+    }
+
+    async fn particle(&self, point: &Point) -> Result<ParticleSphere, DriverErr> {
+        let server = WebServer::restore((), (), ());
+        Ok(server.sphere()?)
+    }
+}
+
+/// turns HTTP requests into [`Method::Http`] pings and the reflected cores back into responses.
+/// The gateway does not authenticate requests: every ping is sent as [`Agent::Anonymous`] and
+/// the credentials a client presents (an `Authorization` header or cookies) are only passed on
+/// in the headers, so a particle that must know who is calling has to verify them itself
+struct HttpGateway {
+    router: HttpRouter,
+    max_body: usize,
+    transmitter: ProtoTransmitter,
+    logger: Logger,
+}
+
+impl HttpGateway {
+    fn new(
+        router: HttpRouter,
+        max_body: usize,
+        transmitter: ProtoTransmitter,
+        logger: Logger,
+    ) -> Self {
+        Self {
+            router,
+            max_body,
+            transmitter,
+            logger,
+        }
+    }
+
+    async fn handle<B>(&self, request: Request<B>) -> Response<Full<Bytes>>
+    where
+        B: Body<Data = Bytes>,
+        B::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        match self.direct(request).await {
+            Ok(core) => response(core),
+            Err(err) => {
+                let mut core = ReflectedCore::status(err.status());
+                core.body = Substance::Text(err.message());
+                response(core)
+            }
+        }
+    }
+
+    async fn direct<B>(&self, request: Request<B>) -> Result<ReflectedCore, SpaceErr>
+    where
+        B: Body<Data = Bytes>,
+        B::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        let method = http_method(request.method())?;
+
+        let host = request
+            .headers()
+            .get(HOST)
+            .and_then(|host| host.to_str().ok())
+            .or(request.uri().host())
+            .map(|host| host.to_string());
+
+        let point = self
+            .router
+            .select(host.as_deref(), request.uri().path())
+            .cloned()
+            .ok_or(SpaceErr::new(404u16, "Not Found"))?;
+
+        let uri = Url::parse(
+            format!(
+                "http://{}{}",
+                host.as_deref().unwrap_or("localhost"),
+                request
+                    .uri()
+                    .path_and_query()
+                    .map_or("/", |path| path.as_str())
+            )
+            .as_str(),
+        )
+        .map_err(|err| SpaceErr::new(400u16, err.to_string()))?;
+
+        let mut headers = HeaderMap::new();
+        for (name, value) in request.headers() {
+            if let Ok(value) = value.to_str() {
+                headers.insert(name.to_string(), value.to_string());
+            }
+        }
+
+        let body = Limited::new(request.into_body(), self.max_body)
+            .collect()
+            .await
+            .map_err(|err| match err.downcast_ref::<LengthLimitError>() {
+                Some(_) => SpaceErr::new(413u16, "Payload Too Large"),
+                None => SpaceErr::new(400u16, err.to_string()),
+            })?
+            .to_bytes();
+        let body = if body.is_empty() {
+            Substance::Empty
+        } else {
+            Substance::Bin(body.to_vec())
+        };
+
+        let mut proto = DirectedProto::http(point.to_surface(), method);
+        proto.uri(uri);
+        proto.body(body);
+        proto.core.headers = headers;
+        // see the HttpGateway docs: the ingress has no authenticated agent to forward
+        proto.agent(Agent::Anonymous);
+
+        self.logger
+            .info(format!("http ingress {} -> {}", proto.core.uri, point));
+        let pong = self.transmitter.ping(proto).await?;
+        Ok(pong.variant.core)
+    }
+}
+
+fn http_method(method: &hyper::Method) -> Result<HttpMethod, SpaceErr> {
+    Ok(match *method {
+        hyper::Method::OPTIONS => HttpMethod::Options,
+        hyper::Method::GET => HttpMethod::Get,
+        hyper::Method::POST => HttpMethod::Post,
+        hyper::Method::PUT => HttpMethod::Put,
+        hyper::Method::DELETE => HttpMethod::Delete,
+        hyper::Method::HEAD => HttpMethod::Head,
+        hyper::Method::TRACE => HttpMethod::Trace,
+        hyper::Method::CONNECT => HttpMethod::Connect,
+        hyper::Method::PATCH => HttpMethod::Patch,
+        _ => return Err(SpaceErr::new(405u16, "Method Not Allowed")),
+    })
+}
+
+/// headers that describe a single connection or a body's framing.  They are never copied from
+/// a reflected core since hyper frames the response itself
+static HOP_BY_HOP: [HeaderName; 10] = [
+    CONNECTION,
+    HeaderName::from_static("keep-alive"),
+    PROXY_AUTHENTICATE,
+    PROXY_AUTHORIZATION,
+    TE,
+    TRAILER,
+    TRANSFER_ENCODING,
+    UPGRADE,
+    CONTENT_LENGTH,
+    HeaderName::from_static("proxy-connection"),
+];
+
+fn response(core: ReflectedCore) -> Response<Full<Bytes>> {
+    let (content_type, body): (Option<&str>, Vec<u8>) = match core.body {
+        Substance::Empty => (None, vec![]),
+        Substance::Bin(bin) => (Some("application/octet-stream"), bin),
+        Substance::Text(text) => (Some("text/plain; charset=utf-8"), text.into_bytes()),
+        substance => (
+            Some("application/json"),
+            serde_json::to_vec(&substance).unwrap_or_default(),
+        ),
+    };
+
+    let mut response = Response::new(Full::new(Bytes::from(body)));
+    *response.status_mut() = hyper::StatusCode::from_u16(core.status.as_u16())
+        .unwrap_or(hyper::StatusCode::INTERNAL_SERVER_ERROR);
+    for (name, value) in core.headers {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_str(name.as_str()),
+            HeaderValue::from_str(value.as_str()),
+        ) {
+            if !HOP_BY_HOP.contains(&name) {
+                response.headers_mut().insert(name, value);
+            }
+        }
+    }
+    if let Some(content_type) = content_type {
+        if !response.headers().contains_key(CONTENT_TYPE) {
+            response
+                .headers_mut()
+                .insert(CONTENT_TYPE, HeaderValue::from_static(content_type));
+        }
+    }
+    response
+}
+
+#[derive(DirectedHandler)]
+pub struct WebServer;
+
+#[handler]
+impl WebServer {}
+
+impl Particle for WebServer {
+    type Skel = ();
+    type Ctx = ();
+    type State = ();
+    type Err = StdParticleErr;
+
+    fn restore(_: Self::Skel, _: Self::Ctx, _: Self::State) -> Self {
+        WebServer
+    }
+
+    fn sphere(self) -> Result<ParticleSphere, Self::Err> {
+        Ok(ParticleSphere::new_handler(self))
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use crate::driver::web::{HttpGateway, HttpRoute, HttpRouter};
+    use http_body_util::{BodyExt, Full};
+    use hyper::body::Bytes;
+    use hyper::header::{CONNECTION, CONTENT_TYPE, TRANSFER_ENCODING};
+    use hyper::{Request, StatusCode};
+    use starlane_space::loc::ToSurface;
+    use starlane_space::point::Point;
+    use starlane_space::settings::Timeouts;
+    use starlane_space::substance::Substance;
+    use starlane_space::wave::core::ReflectedCore;
+    use starlane_space::wave::exchange::asynch::{Exchanger, ProtoTransmitterBuilder, TxRouter};
+    use starlane_space::wave::exchange::SetStrategy;
+    use std::str::FromStr;
+    use std::sync::Arc;
+    use tokio::sync::mpsc;
+
+    #[tokio::test]
+    pub async fn test_http_gateway() {
+        let site = Point::from_str("localhost:site").unwrap();
+        let router = HttpRouter::new(&vec![HttpRoute::new(None, "/site", &site)]).unwrap();
+        let (tx, mut rx) = mpsc::channel(16);
+        let exchanger = Exchanger::new(
+            Point::from_str("localhost:web").unwrap().to_surface(),
+            Timeouts::default(),
+            Default::default(),
+        );
+        let mut transmitter =
+            ProtoTransmitterBuilder::new(Arc::new(TxRouter::new(tx)), exchanger.clone());
+        transmitter.from = SetStrategy::Override(exchanger.surface.clone());
+        let gateway = HttpGateway::new(router, 8, transmitter.build(), Default::default());
+
+        // the particle echoes the body back with headers a proxy must not forward
+        {
+            let site = site.clone();
+            tokio::spawn(async move {
+                while let Some(wave) = rx.recv().await {
+                    let directed = wave.to_directed().unwrap();
+                    let mut core = ReflectedCore::ok_body(directed.core().body.clone());
+                    core.headers.insert("x-site".to_string(), "yes".to_string());
+                    core.headers
+                        .insert("connection".to_string(), "close".to_string());
+                    core.headers
+                        .insert("transfer-encoding".to_string(), "chunked".to_string());
+                    core.headers
+                        .insert("content-length".to_string(), "999".to_string());
+                    let reflected = directed.reflection().unwrap().make(core, site.to_surface());
+                    exchanger.reflected(reflected).await.unwrap();
+                }
+            });
+        }
+
+        let request = |path: &str, body: &'static str| {
+            Request::post(path)
+                .header("host", "localhost")
+                .body(Full::new(Bytes::from(body)))
+                .unwrap()
+        };
+
+        let response = gateway.handle(request("/site/index", "hello")).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["x-site"], "yes");
+        assert_eq!(response.headers()[CONTENT_TYPE], "application/octet-stream");
+        assert!(!response.headers().contains_key(CONNECTION));
+        assert!(!response.headers().contains_key(TRANSFER_ENCODING));
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(body.as_ref(), b"hello");
+
+        let response = gateway.handle(request("/site/index", "too large!")).await;
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        let response = gateway.handle(request("/elsewhere", "")).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(body.as_ref(), b"Not Found");
+    }
+
+    #[test]
+    pub fn test_http_router() {
+        let site = Point::from_str("localhost:site").unwrap();
+        let api = Point::from_str("localhost:api").unwrap();
+        let docs = Point::from_str("docs:site").unwrap();
+        let router = HttpRouter::new(&vec![
+            HttpRoute::new(None, "/", &site),
+            HttpRoute::new(None, "/api/", &api),
+            HttpRoute::new(Some("Docs.Example.com".to_string()), "/", &docs),
+        ])
+        .unwrap();
+
+        assert_eq!(router.select(None, "/index.html"), Some(&site));
+        assert_eq!(router.select(None, "/api"), Some(&api));
+        assert_eq!(router.select(None, "/api/users/1"), Some(&api));
+        assert_eq!(router.select(None, "/apiary"), Some(&site));
+        assert_eq!(
+            router.select(Some("docs.example.com:8080"), "/api/users"),
+            Some(&api)
+        );
+        assert_eq!(router.select(Some("docs.example.com"), "/"), Some(&docs));
+        assert_eq!(router.select(Some("docs example.com"), "/"), Some(&site));
+
+        let local = Point::from_str("localhost:local").unwrap();
+        let router = HttpRouter::new(&vec![
+            HttpRoute::new(None, "/", &site),
+            HttpRoute::new(Some("::1".to_string()), "/", &local),
+        ])
+        .unwrap();
+        assert_eq!(router.select(Some("[::1]:8080"), "/"), Some(&local));
+        assert_eq!(router.select(Some("[::1]"), "/"), Some(&local));
+        assert_eq!(router.select(Some("[::2]:8080"), "/"), Some(&site));
+
+        let router = HttpRouter::new(&vec![HttpRoute::new(None, "/api", &api)]).unwrap();
+        assert_eq!(router.select(None, "/"), None);
+    }
+}
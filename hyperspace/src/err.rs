@@ -50,6 +50,12 @@ impl From<tokio::io::Error> for HypErr {
     }
 }
 
+impl From<crate::hyperlane::tcp::Error> for HypErr {
+    fn from(value: crate::hyperlane::tcp::Error) -> Self {
+        HypErr::String(value.message)
+    }
+}
+
 impl CoreReflector for HypErr {
     fn as_reflected_core(self) -> ReflectedCore {
         match self {
@@ -1,6 +1,6 @@
 pub mod os;
 
-use crate::executor::Executor;
+use crate::executor::{Body, ByteStream, Executor};
 use itertools::Itertools;
 use nom::AsBytes;
 use os::OsProcess;
@@ -12,7 +12,7 @@ use std::ops::{Deref, DerefMut};
 use thiserror::Error;
 use tokio::io::AsyncWriteExt;
 
-pub type CliIn = CliInDef<Option<Vec<u8>>>;
+pub type CliIn = CliInDef<Option<Body>>;
 #[derive(Clone, Eq, PartialEq)]
 pub struct HostEnv {
     pub pwd: String,
@@ -111,9 +111,14 @@ impl CliIn {
     }
 
     pub fn str_stdin(args: Vec<String>, stdin: Vec<u8>) -> Self {
+        Self::str_body(args, Body::Bin(stdin))
+    }
+
+    /// `body` is copied to stdin as it is read
+    pub fn str_body(args: Vec<String>, body: Body) -> Self {
         Self {
             args,
-            stdin: Some(stdin),
+            stdin: Some(body),
         }
     }
 }
@@ -152,6 +157,35 @@ impl CliOut {
 }
 
 impl CliOut {
+    /// wait for the process to exit and return true if it exited successfully
+    pub async fn success(&mut self) -> Result<bool, CliErr> {
+        match self {
+            CliOut::Os(proc) => Ok(proc.wait().await?.success()),
+        }
+    }
+
+    /// stdout as a [ByteStream] so large outputs are never buffered
+    pub fn stdout_stream(&mut self) -> Result<ByteStream, CliErr> {
+        match self {
+            CliOut::Os(proc) => {
+                let stdout = proc.stdout.take().ok_or(CliErr::TakeStdOut)?;
+                Ok(Box::pin(stdout))
+            }
+        }
+    }
+
+    /// everything the process printed to stderr
+    pub async fn stderr(&mut self) -> Result<String, CliErr> {
+        match self {
+            CliOut::Os(proc) => {
+                let mut err = vec![];
+                let mut stderr = proc.stderr.take().ok_or(CliErr::TakeStdErr)?;
+                tokio::io::copy(&mut stderr, &mut err).await?;
+                Ok(String::from_utf8_lossy(&err).trim().to_string())
+            }
+        }
+    }
+
     pub async fn stdout(&mut self) -> Result<Vec<u8>, CliErr> {
         match self {
             CliOut::Os(proc) => {
@@ -1,5 +1,5 @@
 use crate::executor::cli::{CliErr, CliIn, CliOut};
-use crate::executor::{ExeConf, Executor};
+use crate::executor::{Body, ExeConf, Executor};
 use crate::host::{ExeStub, Host, HostCli, Proc};
 use async_trait::async_trait;
 use std::ops::{Deref, DerefMut};
@@ -38,19 +38,21 @@ impl Executor for CliOsExecutor {
         command.current_dir(self.stub.env.pwd.clone());
         command.env_clear();
         command.envs(&self.stub.env.env);
-        //command.stdin(Stdio::piped()).output().await?;
-        command.stdin(Stdio::piped()).output().await?;
-        //command.stdout(Stdio::piped()).output().await?;
-        command.stdout(Stdio::piped()).output().await?;
-        //command.stderr(Stdio::piped()).output().await?;
-        command.stderr(Stdio::piped()).output().await?;
+        command.stdin(Stdio::piped());
+        command.stdout(Stdio::piped());
+        command.stderr(Stdio::piped());
         let child = command.spawn()?;
         //        Ok(OsProcess::new(child))
         let mut process = OsProcess::new(child);
 
-        if let Option::Some(mut data) = input.stdin.take() {
+        if let Option::Some(body) = input.stdin.take() {
             let mut stdin = process.stdin.take().ok_or(CliErr::TakeStdIn)?;
-            stdin.write_all(&mut data).await?;
+            match body {
+                Body::Bin(data) => stdin.write_all(&data).await?,
+                Body::Stream(mut stream) => {
+                    tokio::io::copy(&mut stream, &mut stdin).await?;
+                }
+            }
             stdin.flush().await?;
         }
 
@@ -1,17 +1,20 @@
 use crate::executor::cli::os::CliOsExecutor;
 use crate::executor::cli::{CliErr, CliIn, CliOut};
-use crate::executor::Executor;
+use crate::executor::{Body, ByteStream, Executor};
 use crate::host::err::HostErr;
 use clap::{Parser, Subcommand};
 use itertools::Itertools;
 use path_clean::PathClean;
 use starlane_space::substance::Bin;
+use std::fmt::{Display, Formatter};
 use std::io::BufRead;
 use std::path::{PathBuf, StripPrefixError};
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use strum_macros::EnumString;
 use thiserror::Error;
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
 /*
 impl <E> From<Box<E>> for FileStore
 where E: Executor<In=CliIn,Out=CliOut> {
@@ -48,6 +51,63 @@ impl FileStoreApi {
         self.filestore.execute(FileStoreIn::Init).await?;
         Ok(())
     }
+
+    /// `path` relative to the root of this api
+    fn norm(&self, path: &PathBuf) -> Result<PathBuf, FileStoreErr> {
+        RootDir::new(self.path.clone()).norm(path)
+    }
+
+    pub async fn mkdir(&self, path: &PathBuf) -> Result<(), FileStoreErr> {
+        let path = self.norm(path)?;
+        self.filestore.execute(FileStoreIn::Mkdir { path }).await?;
+        Ok(())
+    }
+
+    pub async fn write(&self, path: &PathBuf, state: Body) -> Result<(), FileStoreErr> {
+        let path = self.norm(path)?;
+        self.filestore
+            .execute(FileStoreIn::Write { path, state })
+            .await?;
+        Ok(())
+    }
+
+    pub async fn read(&self, path: &PathBuf) -> Result<Bin, FileStoreErr> {
+        let mut bin = vec![];
+        self.read_stream(path).await?.read_to_end(&mut bin).await?;
+        Ok(bin)
+    }
+
+    pub async fn read_stream(&self, path: &PathBuf) -> Result<ByteStream, FileStoreErr> {
+        let path = self.norm(path)?;
+        match self.filestore.execute(FileStoreIn::Read { path }).await? {
+            FileStoreOut::Read(stream) => Ok(stream),
+            _ => Err(FileStoreErr::UnexpectedOut("read")),
+        }
+    }
+
+    pub async fn stat(&self, path: &PathBuf) -> Result<FileStat, FileStoreErr> {
+        let path = self.norm(path)?;
+        match self.filestore.execute(FileStoreIn::Stat { path }).await? {
+            FileStoreOut::Stat(stat) => Ok(stat),
+            _ => Err(FileStoreErr::UnexpectedOut("stat")),
+        }
+    }
+
+    /// the entries of the directory at `path`.  Only the file name of each entry is
+    /// meaningful since every [FileStore] reports them relative to its own root
+    pub async fn list(&self, path: &PathBuf) -> Result<Vec<PathBuf>, FileStoreErr> {
+        let path = self.norm(path)?;
+        match self.filestore.execute(FileStoreIn::List { path }).await? {
+            FileStoreOut::List(paths) => Ok(paths),
+            _ => Err(FileStoreErr::UnexpectedOut("list")),
+        }
+    }
+
+    pub async fn remove(&self, path: &PathBuf) -> Result<(), FileStoreErr> {
+        let path = self.norm(path)?;
+        self.filestore.execute(FileStoreIn::Remove { path }).await?;
+        Ok(())
+    }
 }
 
 impl From<Box<CliOsExecutor>> for FileStore {
@@ -58,6 +118,7 @@ impl From<Box<CliOsExecutor>> for FileStore {
 
 pub enum FileStore {
     Cli(Box<dyn Executor<In = CliIn, Out = CliOut, Err = CliErr> + Send + Sync>),
+    Native(NativeFileStore),
 }
 
 impl FileStore {
@@ -77,6 +138,7 @@ impl FileStore {
                     .with_env(FILE_STORE_ROOT, &root.to_str().unwrap());
                 Ok(conf.create()?)
             }
+            FileStore::Native(native) => Ok(FileStore::Native(native.sub_root(&sub_root).await?)),
         }
     }
 
@@ -85,23 +147,57 @@ impl FileStore {
             FileStore::Cli(executor) => {
                 let kind: FileStoreInKind = (&input).into();
                 let mut input = input.into();
-println!("pre cliout");                
                 let mut out = executor.execute(input).await?;
 
-println!("post cliout...");
                 let rtn = match kind {
-                    FileStoreInKind::Init => FileStoreOut::Init,
-                    FileStoreInKind::Write { .. } => FileStoreOut::Write,
+                    FileStoreInKind::Init => {
+                        Self::check(&mut out).await?;
+                        FileStoreOut::Init
+                    }
+                    FileStoreInKind::Write { .. } => {
+                        Self::check(&mut out).await?;
+                        FileStoreOut::Write
+                    }
+                    FileStoreInKind::Append { .. } => {
+                        Self::check(&mut out).await?;
+                        FileStoreOut::Append
+                    }
                     FileStoreInKind::Read { .. } => {
+                        out.close_stdin()?;
+                        FileStoreOut::Read(Self::checked_stream(&mut out).await?)
+                    }
+                    FileStoreInKind::ReadRange { .. } => {
+                        out.close_stdin()?;
+                        FileStoreOut::ReadRange(Self::checked_stream(&mut out).await?)
+                    }
+                    FileStoreInKind::Stat { .. } => {
                         out.close_stdin()?;
                         let stdout = out.stdout().await?;
-                        FileStoreOut::Read(stdout)
+                        Self::check(&mut out).await?;
+                        let stdout = String::from_utf8(stdout)
+                            .map_err(|err| FileStoreErr::Stat(err.to_string()))?;
+                        FileStoreOut::Stat(FileStat::from_str(stdout.trim())?)
+                    }
+                    FileStoreInKind::Rename { .. } => {
+                        Self::check(&mut out).await?;
+                        FileStoreOut::Rename
+                    }
+                    FileStoreInKind::Copy { .. } => {
+                        Self::check(&mut out).await?;
+                        FileStoreOut::Copy
+                    }
+                    FileStoreInKind::Mkdir { .. } => {
+                        Self::check(&mut out).await?;
+                        FileStoreOut::Mkdir
+                    }
+                    FileStoreInKind::Remove { .. } => {
+                        Self::check(&mut out).await?;
+                        FileStoreOut::Remove
                     }
-                    FileStoreInKind::Mkdir { .. } => FileStoreOut::Mkdir,
-                    FileStoreInKind::Remove { .. } => FileStoreOut::Remove,
                     FileStoreInKind::List { .. } => {
                         out.close_stdin()?;
                         let stdout = out.stdout().await?;
+                        Self::check(&mut out).await?;
                         let paths: Vec<std::io::Result<String>> =
                             stdout.lines().into_iter().collect_vec();
 
@@ -116,9 +212,11 @@ println!("post cliout...");
 
                         FileStoreOut::List(paths)
                     }
-                    FileStoreInKind::Exists { .. } => FileStoreOut::Exists(true),
+                    // the cli exits with a failure code when the path does not exist
+                    FileStoreInKind::Exists { .. } => FileStoreOut::Exists(out.success().await?),
                     FileStoreInKind::Pwd => {
                         let stdout = out.stdout().await?;
+                        Self::check(&mut out).await?;
                         let line = stdout
                             .lines()
                             .into_iter()
@@ -131,11 +229,172 @@ println!("post cliout...");
                 out.close_stdin();
                 Ok(rtn)
             }
+            FileStore::Native(native) => native.execute(input).await,
+        }
+    }
+
+    /// fail with what the cli printed to stderr if it exited with a failure code
+    async fn check(out: &mut CliOut) -> Result<(), FileStoreErr> {
+        if out.success().await? {
+            Ok(())
+        } else {
+            Err(FileStoreErr::CliFailed(out.stderr().await?))
+        }
+    }
+
+    /// stdout of the cli as a stream.  A cli that exits without printing anything is checked
+    /// for failure so that a missing path is not mistaken for an empty file
+    async fn checked_stream(out: &mut CliOut) -> Result<ByteStream, FileStoreErr> {
+        let mut stdout = tokio::io::BufReader::new(out.stdout_stream()?);
+        if tokio::io::AsyncBufReadExt::fill_buf(&mut stdout)
+            .await?
+            .is_empty()
+        {
+            Self::check(out).await?;
+        }
+        Ok(Box::pin(stdout))
+    }
+}
+
+/// a [FileStore] that works directly on the local filesystem with `tokio::fs` instead of
+/// forking a cli process for every operation.  Every path is normalized into `root`
+#[derive(Clone)]
+pub struct NativeFileStore {
+    root: RootDir,
+}
+
+impl NativeFileStore {
+    pub fn new(root: PathBuf) -> Self {
+        Self {
+            root: RootDir::new(root),
+        }
+    }
+
+    pub async fn sub_root(&self, sub_root: &PathBuf) -> Result<NativeFileStore, FileStoreErr> {
+        Ok(NativeFileStore::new(self.path(sub_root).await?))
+    }
+
+    /// normalizes `path` into the root.  Normalizing is lexical so it cannot see a symlink
+    /// that leads out of the root: the deepest part of the path that exists is resolved and
+    /// must still be inside the resolved root
+    async fn path(&self, path: &PathBuf) -> Result<PathBuf, FileStoreErr> {
+        let normed = self.root.norm(path)?;
+        let root = match tokio::fs::canonicalize(&self.root.root).await {
+            Ok(root) => root,
+            // nothing exists below a missing root to escape through
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(normed),
+            Err(err) => return Err(err.into()),
+        };
+
+        let mut existing = normed.as_path();
+        let resolved = loop {
+            match tokio::fs::canonicalize(existing).await {
+                Ok(resolved) => break resolved,
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                    // a dangling symlink could be created through, wherever it points
+                    if tokio::fs::symlink_metadata(existing).await.is_ok() {
+                        return Err(FileStoreErr::PathEscapesFileStoreBoundary(path.clone()));
+                    }
+                    match existing.parent() {
+                        Some(parent) => existing = parent,
+                        None => return Ok(normed),
+                    }
+                }
+                Err(err) => return Err(err.into()),
+            }
+        };
+
+        if !resolved.starts_with(&root) {
+            return Err(FileStoreErr::PathEscapesFileStoreBoundary(path.clone()));
+        }
+        Ok(normed)
+    }
+
+    pub async fn execute(&self, input: FileStoreIn) -> Result<FileStoreOut, FileStoreErr> {
+        Ok(match input {
+            FileStoreIn::Init => {
+                tokio::fs::create_dir_all(&self.root.root).await?;
+                FileStoreOut::Init
+            }
+            FileStoreIn::Write { path, state } => {
+                let mut file = tokio::fs::File::create(self.path(&path).await?).await?;
+                Self::copy(state, &mut file).await?;
+                FileStoreOut::Write
+            }
+            FileStoreIn::Append { path, state } => {
+                let mut file = tokio::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(self.path(&path).await?)
+                    .await?;
+                Self::copy(state, &mut file).await?;
+                FileStoreOut::Append
+            }
+            FileStoreIn::Read { path } => FileStoreOut::Read(Box::pin(
+                tokio::fs::File::open(self.path(&path).await?).await?,
+            )),
+            FileStoreIn::ReadRange { path, offset, len } => {
+                let mut file = tokio::fs::File::open(self.path(&path).await?).await?;
+                file.seek(std::io::SeekFrom::Start(offset)).await?;
+                let stream: ByteStream = match len {
+                    None => Box::pin(file),
+                    Some(len) => Box::pin(file.take(len)),
+                };
+                FileStoreOut::ReadRange(stream)
+            }
+            FileStoreIn::Stat { path } => {
+                let metadata = tokio::fs::metadata(self.path(&path).await?).await?;
+                FileStoreOut::Stat((&metadata).into())
+            }
+            FileStoreIn::Rename { from, to } => {
+                tokio::fs::rename(self.path(&from).await?, self.path(&to).await?).await?;
+                FileStoreOut::Rename
+            }
+            FileStoreIn::Copy { from, to } => {
+                tokio::fs::copy(self.path(&from).await?, self.path(&to).await?).await?;
+                FileStoreOut::Copy
+            }
+            FileStoreIn::Mkdir { path } => {
+                tokio::fs::create_dir_all(self.path(&path).await?).await?;
+                FileStoreOut::Mkdir
+            }
+            FileStoreIn::Remove { path } => {
+                let path = self.path(&path).await?;
+                if tokio::fs::metadata(&path).await?.is_dir() {
+                    tokio::fs::remove_dir(path).await?;
+                } else {
+                    tokio::fs::remove_file(path).await?;
+                }
+                FileStoreOut::Remove
+            }
+            FileStoreIn::List { path } => {
+                let mut paths = vec![];
+                let mut dir = tokio::fs::read_dir(self.path(&path).await?).await?;
+                while let Some(entry) = dir.next_entry().await? {
+                    paths.push(entry.path());
+                }
+                FileStoreOut::List(paths)
+            }
+            FileStoreIn::Exists { path } => {
+                FileStoreOut::Exists(tokio::fs::try_exists(self.path(&path).await?).await?)
+            }
+            FileStoreIn::Pwd => FileStoreOut::Pwd(self.root.root.clone()),
+        })
+    }
+
+    async fn copy(body: Body, file: &mut tokio::fs::File) -> Result<(), FileStoreErr> {
+        match body {
+            Body::Bin(bin) => file.write_all(&bin).await?,
+            Body::Stream(mut stream) => {
+                tokio::io::copy(&mut stream, file).await?;
+            }
         }
+        file.flush().await?;
+        Ok(())
     }
 }
 
-pub type FileStoreIn = FileStoreInDef<PathBuf, Bin>;
+pub type FileStoreIn = FileStoreInDef<PathBuf, Body>;
 pub type FileStoreInKind = FileStoreInDef<(), ()>;
 
 impl Into<FileStoreInKind> for &FileStoreIn {
@@ -146,7 +405,19 @@ impl Into<FileStoreInKind> for &FileStoreIn {
                 path: (),
                 state: (),
             },
+            FileStoreIn::Append { .. } => FileStoreInKind::Append {
+                path: (),
+                state: (),
+            },
             FileStoreIn::Read { .. } => FileStoreInKind::Read { path: () },
+            FileStoreIn::ReadRange { offset, len, .. } => FileStoreInKind::ReadRange {
+                path: (),
+                offset: *offset,
+                len: *len,
+            },
+            FileStoreIn::Stat { .. } => FileStoreInKind::Stat { path: () },
+            FileStoreIn::Rename { .. } => FileStoreInKind::Rename { from: (), to: () },
+            FileStoreIn::Copy { .. } => FileStoreInKind::Copy { from: (), to: () },
             FileStoreIn::Mkdir { .. } => FileStoreInKind::Mkdir { path: () },
             FileStoreIn::Remove { .. } => FileStoreInKind::Remove { path: () },
             FileStoreIn::List { .. } => FileStoreInKind::List { path: () },
@@ -158,12 +429,48 @@ impl Into<FileStoreInKind> for &FileStoreIn {
 
 pub enum FileStoreInDef<P, S> {
     Init,
-    Write { path: P, state: S },
-    Read { path: P },
-    Mkdir { path: P },
-    Remove { path: P },
-    List { path: P },
-    Exists { path: P },
+    Write {
+        path: P,
+        state: S,
+    },
+    /// create the file if it does not exist and append `state` to its end
+    Append {
+        path: P,
+        state: S,
+    },
+    Read {
+        path: P,
+    },
+    /// stream `len` bytes (or to the end of the file when `None`) starting at `offset`
+    ReadRange {
+        path: P,
+        offset: u64,
+        len: Option<u64>,
+    },
+    Stat {
+        path: P,
+    },
+    /// moves `from` to `to`, replacing `to` if it is a file
+    Rename {
+        from: P,
+        to: P,
+    },
+    Copy {
+        from: P,
+        to: P,
+    },
+    Mkdir {
+        path: P,
+    },
+    Remove {
+        path: P,
+    },
+    List {
+        path: P,
+    },
+    Exists {
+        path: P,
+    },
     Pwd,
 }
 
@@ -172,9 +479,26 @@ impl Into<CliIn> for FileStoreIn {
         match self {
             FileStoreIn::Init => CliIn::args(vec!["init"]),
             FileStoreIn::Write { path, state } => {
-                CliIn::str_stdin(vec!["write".to_string(), to_str(&path)], state)
+                CliIn::str_body(vec!["write".to_string(), to_str(&path)], state)
+            }
+            FileStoreIn::Append { path, state } => {
+                CliIn::str_body(vec!["append".to_string(), to_str(&path)], state)
             }
             FileStoreIn::Read { path } => CliIn::str_args(vec!["read".to_string(), to_str(&path)]),
+            FileStoreIn::ReadRange { path, offset, len } => {
+                let mut args = vec!["read-range".to_string(), to_str(&path), offset.to_string()];
+                if let Some(len) = len {
+                    args.push(len.to_string());
+                }
+                CliIn::str_args(args)
+            }
+            FileStoreIn::Stat { path } => CliIn::str_args(vec!["stat".to_string(), to_str(&path)]),
+            FileStoreIn::Rename { from, to } => {
+                CliIn::str_args(vec!["rename".to_string(), to_str(&from), to_str(&to)])
+            }
+            FileStoreIn::Copy { from, to } => {
+                CliIn::str_args(vec!["copy".to_string(), to_str(&from), to_str(&to)])
+            }
             FileStoreIn::Mkdir { path } => {
                 CliIn::str_args(vec!["mkdir".to_string(), to_str(&path)])
             }
@@ -193,7 +517,12 @@ impl Into<CliIn> for FileStoreIn {
 pub enum FileStoreOut {
     Init,
     Write,
-    Read(Bin),
+    Append,
+    Read(ByteStream),
+    ReadRange(ByteStream),
+    Stat(FileStat),
+    Rename,
+    Copy,
     Mkdir,
     Remove,
     List(Vec<PathBuf>),
@@ -201,6 +530,68 @@ pub enum FileStoreOut {
     Pwd(PathBuf),
 }
 
+#[derive(Clone, Debug, Eq, PartialEq, strum_macros::Display, EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum FileKind {
+    File,
+    Dir,
+}
+
+/// what `Stat` knows about a path.  The cli contract prints it as one line:
+/// `<file|dir> <size> <modified seconds since the epoch or '-'>`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FileStat {
+    pub kind: FileKind,
+    pub size: u64,
+    pub modified: Option<SystemTime>,
+}
+
+impl From<&std::fs::Metadata> for FileStat {
+    fn from(metadata: &std::fs::Metadata) -> Self {
+        Self {
+            kind: match metadata.is_dir() {
+                true => FileKind::Dir,
+                false => FileKind::File,
+            },
+            size: metadata.len(),
+            modified: metadata.modified().ok(),
+        }
+    }
+}
+
+impl Display for FileStat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let modified = self
+            .modified
+            .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+            .map_or("-".to_string(), |modified| modified.as_secs().to_string());
+        write!(f, "{} {} {}", self.kind, self.size, modified)
+    }
+}
+
+impl FromStr for FileStat {
+    type Err = FileStoreErr;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || FileStoreErr::Stat(s.to_string());
+        let mut parts = s.split_whitespace();
+        let kind = FileKind::from_str(parts.next().ok_or_else(err)?).map_err(|_| err())?;
+        let size = u64::from_str(parts.next().ok_or_else(err)?).map_err(|_| err())?;
+        let modified = match parts.next().ok_or_else(err)? {
+            "-" => None,
+            secs => Some(UNIX_EPOCH + Duration::from_secs(u64::from_str(secs).map_err(|_| err())?)),
+        };
+        if parts.next().is_some() {
+            return Err(err());
+        }
+        Ok(Self {
+            kind,
+            size,
+            modified,
+        })
+    }
+}
+
 #[derive(Clone, Debug, Parser)]
 #[command(version, about, long_about = None)]
 pub struct FileStoreCli {
@@ -211,12 +602,43 @@ pub struct FileStoreCli {
 #[derive(Clone, Debug, Subcommand, EnumString, strum_macros::Display)]
 pub enum FileStoreCommand {
     Init,
-    Write { path: PathBuf },
-    Read { path: PathBuf },
-    Mkdir { path: PathBuf },
-    Remove { path: PathBuf },
-    List { path: PathBuf },
-    Exists { path: PathBuf },
+    Write {
+        path: PathBuf,
+    },
+    Append {
+        path: PathBuf,
+    },
+    Read {
+        path: PathBuf,
+    },
+    ReadRange {
+        path: PathBuf,
+        offset: u64,
+        len: Option<u64>,
+    },
+    Stat {
+        path: PathBuf,
+    },
+    Rename {
+        from: PathBuf,
+        to: PathBuf,
+    },
+    Copy {
+        from: PathBuf,
+        to: PathBuf,
+    },
+    Mkdir {
+        path: PathBuf,
+    },
+    Remove {
+        path: PathBuf,
+    },
+    List {
+        path: PathBuf,
+    },
+    Exists {
+        path: PathBuf,
+    },
     Pwd,
 }
 
@@ -233,9 +655,28 @@ impl Into<Vec<String>> for FileStoreCli {
             FileStoreCommand::Write { path } => {
                 vec!["write".to_string(), to_str(path)]
             }
+            FileStoreCommand::Append { path } => {
+                vec!["append".to_string(), to_str(path)]
+            }
             FileStoreCommand::Read { path } => {
                 vec!["read".to_string(), to_str(path)]
             }
+            FileStoreCommand::ReadRange { path, offset, len } => {
+                let mut args = vec!["read-range".to_string(), to_str(path), offset.to_string()];
+                if let Some(len) = len {
+                    args.push(len.to_string());
+                }
+                args
+            }
+            FileStoreCommand::Stat { path } => {
+                vec!["stat".to_string(), to_str(path)]
+            }
+            FileStoreCommand::Rename { from, to } => {
+                vec!["rename".to_string(), to_str(from), to_str(to)]
+            }
+            FileStoreCommand::Copy { from, to } => {
+                vec!["copy".to_string(), to_str(from), to_str(to)]
+            }
             FileStoreCommand::Mkdir { path } => {
                 vec!["mkdir".to_string(), to_str(path)]
             }
@@ -281,6 +722,7 @@ impl TryFrom<CliOsExecutor> for FileStore {
     }
 }
 
+#[derive(Clone)]
 pub struct RootDir {
     root: PathBuf,
 }
@@ -301,12 +743,9 @@ impl RootDir {
             false => sub_path.clone(),
         };
         let normed: PathBuf = self.root.join(path).clean();
-        let parent = match normed.parent() {
-            None => PathBuf::from_str("/")?,
-            Some(parent) => parent.clone().into(),
-        };
 
-        if !parent.starts_with(&self.root) {
+        // the root itself is a valid path so that it can be listed
+        if !normed.starts_with(&self.root) {
             return Err(FileStoreErr::PathEscapesFileStoreBoundary(sub_path))?;
         }
 
@@ -328,6 +767,12 @@ pub enum FileStoreErr {
     CliErr(#[from] CliErr),
     #[error("command 'pwd' did not return anything")]
     Pwd,
+    #[error("could not parse stat: '{0}'")]
+    Stat(String),
+    #[error("FileStore cli failed: {0}")]
+    CliFailed(String),
+    #[error("FileStore returned an unexpected result for '{0}'")]
+    UnexpectedOut(&'static str),
     #[error("io error: {0}")]
     TokioIo(String),
     #[error("{0}")]
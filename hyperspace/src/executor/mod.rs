@@ -5,6 +5,8 @@ use crate::executor::cli::os::CliOsExecutor;
 use crate::host::err::HostErr;
 use crate::host::Host;
 use async_trait::async_trait;
+use std::pin::Pin;
+use tokio::io::AsyncRead;
 
 #[async_trait]
 pub trait Executor
@@ -49,3 +51,33 @@ impl ExeConf {
         }
     }
 }
+
+/// bytes that are read as they are consumed instead of being buffered in memory
+pub type ByteStream = Pin<Box<dyn AsyncRead + Send + Sync>>;
+
+/// content handed to an [Executor]: either already in memory or a [ByteStream]
+pub enum Body {
+    Bin(Vec<u8>),
+    Stream(ByteStream),
+}
+
+impl Body {
+    pub fn stream<R>(reader: R) -> Self
+    where
+        R: AsyncRead + Send + Sync + 'static,
+    {
+        Body::Stream(Box::pin(reader))
+    }
+}
+
+impl From<Vec<u8>> for Body {
+    fn from(bin: Vec<u8>) -> Self {
+        Body::Bin(bin)
+    }
+}
+
+impl From<&str> for Body {
+    fn from(text: &str) -> Self {
+        Body::Bin(text.as_bytes().to_vec())
+    }
+}
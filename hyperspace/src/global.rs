@@ -1,28 +1,41 @@
+use crate::properties::{hash_secrets, read_privilege, redact_secrets, redact_set_secrets};
 use crate::registry::err::RegErr;
-use crate::registry::Registration;
+use crate::registry::{Registration, Registry};
 use crate::star::{HyperStarSkel, SmartLocator, StarErr};
 use once_cell::sync::Lazy;
 use starlane_macros::{handler, push_mark, route, DirectedHandler};
 use starlane_space::artifact::ArtRef;
-use starlane_space::command::direct::create::{Create, PointSegTemplate};
+use starlane_space::command::common::{SetProperties, StateSrc};
+use starlane_space::command::direct::create::{Create, PointSegTemplate, Strategy};
+use starlane_space::command::direct::delete::Delete;
+use starlane_space::command::direct::get::{Get, GetOp};
+use starlane_space::command::direct::query::Query;
+use starlane_space::command::direct::select::Select;
 use starlane_space::command::Command;
 use starlane_space::command::RawCommand;
 use starlane_space::config::bind::BindConfig;
 use starlane_space::err::{CoreReflector, SpaceErr};
-use starlane_space::loc::{ToPoint, ToSurface};
+use starlane_space::hyper::{Created, HyperEvent, PropertiesChanged, Watcher};
+use starlane_space::kind::{FileSubKind, Kind};
+use starlane_space::loc::{Layer, ToPoint, ToSurface};
 use starlane_space::log::Logger;
 use starlane_space::parse::util::new_span;
 use starlane_space::parse::util::result;
 use starlane_space::parse::{bind_config, command_line};
+use starlane_space::particle::property::PropertiesConfig;
 use starlane_space::particle::{Details, Status};
 use starlane_space::point::Point;
-use starlane_space::substance::Substance;
+use starlane_space::security::{Access, ChildPerms, ParticlePerms, Permissions};
+use starlane_space::selector::PointHierarchy;
+use starlane_space::substance::{Substance, SubstanceList};
 use starlane_space::util::{log, ToResolved};
 use starlane_space::wave::core::cmd::CmdMethod;
 use starlane_space::wave::core::http2::StatusCode;
+use starlane_space::wave::core::hyper::HypMethod;
 use starlane_space::wave::core::ReflectedCore;
 use starlane_space::wave::exchange::asynch::{DirectedHandler, InCtx};
-use starlane_space::wave::{Agent, DirectedProto};
+use starlane_space::wave::{Agent, DirectedProto, Scope};
+use std::collections::HashMap;
 use std::str::FromStr;
 use std::sync::Arc;
 use thiserror::Error;
@@ -63,30 +76,81 @@ impl GlobalCommandExecutionHandler {
     pub async fn command(&self, ctx: InCtx<'_, Command>) -> Result<ReflectedCore, StarErr> {
         let global = GlobalExecutionChamber::new(self.skel.clone());
         let agent = ctx.wave().agent().clone();
+        let scope = ctx.wave().scope().clone();
+        if let Some(forbidden) = global
+            .authorizer
+            .authorize(ctx.input, &agent, &scope)
+            .await?
+        {
+            return Ok(forbidden);
+        }
         match ctx.input {
             Command::Create(create) => {
-                let details = self
+                let mut details = self
                     .skel
                     .logger
                     .result(global.create(create, &agent).await)?;
+                let config = self
+                    .skel
+                    .machine_api
+                    .properties_config(&details.stub.kind)
+                    .await?;
+                redact_secrets(&config, &mut details.properties, |_| false);
+                Ok(ReflectedCore::ok_body(details.into()))
+            }
+            Command::Get(get) => {
+                let details = global.get(get, &agent, &scope).await?;
                 Ok(ReflectedCore::ok_body(details.into()))
             }
             Command::Select(select) => {
-                let mut select = select.clone();
-                let substance: Substance = self.skel.registry.select(&mut select).await?.into();
+                let substance: Substance = global
+                    .authorizer
+                    .select(select, &agent, &scope)
+                    .await?
+                    .into();
                 Ok(ReflectedCore::ok_body(substance))
             }
             Command::Delete(delete) => {
-                let substance: Substance = self.skel.registry.delete(delete).await?.into();
+                let mut deleted = match global.delete_external_state(delete, &agent).await? {
+                    Ok(deleted) => deleted,
+                    Err(failed) => return Ok(failed),
+                };
+                deleted
+                    .list
+                    .append(&mut self.skel.registry.delete(delete).await?.list);
+                let substance: Substance = deleted.into();
                 Ok(ReflectedCore::ok_body(substance))
             }
             Command::Set(set) => {
+                let kind = self
+                    .skel
+                    .registry
+                    .record(&set.point)
+                    .await?
+                    .details
+                    .stub
+                    .kind;
+                let config = self.skel.machine_api.properties_config(&kind).await?;
+                let properties = hash_secrets(&config, &set.properties);
                 self.skel
                     .registry
-                    .set_properties(&set.point, &set.properties)
+                    .set_properties(&set.point, &properties)
+                    .await?;
+                self.skel
+                    .emit(properties_changed(&set.point, &config, &properties))
                     .await?;
                 Ok(ReflectedCore::ok())
             }
+            Command::Write(write) => {
+                // proxy the write command
+                let mut proto = DirectedProto::ping();
+                proto.method(CmdMethod::Update);
+                proto.agent(ctx.wave().agent().clone());
+                proto.to(write.point.to_surface());
+                proto.body(write.payload.clone());
+                let pong = ctx.transmitter.ping(proto).await?;
+                Ok(pong.variant.core)
+            }
             Command::Read(read) => {
                 // proxy the read command
                 let mut proto = DirectedProto::ping();
@@ -96,7 +160,248 @@ impl GlobalCommandExecutionHandler {
                 let pong = ctx.transmitter.ping(proto).await?;
                 Ok(pong.variant.core)
             }
-            c => Err(SpaceErr::unimplemented(format!("command not recognized")))?,
+            Command::Watch(watch) => {
+                let watcher = Watcher {
+                    watch: watch.to_recipient(),
+                    surface: ctx.wave().from().clone(),
+                };
+                global.subscribe(HypMethod::Watch, watcher).await
+            }
+            Command::Unwatch(unwatch) => {
+                let watcher = Watcher {
+                    watch: unwatch.to_recipient(),
+                    surface: ctx.wave().from().clone(),
+                };
+                global.subscribe(HypMethod::Unwatch, watcher).await
+            }
+        }
+    }
+}
+
+/// `details` holding only the properties `get` requested (all of them when none are named).
+/// A `CoreSecret` is redacted unless `access` has the `property:<key>:read` privilege.
+/// Returns the secret keys that were revealed
+pub fn read_properties(
+    get: &Get,
+    mut details: Details,
+    config: &PropertiesConfig,
+    access: &Access,
+) -> Result<(Details, Vec<String>), SpaceErr> {
+    let keys = match &get.op {
+        GetOp::State => Err(SpaceErr::unimplemented("Get State"))?,
+        GetOp::Properties(keys) => keys,
+    };
+    if !keys.is_empty() {
+        details.properties.retain(|key, _| keys.contains(key));
+    }
+    let revealed = redact_secrets(config, &mut details.properties, |key| {
+        access.check_privilege(read_privilege(key).as_str()).is_ok()
+    });
+    Ok((details, revealed))
+}
+
+/// the event announcing that `properties` were set on `point`.  Watching only takes `read`
+/// permission so every `CoreSecret` is redacted regardless of who is watching
+pub fn properties_changed(
+    point: &Point,
+    config: &PropertiesConfig,
+    properties: &SetProperties,
+) -> HyperEvent {
+    HyperEvent::PropertiesChanged(PropertiesChanged {
+        point: point.clone(),
+        properties: redact_set_secrets(config, properties),
+    })
+}
+
+/// the audit log entry for `agent` reading the secret `keys` of `point`
+fn secret_read_audit(
+    logger: &Logger,
+    agent: &Agent,
+    point: &Point,
+    keys: &Vec<String>,
+) -> (Logger, String) {
+    let mut logger = logger.clone();
+    logger.set_span_attr("audit", "secret-read");
+    logger.set_span_attr("agent", agent.to_point());
+    logger.set_span_attr("point", point);
+    logger.set_span_attr("properties", keys.join(","));
+    let message = format!(
+        "agent '{}' read secret properties [{}] of '{}'",
+        agent.to_point().to_string(),
+        keys.join(","),
+        point.to_string()
+    );
+    (logger, message)
+}
+
+/// checks what an [Agent] may do against the [Access] the registry grants it
+#[derive(Clone)]
+pub struct Authorizer {
+    registry: Registry,
+    logger: Logger,
+}
+
+impl Authorizer {
+    pub fn new(registry: Registry, logger: Logger) -> Self {
+        Self { registry, logger }
+    }
+
+    /// the [Access] `agent` has on `on` narrowed by the [Scope] of the session it arrived
+    /// through (see [Access::mask]).  An agent whose access cannot be determined (for example
+    /// an agent that is not a registered particle) is granted no access at all
+    pub async fn access(&self, agent: &Agent, scope: &Scope, on: &Point) -> Access {
+        let access = async {
+            let access = self.registry.access(&agent.to_point(), on).await?;
+            if let Scope::Grants(_) = scope {
+                let hierarchy: PointHierarchy = self
+                    .registry
+                    .query(on, &Query::PointHierarchy)
+                    .await?
+                    .try_into()?;
+                Ok::<Access, RegErr>(access.mask(scope, &hierarchy))
+            } else {
+                Ok(access)
+            }
+        };
+        match access.await {
+            Ok(access) => access,
+            Err(err) => {
+                self.logger.warn(format!(
+                    "could not determine access of agent '{}' on '{}' caused by: {}",
+                    agent.to_point().to_string(),
+                    on.to_string(),
+                    err.to_string()
+                ));
+                Access::none()
+            }
+        }
+    }
+
+    /// check the [Permissions] of `agent` against the particles `command` targets:
+    ///
+    /// * [Command::Create] requires `create` [ChildPerms] on the parent
+    /// * [Command::Select] requires `select` [ChildPerms] on the query root (each result is
+    ///   checked again by [Authorizer::select])
+    /// * [Command::Delete] requires `delete` [ChildPerms] on the parent of every selected particle
+    /// * [Command::Set] & [Command::Write] require `write` [ParticlePerms] on the point
+    /// * [Command::Get], [Command::Read], [Command::Watch] & [Command::Unwatch] require `read` [ParticlePerms] on the point
+    ///
+    /// returns a `403` [ReflectedCore] describing the denial or [None] if `agent` is permitted
+    pub async fn authorize(
+        &self,
+        command: &Command,
+        agent: &Agent,
+        scope: &Scope,
+    ) -> Result<Option<ReflectedCore>, StarErr> {
+        let denied = match command {
+            Command::Create(create) => {
+                let parent = &create.template.point.parent;
+                self.deny(agent, scope, parent, "create", |p| p.child.create)
+                    .await
+            }
+            Command::Select(select) => {
+                let root = select.pattern.query_root();
+                self.deny(agent, scope, &root, "select", |p| p.child.select)
+                    .await
+            }
+            Command::Delete(delete) => {
+                let mut select = delete.clone().into();
+                let selection = self.registry.select(&mut select).await?;
+                let mut denied = None;
+                for point in selection.list {
+                    let point: Point = (*point).try_into()?;
+                    let parent = point.parent().unwrap_or_else(Point::root);
+                    denied = self
+                        .deny(agent, scope, &parent, "delete", |p| p.child.delete)
+                        .await;
+                    if denied.is_some() {
+                        break;
+                    }
+                }
+                denied
+            }
+            Command::Set(set) => {
+                self.deny(agent, scope, &set.point, "write", |p| p.particle.write)
+                    .await
+            }
+            Command::Write(write) => {
+                self.deny(agent, scope, &write.point, "write", |p| p.particle.write)
+                    .await
+            }
+            Command::Get(get) => {
+                self.deny(agent, scope, &get.point, "read", |p| p.particle.read)
+                    .await
+            }
+            Command::Read(read) => {
+                self.deny(agent, scope, &read.point, "read", |p| p.particle.read)
+                    .await
+            }
+            Command::Watch(watch) => {
+                self.deny(agent, scope, &watch.point, "read", |p| p.particle.read)
+                    .await
+            }
+            Command::Unwatch(unwatch) => {
+                self.deny(agent, scope, &unwatch.point, "read", |p| p.particle.read)
+                    .await
+            }
+        };
+
+        Ok(denied.map(|message| ReflectedCore::fail(403, message)))
+    }
+
+    /// the particles matching `select` whose parent grants `agent` the `select` [ChildPerms].
+    /// A pattern may match below many parents besides its query root so every result is checked
+    pub async fn select(
+        &self,
+        select: &Select,
+        agent: &Agent,
+        scope: &Scope,
+    ) -> Result<SubstanceList, StarErr> {
+        let mut select = select.clone();
+        let selection = self.registry.select(&mut select).await?;
+        let mut permitted = HashMap::new();
+        let mut list = SubstanceList::new();
+        for substance in selection.list {
+            let point = match &*substance {
+                Substance::Stub(stub) => stub.point.clone(),
+                other => other.clone().try_into()?,
+            };
+            let parent = point.parent().unwrap_or_else(Point::root);
+            if !permitted.contains_key(&parent) {
+                let denied = self
+                    .deny(agent, scope, &parent, "select", |p| p.child.select)
+                    .await;
+                permitted.insert(parent.clone(), denied.is_none());
+            }
+            if permitted[&parent] {
+                list.push(substance);
+            }
+        }
+        Ok(list)
+    }
+
+    /// returns a denial message if the [Permissions] of `agent` on `on` do not pass `check`
+    async fn deny<F>(
+        &self,
+        agent: &Agent,
+        scope: &Scope,
+        on: &Point,
+        perm: &str,
+        check: F,
+    ) -> Option<String>
+    where
+        F: FnOnce(&Permissions) -> bool,
+    {
+        let permissions = self.access(agent, scope, on).await.permissions();
+        match check(&permissions) {
+            true => None,
+            false => Some(format!(
+                "Forbidden: agent '{}' does not have '{}' permission on '{}' (permissions: {})",
+                agent.to_point().to_string(),
+                perm,
+                on.to_string(),
+                permissions.to_string()
+            )),
         }
     }
 }
@@ -104,12 +409,36 @@ impl GlobalCommandExecutionHandler {
 pub struct GlobalExecutionChamber {
     pub skel: HyperStarSkel,
     pub logger: Logger,
+    pub authorizer: Authorizer,
 }
 
 impl GlobalExecutionChamber {
     pub fn new(skel: HyperStarSkel) -> Self {
         let logger = push_mark!(skel.logger);
-        Self { skel, logger }
+        let authorizer = Authorizer::new(skel.registry.clone(), logger.clone());
+        Self {
+            skel,
+            logger,
+            authorizer,
+        }
+    }
+
+    /// the [Details] of `get.point` as filtered by [read_properties] with the [Access] of
+    /// `agent`.  Every secret that is revealed is recorded in the audit log
+    pub async fn get(&self, get: &Get, agent: &Agent, scope: &Scope) -> Result<Details, StarErr> {
+        let details = self.skel.registry.record(&get.point).await?.details;
+        let config = self
+            .skel
+            .machine_api
+            .properties_config(&details.stub.kind)
+            .await?;
+        let access = self.authorizer.access(agent, scope, &get.point).await;
+        let (details, revealed) = read_properties(get, details, &config, &access)?;
+        if !revealed.is_empty() {
+            let (logger, message) = secret_read_audit(&self.logger, agent, &get.point, &revealed);
+            logger.info(message);
+        }
+        Ok(details)
     }
 
     #[track_caller]
@@ -123,17 +452,10 @@ impl GlobalExecutionChamber {
             PointSegTemplate::Exact(child_segment) => {
                 let point = create.template.point.parent.push(child_segment.clone())?;
 
-                let properties = self
-                    .skel
-                    .machine_api
-                    .properties_config(&child_kind)
-                    .await?
-                    .fill_create_defaults(&create.properties)?;
-                self.skel
-                    .machine_api
-                    .properties_config(&child_kind)
-                    .await?
-                    .check_create(&properties)?;
+                let config = self.skel.machine_api.properties_config(&child_kind).await?;
+                let properties = config.fill_create_defaults(&create.properties)?;
+                config.check_create(&properties)?;
+                let properties = hash_secrets(&config, &properties);
 
                 let registration = Registration {
                     point: point.clone(),
@@ -161,12 +483,13 @@ impl GlobalExecutionChamber {
                     .await?;
                 let child_segment = pattern.replace("%", index.to_string().as_str());
                 let point = create.template.point.parent.push(child_segment.clone())?;
+                let config = self.skel.machine_api.properties_config(&child_kind).await?;
                 let registration = Registration {
                     point: point.clone(),
                     kind: child_kind.clone(),
                     registry: Default::default(),
-                    properties: create.properties.clone(),
-                    owner: Point::root(),
+                    properties: hash_secrets(&config, &create.properties),
+                    owner: agent.clone().to_point(),
                     strategy: create.strategy.clone(),
                     status: Status::Ready,
                 };
@@ -184,8 +507,393 @@ impl GlobalExecutionChamber {
             //});
         }
 
+        if child_kind == Kind::FileStore {
+            self.create_filestore_root(&point, agent).await?;
+        }
+
+        // a re-published bundle must not be served from artifacts cached before.  Only this
+        // machine's caches are invalidated, see Artifacts::invalidate
+        if child_kind == Kind::Bundle {
+            self.skel.machine_api.artifacts.invalidate(&point);
+        }
+
         let record = self.skel.registry.record(&point).await?;
 
+        self.skel
+            .emit(HyperEvent::Created(Created {
+                point: point.clone(),
+                kind: record.details.stub.kind.clone().into(),
+            }))
+            .await?;
+
         Ok(record.details)
     }
+
+    /// every FileStore gets a `:/` root `File<Dir>` owned by the agent that created the
+    /// FileStore, under which all of its files are created
+    async fn create_filestore_root(&self, filestore: &Point, agent: &Agent) -> Result<(), StarErr> {
+        let point = filestore.push_file(":/".to_string())?;
+        let registration = Registration {
+            point: point.clone(),
+            kind: Kind::File(FileSubKind::Dir),
+            registry: Default::default(),
+            properties: Default::default(),
+            owner: agent.clone().to_point(),
+            strategy: Strategy::Ensure,
+            status: Status::Ready,
+        };
+        self.skel.registry.register(&registration).await?;
+        SmartLocator::new(self.skel.clone())
+            .provision(&point, StateSrc::None)
+            .await?;
+        Ok(())
+    }
+
+    /// before a [Delete] removes them from the registry, particles that keep state outside of
+    /// the registry (see [Kind::has_external_state]) are sent a [CmdMethod::Delete] on behalf of
+    /// `agent`.  Deeper particles go first so a Dir is empty by the time it is deleted.  Each
+    /// particle's record is deleted as soon as its state is gone, so a failure part way through
+    /// leaves only the particles that still have state registered.  Returns the deleted records or
+    /// the reflected core of the first particle that fails to delete its state
+    pub async fn delete_external_state(
+        &self,
+        delete: &Delete,
+        agent: &Agent,
+    ) -> Result<Result<SubstanceList, ReflectedCore>, StarErr> {
+        let mut select = delete.clone().into();
+        let selection = self.skel.registry.select(&mut select).await?;
+        let mut points = vec![];
+        for point in selection.list {
+            let point: Point = (*point).try_into()?;
+            let record = self.skel.registry.record(&point).await?;
+            if record.details.stub.kind.has_external_state() {
+                points.push(point);
+            }
+        }
+        points.sort_by_key(|point| std::cmp::Reverse(point.segments.len()));
+        let mut deleted = SubstanceList::new();
+        for point in points {
+            let mut proto = DirectedProto::ping();
+            proto.method(CmdMethod::Delete);
+            proto.agent(agent.clone());
+            proto.to(point.to_surface());
+            let pong = self.skel.star_transmitter.ping(proto).await?;
+            if !pong.core.is_ok() {
+                return Ok(Err(pong.variant.core));
+            }
+            let mut removed = self
+                .skel
+                .registry
+                .delete(&Delete {
+                    selector: point.into(),
+                })
+                .await?;
+            deleted.list.append(&mut removed.list);
+        }
+        Ok(Ok(deleted))
+    }
+
+    /// send a [HypMethod::Watch] or [HypMethod::Unwatch] for `watcher` to the star holding the
+    /// watched particle, which is where the watch is registered
+    pub async fn subscribe(
+        &self,
+        method: HypMethod,
+        watcher: Watcher,
+    ) -> Result<ReflectedCore, StarErr> {
+        let record = self.skel.registry.record(&watcher.watch.point).await?;
+        let star = record.location.star.ok_or(SpaceErr::not_found(format!(
+            "cannot watch '{}' because it has not been assigned to a star",
+            watcher.watch.point.to_string()
+        )))?;
+        let mut proto = DirectedProto::ping();
+        proto.method(method);
+        proto.to(star.to_surface().with_layer(Layer::Core));
+        proto.body(watcher.into());
+        let pong = self.skel.star_transmitter.ping(proto).await?;
+        Ok(pong.variant.core)
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use crate::global::{properties_changed, read_properties, secret_read_audit, Authorizer};
+    use crate::properties::{hash_secrets, properties_config, read_privilege, REDACTED};
+    use crate::registry::conformance;
+    use crate::registry::mem::registry::MemoryRegistry;
+    use crate::registry::Registry;
+    use crate::star::watchers::StarWatchers;
+    use starlane_space::command::common::{PropertyMod, SetProperties};
+    use starlane_space::command::direct::delete::Delete;
+    use starlane_space::command::direct::get::{Get, GetOp};
+    use starlane_space::command::direct::select::Select;
+    use starlane_space::command::direct::set::Set;
+    use starlane_space::command::Command;
+    use starlane_space::hyper::HyperEvent;
+    use starlane_space::kind::Kind;
+    use starlane_space::loc::ToSurface;
+    use starlane_space::log::Logger;
+    use starlane_space::particle::{Aspect, Details, Property, Status, Stub, Watch};
+    use starlane_space::point::Point;
+    use starlane_space::security::{
+        Access, AccessGrant, AccessGrantKind, EnumeratedAccess, EnumeratedPrivileges, Permissions,
+        PermissionsMask, Privileges,
+    };
+    use starlane_space::selector::Selector;
+    use starlane_space::substance::Substance;
+    use starlane_space::wave::Agent;
+    use starlane_space::wave::Scope;
+    use starlane_space::HYPERUSER;
+    use std::str::FromStr;
+    use std::sync::Arc;
+
+    #[test]
+    pub fn test_read_properties() {
+        let point = Point::from_str("space:users:less").unwrap();
+        let mut details = Details {
+            stub: Stub {
+                point: point.clone(),
+                kind: Kind::User,
+                status: Status::Ready,
+            },
+            properties: Default::default(),
+        };
+        for (key, value) in [
+            ("password", "pbkdf2-sha256$1$salt$hash"),
+            ("email", "less@a.io"),
+        ] {
+            let property = Property {
+                key: key.to_string(),
+                value: value.to_string(),
+                locked: false,
+            };
+            details.properties.insert(key.to_string(), property);
+        }
+        let config = properties_config(&Kind::User);
+        let get = |keys: Vec<&str>| Get {
+            point: point.clone(),
+            op: GetOp::Properties(keys.into_iter().map(|key| key.to_string()).collect()),
+        };
+
+        // without the privilege the secret is redacted and nothing is revealed
+        let (read, revealed) =
+            read_properties(&get(vec![]), details.clone(), &config, &Access::none()).unwrap();
+        assert_eq!(read.properties["password"].value, REDACTED);
+        assert_eq!(read.properties["email"].value, "less@a.io");
+        assert!(revealed.is_empty());
+
+        // `property:password:read` reveals it
+        let mut privileges = EnumeratedPrivileges::none();
+        privileges.add(read_privilege("password").as_str());
+        let access = Access::Enumerated(EnumeratedAccess {
+            permissions: EnumeratedAccess::none().permissions,
+            privileges: Privileges::Enumerated(privileges),
+        });
+        let (read, revealed) =
+            read_properties(&get(vec![]), details.clone(), &config, &access).unwrap();
+        assert_eq!(
+            read.properties["password"].value,
+            "pbkdf2-sha256$1$salt$hash"
+        );
+        assert_eq!(revealed, vec!["password".to_string()]);
+
+        // and the read is audited
+        let agent = Agent::Point(Point::from_str("space:users:fae").unwrap());
+        let (logger, message) = secret_read_audit(&Logger::default(), &agent, &point, &revealed);
+        assert_eq!(logger.get_span_attr("audit").unwrap(), "secret-read");
+        assert_eq!(logger.get_span_attr("agent").unwrap(), "space:users:fae");
+        assert_eq!(logger.get_span_attr("point").unwrap(), "space:users:less");
+        assert_eq!(logger.get_span_attr("properties").unwrap(), "password");
+        assert_eq!(
+            message,
+            "agent 'space:users:fae' read secret properties [password] of 'space:users:less'"
+        );
+
+        // only the requested keys are returned
+        let (read, revealed) =
+            read_properties(&get(vec!["email"]), details.clone(), &config, &access).unwrap();
+        assert_eq!(read.properties.len(), 1);
+        assert!(revealed.is_empty());
+    }
+
+    #[test]
+    pub fn test_properties_changed_redacts_secrets() {
+        let point = Point::from_str("space:users:less").unwrap();
+        let config = properties_config(&Kind::User);
+
+        // a watcher that may only read the user, which is all a watch requires
+        let watcher = Point::from_str("space:users:fae").unwrap().to_surface();
+        let access = Access::Enumerated(EnumeratedAccess {
+            permissions: Permissions::from_str("csd-Rwx").unwrap(),
+            privileges: Privileges::none(),
+        });
+        assert!(access.permissions().particle.read);
+        assert!(access
+            .check_privilege(read_privilege("password").as_str())
+            .is_err());
+        let watchers = StarWatchers::new();
+        watchers.watch(
+            Watch {
+                point: point.clone(),
+                aspect: Aspect::Property,
+            },
+            watcher.clone(),
+        );
+
+        let mut set = SetProperties::new();
+        for (key, value) in [("password", "s3cr3t"), ("email", "less@a.io")] {
+            set.push(PropertyMod::Set {
+                key: key.to_string(),
+                value: value.to_string(),
+                lock: false,
+            });
+        }
+        let stored = hash_secrets(&config, &set);
+        let event = properties_changed(&point, &config, &stored);
+
+        assert_eq!(watchers.watchers(&event.watch()), vec![watcher]);
+        let changed = match event {
+            HyperEvent::PropertiesChanged(changed) => changed,
+            other => panic!("expected PropertiesChanged, found {}", other),
+        };
+        assert_eq!(
+            changed.properties.get("password").unwrap().opt().unwrap(),
+            REDACTED
+        );
+        assert_eq!(
+            changed.properties.get("email").unwrap().opt().unwrap(),
+            "less@a.io"
+        );
+    }
+
+    /// `less` may read everything under `localhost:app` but only select the children of
+    /// `localhost:app` itself
+    async fn authorizer() -> (Authorizer, Agent) {
+        let registry: Registry = Arc::new(MemoryRegistry::new());
+        conformance::tree(&registry).await.unwrap();
+        let app = Point::from_str("localhost:app").unwrap();
+        let superuser = Point::from_str("localhost:users:superuser").unwrap();
+        let less = Point::from_str("localhost:app:users:less").unwrap();
+        registry
+            .register(&conformance::registration(&less, Kind::User, &app))
+            .await
+            .unwrap();
+        registry
+            .grant(&AccessGrant {
+                kind: AccessGrantKind::Super,
+                on_point: Selector::from_str("localhost+:**").unwrap(),
+                to_point: superuser.clone().into(),
+                by_particle: HYPERUSER.clone(),
+            })
+            .await
+            .unwrap();
+        // grants made by `app` are only honored once it owns itself
+        registry
+            .chown(
+                &Selector::from_str("localhost:app+:**").unwrap(),
+                &app,
+                &superuser,
+            )
+            .await
+            .unwrap();
+        for (mask, on) in [
+            ("+csd-Rwx", "localhost:app+:**"),
+            ("+cSd-rwx", "localhost:app"),
+        ] {
+            registry
+                .grant(&AccessGrant {
+                    kind: AccessGrantKind::PermissionsMask(
+                        PermissionsMask::from_str(mask).unwrap(),
+                    ),
+                    on_point: Selector::from_str(on).unwrap(),
+                    to_point: less.clone().into(),
+                    by_particle: app.clone(),
+                })
+                .await
+                .unwrap();
+        }
+        (
+            Authorizer::new(registry, Logger::default()),
+            Agent::Point(less),
+        )
+    }
+
+    #[tokio::test]
+    pub async fn test_authorize() {
+        let (authorizer, less) = authorizer().await;
+        let mechtron = Point::from_str("localhost:app:mechtron").unwrap();
+
+        let get = Command::Get(Get {
+            point: mechtron.clone(),
+            op: GetOp::Properties(vec![]),
+        });
+        let denied = authorizer
+            .authorize(&get, &less, &Scope::Full)
+            .await
+            .unwrap();
+        assert!(denied.is_none());
+
+        // read only, so setting properties is forbidden
+        let set = Command::Set(Set {
+            point: mechtron.clone(),
+            properties: SetProperties::new(),
+        });
+        let denied = authorizer
+            .authorize(&set, &less, &Scope::Full)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(denied.status.as_u16(), 403);
+
+        let delete = Command::Delete(Delete {
+            selector: Selector::from_str("localhost:app:mechtron").unwrap(),
+        });
+        let denied = authorizer
+            .authorize(&delete, &less, &Scope::Full)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(denied.status.as_u16(), 403);
+
+        // an agent that is not in the registry has no access at all
+        let anonymous = authorizer
+            .authorize(&get, &Agent::Anonymous, &Scope::Full)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(anonymous.status.as_u16(), 403);
+    }
+
+    #[tokio::test]
+    pub async fn test_select_filters_by_permission() {
+        let (authorizer, less) = authorizer().await;
+        let select = Select::new(Selector::from_str("localhost:app:**").unwrap());
+        let command = Command::Select(select.clone());
+        assert!(authorizer
+            .authorize(&command, &less, &Scope::Full)
+            .await
+            .unwrap()
+            .is_none());
+
+        // the children of `localhost:app:users` are not selectable by `less`
+        let list = authorizer
+            .select(&select, &less, &Scope::Full)
+            .await
+            .unwrap();
+        let mut points: Vec<String> = list
+            .list
+            .into_iter()
+            .map(|substance| match *substance {
+                Substance::Stub(stub) => stub.point.to_string(),
+                other => panic!("expected a Stub received: {:?}", other),
+            })
+            .collect();
+        points.sort();
+        assert_eq!(
+            points,
+            vec![
+                "localhost:app:mechtron".to_string(),
+                "localhost:app:users".to_string()
+            ]
+        );
+    }
 }
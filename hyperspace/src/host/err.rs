@@ -1,5 +1,7 @@
+use starlane_space::artifact::asynch::ArtErr;
 use std::convert::Infallible;
 use std::fmt::{Display, Formatter};
+use std::io;
 use thiserror::Error;
 
 #[derive(Clone, Debug, Error)]
@@ -19,6 +21,32 @@ impl From<Infallible> for HostErr {
     }
 }
 
+impl From<ArtErr> for HostErr {
+    fn from(err: ArtErr) -> Self {
+        HostErr::new(err.to_string())
+    }
+}
+
+impl From<io::Error> for HostErr {
+    fn from(err: io::Error) -> Self {
+        HostErr::new(err.to_string())
+    }
+}
+
+#[cfg(feature = "wasm")]
+impl From<wasmer_wasix::WasiStateCreationError> for HostErr {
+    fn from(err: wasmer_wasix::WasiStateCreationError) -> Self {
+        HostErr::new(err.to_string())
+    }
+}
+
+#[cfg(feature = "wasm")]
+impl From<wasmer_wasix::WasiRuntimeError> for HostErr {
+    fn from(err: wasmer_wasix::WasiRuntimeError) -> Self {
+        HostErr::new(err.to_string())
+    }
+}
+
 /*
 impl ToString for Err {
     fn to_string(&self) -> String {
@@ -13,6 +13,8 @@ use std::ops::{Deref, DerefMut};
 use tokio::io::AsyncWriteExt;
 
 pub mod err;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 #[derive(Clone, Eq, PartialEq, Hash)]
 pub struct ExtKey<B>
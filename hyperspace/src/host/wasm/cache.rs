@@ -1,33 +1,60 @@
 use crate::host::err;
-use crate::host::wasm::source::Source;
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+use starlane_space::point::Point;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use wasmer::{Module, Store};
 
+/// identifies a compiled module by the artifact [`Point`] it was fetched from and the
+/// sha256 of its content, so a module redeployed to the same point is compiled again
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct WasmKey {
+    pub point: Point,
+    pub hash: String,
+}
+
+impl WasmKey {
+    pub fn new(point: &Point, wasm: &[u8]) -> Self {
+        Self {
+            point: point.clone(),
+            hash: format!("{:x}", Sha256::digest(wasm)),
+        }
+    }
+
+    fn file_name(&self) -> String {
+        format!("{}-{}.ser", self.point.to_md5(), self.hash)
+    }
+}
+
 #[async_trait]
-pub trait WasmModuleCache {
-    async fn get(&mut self, key: &str, store: &Store) -> Result<Module, err::HostErr>;
+pub trait WasmModuleCache: Send + Sync {
+    async fn get(
+        &mut self,
+        key: &WasmKey,
+        wasm: &[u8],
+        store: &Store,
+    ) -> Result<Module, err::HostErr>;
 }
 
+/// keeps the modules that compiled. A failed compile is not kept so it is attempted again
+/// and a module compiled for a point replaces the one compiled for its previous content
 pub struct WasmModuleMemCache {
-    source: Box<dyn Source>,
-    map: HashMap<String, Result<Module, err::HostErr>>,
+    map: HashMap<WasmKey, Module>,
     ser: Option<SerializedCache>,
 }
 
 impl WasmModuleMemCache {
-    pub fn new(source: Box<dyn Source>) -> Self {
+    pub fn new() -> Self {
         Self {
-            source,
             map: Default::default(),
             ser: Option::None,
         }
     }
 
-    pub fn new_with_ser(source: Box<dyn Source>, ser_path: PathBuf) -> Self {
+    pub fn new_with_ser(ser_path: PathBuf) -> Self {
         let ser = SerializedCache::new(ser_path);
         Self {
-            source,
             map: Default::default(),
             ser: Some(ser),
         }
@@ -36,44 +63,38 @@ impl WasmModuleMemCache {
 
 #[async_trait]
 impl WasmModuleCache for WasmModuleMemCache {
-    async fn get(&mut self, key: &str, store: &Store) -> Result<Module, err::HostErr> {
-        async fn load(
-            source: &Box<dyn Source>,
-            key: &str,
-            store: &Store,
-        ) -> Result<Module, err::HostErr> {
-            let wasm_bytes = source.get(key).await?;
-            let module = Module::new(store, wasm_bytes).map_err(|e| e.into());
-            module
+    async fn get(
+        &mut self,
+        key: &WasmKey,
+        wasm: &[u8],
+        store: &Store,
+    ) -> Result<Module, err::HostErr> {
+        fn compile(wasm: &[u8], store: &Store) -> Result<Module, err::HostErr> {
+            Module::new(store, wasm).map_err(|e| err::HostErr::new(e.to_string()))
         }
 
-        if !self.map.contains_key(key) {
-            if let Some(ser) = &self.ser {
-                if let Option::Some(Result::Ok(module)) = ser.get(&key.to_string(), store).await {
-                    self.map.insert(key.to_string(), Result::Ok(module));
-                } else {
-                    let rtn = load(&self.source, key, store).await;
-
-                    if let Result::Ok(module) = &rtn {
-                        ser.store(&key.to_string(), module).await?;
-                    }
-                    self.map.insert(key.to_string(), rtn);
-                }
-            } else {
-                self.map
-                    .insert(key.to_string(), load(&self.source, key, store).await);
-            }
+        if let Some(module) = self.map.get(key) {
+            return Ok(module.clone());
         }
 
-        match self.map.get(key).unwrap() {
-            Ok(module) => Result::Ok(module.clone()),
-            Err(err) => Result::Err(err.clone()),
-        }
+        let module = match &self.ser {
+            Some(ser) => match ser.get(key, store).await {
+                Some(Ok(module)) => module,
+                _ => {
+                    let module = compile(wasm, store)?;
+                    ser.store(key, &module).await?;
+                    module
+                }
+            },
+            None => compile(wasm, store)?,
+        };
+
+        self.map.retain(|cached, _| cached.point != key.point);
+        self.map.insert(key.clone(), module.clone());
+        Ok(module)
     }
 }
 
-impl WasmModuleMemCache {}
-
 pub struct SerializedCache {
     path: PathBuf,
 }
@@ -83,19 +104,21 @@ impl SerializedCache {
         Self { path }
     }
 
-    pub async fn get(&self, name: &str, store: &Store) -> Option<Result<Module, err::HostErr>> {
-        let file = self.path.join(Path::new(format!("{}.ser", name).as_str()));
+    pub async fn get(&self, key: &WasmKey, store: &Store) -> Option<Result<Module, err::HostErr>> {
+        let file = self.path.join(Path::new(key.file_name().as_str()));
         if !file.exists() {
             return Option::None;
         }
         let result =
             unsafe { Module::deserialize_from_file(&store, file).map_err(|e| e.to_string()) };
 
-        Some(result.map_err(|e| e.into()))
+        Some(result.map_err(err::HostErr::new))
     }
 
-    pub async fn store(&self, name: &str, module: &Module) -> Result<(), err::HostErr> {
-        let file = self.path.join(Path::new(format!("{}.ser", name).as_str()));
-        module.serialize_to_file(file).map_err(|e| e.into())
+    pub async fn store(&self, key: &WasmKey, module: &Module) -> Result<(), err::HostErr> {
+        let file = self.path.join(Path::new(key.file_name().as_str()));
+        module
+            .serialize_to_file(file)
+            .map_err(|e| err::HostErr::new(e.to_string()))
     }
 }
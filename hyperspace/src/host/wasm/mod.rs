@@ -1,144 +1,174 @@
-mod cache;
-mod source;
+pub mod cache;
 
+use crate::driver::mechtron::{MechtronHost, MechtronHostFactory};
+use crate::driver::DriverErr;
 use crate::host::err::HostErr;
-use crate::host::wasm::cache::WasmModuleCache;
-use crate::host::FileSystemFactory;
-use starlane_hyperspace::service::OsProcess;
-use std::io::Write;
-use std::path::Path;
+use crate::host::wasm::cache::{WasmKey, WasmModuleCache};
+use async_trait::async_trait;
+use starlane_space::artifact::asynch::ArtifactFetcher;
+use starlane_space::config::mechtron::MechtronConfig;
+use starlane_space::point::Point;
+use starlane_space::wave::core::ReflectedCore;
+use starlane_space::wave::DirectedWave;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use tokio::io::AsyncReadExt;
 use tokio::runtime::Handle;
-use virtual_fs::Pipe;
-use wasmer::{Module, Store};
-use wasmer_compiler_singlepass::Singlepass;
+use tokio::sync::Mutex;
+use wasmer::sys::Singlepass;
+use wasmer::{Engine, Module, Store};
 use wasmer_wasix::runtime::task_manager::tokio::TokioTaskManager;
-use wasmer_wasix::{PluggableRuntime, WasiEnv};
+use wasmer_wasix::virtual_fs::{FileSystem, Pipe};
+use wasmer_wasix::wasmer_wasix_types::wasi::ExitCode;
+use wasmer_wasix::{PluggableRuntime, WasiEnv, WasiEnvBuilder, WasiError};
 
 pub struct WasmService {
-    store: Store,
+    fetcher: Arc<dyn ArtifactFetcher>,
     cache: Box<dyn WasmModuleCache>,
 }
 
 impl WasmService {
-    pub fn new(cache: Box<dyn WasmModuleCache>) -> Self {
-        let compiler = Singlepass::default();
-        let store = Store::new(compiler);
-
-        Self { store, cache }
+    pub fn new(fetcher: Arc<dyn ArtifactFetcher>, cache: Box<dyn WasmModuleCache>) -> Self {
+        Self { fetcher, cache }
     }
 
-    pub async fn provision<S>(
+    /// `wasm` is the artifact point of the module as referenced by `MechtronConfig::wasm`
+    pub async fn provision(
         &mut self,
-        wasm: S,
+        wasm: &Point,
         host_config: WasmHostConfig,
-    ) -> Result<WasmHost, HostErr>
-    where
-        S: ToString,
-    {
+    ) -> Result<WasmHost, HostErr> {
         let store = Store::new(Singlepass::default());
-        //let module = self.cache.get(wasm.to_string().as_str()).await?;
+        let bin = self.fetcher.fetch(wasm).await?;
+        let key = WasmKey::new(wasm, bin.as_slice());
+        let module = self.cache.get(&key, bin.as_slice(), &store).await?;
 
-        let module = self.cache.get("filestore.wasm", &store).await?;
-        //let wasm_bytes = fs::read("filestore.wasm").await?;
-        //let module = Module::new(& store,wasm_bytes).unwrap();
+        Result::Ok(WasmHost::new(module, host_config, store.engine().clone()))
+    }
+}
 
-        Result::Ok(WasmHost::new(module, host_config, store))
+/// runs each Mechtron in its own [`WasmHost`]
+pub struct WasmMechtronHostFactory {
+    service: Mutex<WasmService>,
+    config: WasmHostConfig,
+}
+
+impl WasmMechtronHostFactory {
+    pub fn new(service: WasmService, config: WasmHostConfig) -> Self {
+        Self {
+            service: Mutex::new(service),
+            config,
+        }
     }
 }
 
+#[async_trait]
+impl MechtronHostFactory for WasmMechtronHostFactory {
+    async fn provision(
+        &self,
+        _: &Point,
+        config: &MechtronConfig,
+    ) -> Result<Arc<dyn MechtronHost>, DriverErr> {
+        let host = self
+            .service
+            .lock()
+            .await
+            .provision(&config.wasm, self.config.clone())
+            .await
+            .map_err(|err| DriverErr::String(err.to_string()))?;
+        Ok(Arc::new(WasmMechtron {
+            name: config.name.clone(),
+            host,
+        }))
+    }
+}
+
+/// the guest is invoked as `mechtron <name>` with the bincode serialized [`DirectedWave`] on
+/// stdin and is expected to write the bincode serialized [`ReflectedCore`] to stdout
+pub struct WasmMechtron {
+    name: String,
+    host: WasmHost,
+}
+
+#[async_trait]
+impl MechtronHost for WasmMechtron {
+    async fn handle(&self, wave: DirectedWave) -> Result<ReflectedCore, DriverErr> {
+        let wave = bincode::serialize(&wave).map_err(|err| DriverErr::String(err.to_string()))?;
+        let mut process = self
+            .host
+            .execute_with_data(&["mechtron", self.name.as_str()], wave.as_slice())
+            .await
+            .map_err(|err| DriverErr::String(err.to_string()))?;
+        let mut out = vec![];
+        process
+            .stdout
+            .read_to_end(&mut out)
+            .await
+            .map_err(|err| DriverErr::String(err.to_string()))?;
+        bincode::deserialize(out.as_slice()).map_err(|err| DriverErr::String(err.to_string()))
+    }
+}
+
+/// the output of a guest that ran to completion
+pub struct WasmProcess {
+    pub stdout: Pipe,
+    pub stderr: Pipe,
+}
+
+/// a compiled module that can be run any number of times, every run gets a fresh [`Store`]
+/// so one run cannot leave state behind for the next
 pub struct WasmHost {
-    store: Store,
+    engine: Engine,
     module: Module,
     config: WasmHostConfig,
     runtime: Arc<PluggableRuntime>,
 }
 
 impl WasmHost {
-    fn new(module: Module, config: WasmHostConfig, store: Store) -> Self {
-        let runtime = Arc::new(PluggableRuntime::new(Arc::new(TokioTaskManager::new(
-            Handle::current(),
-        ))));
+    fn new(module: Module, config: WasmHostConfig, engine: Engine) -> Self {
+        let mut runtime = PluggableRuntime::new(Arc::new(TokioTaskManager::new(Handle::current())));
+        runtime.set_engine(engine.clone());
+        let runtime = Arc::new(runtime);
         Self {
-            store,
+            engine,
             module,
             config,
             runtime,
         }
     }
-    pub async fn execute<I, Arg>(&mut self, args: I) -> Result<OsProcess, HostErr>
+
+    pub async fn execute<I, Arg>(&self, args: I) -> Result<WasmProcess, HostErr>
     where
-        I: IntoIterator<Item=Arg>,
+        I: IntoIterator<Item = Arg>,
         Arg: AsRef<[u8]>,
     {
         self.execute_with_data(args, &[]).await
     }
 
     pub async fn execute_with_data<I, Arg>(
-        &mut self,
+        &self,
         args: I,
         stdin: &[u8],
-    ) -> Result<OsProcess, HostErr>
+    ) -> Result<WasmProcess, HostErr>
     where
-        I: IntoIterator<Item=Arg>,
+        I: IntoIterator<Item = Arg>,
         Arg: AsRef<[u8]>,
     {
-        let mut builder = WasiEnv::builder("wasm program").args(args);
-
         let (mut stdin_tx, stdin_rx) = Pipe::channel();
-        let (stdout_tx, stdout_rx) = Pipe::channel();
-        let (stderr_tx, stderr_rx) = Pipe::channel();
-
-        std::io::Write::write_all(&mut stdin_tx, stdin).unwrap();
-        //        stdin_tx.flush().unwrap();
+        std::io::Write::write_all(&mut stdin_tx, stdin)?;
         stdin_tx.close();
 
-        builder = builder
-            .stdin(Box::new(stdin_rx))
-            .stdout(Box::new(stdout_tx))
-            .stderr(Box::new(stderr_tx));
-
-        if let Option::Some(ref fs_config) = self.config.fs {
-            for d in &fs_config.pre_opened_dirs {
-                builder = builder.preopen_dir(Path::new(d))?;
-            }
-            builder = builder.fs(Box::new(
-                fs_config
-                    .fs_factory
-                    .create(Handle::current().clone())
-                    .unwrap(),
-            ));
-
-            builder = builder.env("PWD", fs_config.pwd.clone());
-        };
-
-        if self.config.runtime {
-            builder = builder.runtime(self.runtime.clone());
-        }
-
-        builder = builder.current_dir("/");
-        //builder = builder.stdout(Box::new(io::stdout()));
-
-        //builder.run(self.module.clone())?;
-        builder.run_with_store(self.module.clone(), &mut self.store)?;
-
-        /*
-        Ok(OsProcess {
-            stdout: stdout_rx,
-            stderr: stderr_rx,
-        })
-
-         */
-        todo!()
+        self.execute_with_stdin(args, stdin_rx).await
     }
 
+    /// a guest that exits with a non zero code is an error
     pub async fn execute_with_stdin<I, Arg>(
-        &mut self,
+        &self,
         args: I,
         stdin: Pipe,
-    ) -> Result<OsProcess, HostErr>
+    ) -> Result<WasmProcess, HostErr>
     where
-        I: IntoIterator<Item=Arg>,
+        I: IntoIterator<Item = Arg>,
         Arg: AsRef<[u8]>,
     {
         let mut builder = WasiEnv::builder("wasm program").args(args);
@@ -155,12 +185,7 @@ impl WasmHost {
             for d in &fs_config.pre_opened_dirs {
                 builder = builder.preopen_dir(Path::new(d))?;
             }
-            builder = builder.fs(Box::new(
-                fs_config
-                    .fs_factory
-                    .create(Handle::current().clone())
-                    .unwrap(),
-            ));
+            builder = builder.fs(fs_config.fs_factory.create(Handle::current())?);
 
             builder = builder.env("PWD", fs_config.pwd.clone());
         };
@@ -169,32 +194,72 @@ impl WasmHost {
             builder = builder.runtime(self.runtime.clone());
         }
 
-        builder = builder.current_dir("/");
-        //builder = builder.stdout(Box::new(io::stdout()));
+        // the module was compiled by this engine so the guest must run on it too
+        builder = builder.engine(self.engine.clone()).current_dir("/");
 
-        //builder.run(self.module.clone())?;
-        builder.run_with_store(self.module.clone(), &mut self.store)?;
+        // the guest runs synchronously until it exits
+        let module = self.module.clone();
+        let mut store = Store::new(self.engine.clone());
+        tokio::task::spawn_blocking(move || run(builder, module, &mut store))
+            .await
+            .map_err(|err| HostErr::new(err.to_string()))??;
 
-        /*
-        Ok(OsProcess {
+        Ok(WasmProcess {
             stdout: stdout_rx,
             stderr: stderr_rx,
         })
-         */
-        todo!()
     }
 }
 
-pub struct WasmHostBuilder {}
+/// instantiates the guest and calls its `_start`, a nonzero exit code is an error
+fn run(builder: WasiEnvBuilder, module: Module, store: &mut Store) -> Result<(), HostErr> {
+    let (instance, env) = builder.instantiate(module, store)?;
+    let start = instance
+        .exports
+        .get_function("_start")
+        .map_err(|err| HostErr::new(err.to_string()))?;
+
+    let code = match start.call(store, &[]) {
+        Ok(_) => ExitCode::from(0u16),
+        Err(err) => match err.downcast::<WasiError>() {
+            Ok(WasiError::Exit(code)) => code,
+            Ok(err) => return Err(HostErr::new(err.to_string())),
+            Err(err) => return Err(HostErr::new(err.to_string())),
+        },
+    };
+
+    env.on_exit(store, Some(code));
+
+    if code.is_success() {
+        Ok(())
+    } else {
+        Err(HostErr::new(format!("wasm guest exited with {}", code)))
+    }
+}
 
-impl WasmHostBuilder {}
+/// creates the file system a guest sees
+pub trait FileSystemFactory: Send + Sync {
+    fn create(&self, handle: Handle) -> Result<Box<dyn FileSystem + Send + Sync>, HostErr>;
+}
 
-#[derive(Clone)]
-enum WasmInterfaceKind {
-    Cli,
+/// exposes a directory of the host as the root of the guest's file system
+pub struct RootFileSystemFactory {
+    root: PathBuf,
 }
 
-impl WasmInterfaceKind {}
+impl RootFileSystemFactory {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+}
+
+impl FileSystemFactory for RootFileSystemFactory {
+    fn create(&self, handle: Handle) -> Result<Box<dyn FileSystem + Send + Sync>, HostErr> {
+        let fs = wasmer_wasix::virtual_fs::host_fs::FileSystem::new(handle, self.root.clone())
+            .map_err(|err| HostErr::new(err.to_string()))?;
+        Ok(Box::new(fs))
+    }
+}
 
 #[derive(Clone)]
 pub struct WasmHostConfig {
@@ -219,7 +284,6 @@ impl WasmHostConfig {
 
 pub struct WasmHostConfigBuilder {
     pub runtime: bool,
-    pub pwd: String,
     pub fs: Option<FsConfigBuilder>,
 }
 
@@ -250,7 +314,7 @@ impl WasmHostConfigBuilder {
                 None => None,
                 Some(builder) => Some(builder.build()),
             },
-            runtime: false,
+            runtime: self.runtime,
         }
     }
 }
@@ -258,7 +322,6 @@ impl WasmHostConfigBuilder {
 impl Default for WasmHostConfigBuilder {
     fn default() -> Self {
         WasmHostConfigBuilder {
-            pwd: ".".to_string(),
             runtime: false,
             fs: None,
         }
@@ -272,19 +335,8 @@ pub struct FsConfig {
     pub pwd: String,
 }
 
-pub enum FsKind {
-    Local,
-}
-
-impl Default for FsKind {
-    fn default() -> Self {
-        FsKind::Local
-    }
-}
-
 pub struct FsConfigBuilder {
     fs_factory: Arc<dyn FileSystemFactory>,
-    kind: FsKind,
     pre_opened_dirs: Vec<String>,
     pwd: String,
 }
@@ -293,7 +345,6 @@ impl FsConfigBuilder {
     pub fn new(fs_factory: Arc<dyn FileSystemFactory>) -> Self {
         Self {
             fs_factory,
-            kind: Default::default(),
             pre_opened_dirs: vec![],
             pwd: "./".into(),
         }
@@ -326,85 +377,168 @@ impl FsConfigBuilder {
 
 #[cfg(test)]
 pub mod test {
-    use crate::host::wasm::cache::WasmModuleMemCache;
-    use crate::host::wasm::source::FileSystemSrc;
-    use crate::host::wasm::{WasmHostConfig, WasmService};
-    use crate::host::{FileSystemFactory, RootFileSystemFactory};
+    use crate::driver::mechtron::MechtronHostFactory;
+    use crate::host::wasm::cache::{WasmKey, WasmModuleCache, WasmModuleMemCache};
+    use crate::host::wasm::{WasmHostConfig, WasmMechtronHostFactory, WasmService};
+    use crate::test_util::temp_dir;
+    use starlane_space::artifact::asynch::MapFetcher;
+    use starlane_space::config::mechtron::MechtronConfig;
+    use starlane_space::loc::ToSurface;
+    use starlane_space::point::Point;
+    use starlane_space::substance::Substance;
+    use starlane_space::wave::core::ReflectedCore;
+    use starlane_space::wave::DirectedProto;
+    use std::str::FromStr;
+    use std::sync::Arc;
     use tokio::io::AsyncReadExt;
-    use tokio::runtime::Handle;
-    use virtual_fs::{FileSystem, Pipe};
-
-    #[tokio::test]
-    pub async fn test_fs() {
-        // tokio::fs::remove_dir_all("./test-dir").await;
-        //tokio::fs::create_dir_all("./test-dir").await;
-        let fs_factory = Arc::new(RootFileSystemFactory::new("./test-dir".into()));
-        let fs = fs_factory.create(Handle::current()).unwrap();
-        let paths = fs.read_dir(Path::new("/test-dir")).unwrap();
-        for path in paths {
-            let path = path.unwrap().path();
-            println!("{}", path.display());
-        }
-        let mut file = fs.new_open_options().open("blah.txt").unwrap();
-
-        //virtual_fs::AsyncWriteExt::write_all(& mut file, "hello".as_bytes()).await.unwrap();
+    use wasmer::sys::Singlepass;
+    use wasmer::Store;
+
+    /// a WASI guest that copies its stdin to its stdout
+    pub const ECHO: &str = r#"
+(module
+  (import "wasi_snapshot_preview1" "fd_read"
+    (func $fd_read (param i32 i32 i32 i32) (result i32)))
+  (import "wasi_snapshot_preview1" "fd_write"
+    (func $fd_write (param i32 i32 i32 i32) (result i32)))
+  (memory (export "memory") 1)
+  (func (export "_start")
+    (local $n i32)
+    (block $done
+      (loop $copy
+        (i32.store (i32.const 0) (i32.const 64))
+        (i32.store (i32.const 4) (i32.const 1024))
+        (drop (call $fd_read (i32.const 0) (i32.const 0) (i32.const 1) (i32.const 8)))
+        (local.set $n (i32.load (i32.const 8)))
+        (br_if $done (i32.eqz (local.get $n)))
+        (i32.store (i32.const 4) (local.get $n))
+        (drop (call $fd_write (i32.const 1) (i32.const 0) (i32.const 1) (i32.const 8)))
+        (br $copy)))))
+"#;
+
+    /// a WASI guest that exits with code `1`
+    pub const FAIL: &str = r#"
+(module
+  (import "wasi_snapshot_preview1" "proc_exit" (func $proc_exit (param i32)))
+  (memory (export "memory") 1)
+  (func (export "_start")
+    (call $proc_exit (i32.const 1))))
+"#;
+
+    /// a WASI guest that ignores its stdin and writes `out` to its stdout
+    pub fn constant(out: &[u8]) -> String {
+        let data: String = out.iter().map(|b| format!("\\{:02x}", b)).collect();
+        format!(
+            r#"
+(module
+  (import "wasi_snapshot_preview1" "fd_write"
+    (func $fd_write (param i32 i32 i32 i32) (result i32)))
+  (memory (export "memory") 1)
+  (data (i32.const 64) "{}")
+  (func (export "_start")
+    (i32.store (i32.const 0) (i32.const 64))
+    (i32.store (i32.const 4) (i32.const {}))
+    (drop (call $fd_write (i32.const 1) (i32.const 0) (i32.const 1) (i32.const 8)))))
+"#,
+            data,
+            out.len()
+        )
     }
 
     #[tokio::test]
-    pub async fn test() {
-        tokio::fs::remove_dir_all("./test-dir").await;
-        tokio::fs::create_dir_all("./test-dir").await;
-
-        /*
-        tokio::fs::create_dir_all("./test-dir/subdir").await;
-        tokio::fs::write("./test-dir/test1.txt", "test1").await;
-        tokio::fs::write("./test-dir/test2.txt", "test2").await;
-        tokio::fs::write("./test-dir/subdir/sub1.txt", "test1").await;
-        tokio::fs::write("./test-dir/subdir/sub2.txt", "test2").await;
-
-         */
-        println!("starting test");
-        let source = Box::new(FileSystemSrc::new("."));
-        let cache = Box::new(WasmModuleMemCache::new_with_ser(source, ".".into()));
-        let mut service = WasmService::new(cache);
-        let mut builder = WasmHostConfig::builder();
-        let fs_factory = Arc::new(RootFileSystemFactory::new("./test-dir".into()));
-        builder.fs(fs_factory, |fs_builder| {
-            fs_builder.preopen("/");
-            fs_builder.pwd("/");
-        });
-        builder.runtime(false);
-        let config = builder.build();
-        let mut host = service.provision("filestore.wasm", config).await.unwrap();
-
-        //        let mut process = host.execute(&["test"]).await.unwrap();
-        let mut process = host.execute(&["mkdir", "subdir"]).await.unwrap();
-        let mut process = host.execute(&["exists", "subdir"]).await.unwrap();
-        assert!(host.execute(&["exists", "badfile"]).await.is_err());
-
-        let (mut stdin_tx, stdin_rx) = Pipe::channel();
-
-        std::io::Write::write_all(&mut stdin_tx, "hello you happy people".as_bytes()).unwrap();
-        stdin_tx.close();
-        host.execute_with_stdin(&["write", "/file1.txt"], stdin_rx)
+    pub async fn test_provision() {
+        let echo = Point::from_str("hyper:repo:boot:1.0.0:/wasm/echo.wasm").unwrap();
+        let hello = Point::from_str("hyper:repo:boot:1.0.0:/wasm/hello.wasm").unwrap();
+        let fail = Point::from_str("hyper:repo:boot:1.0.0:/wasm/fail.wasm").unwrap();
+        let mut fetcher = MapFetcher::new();
+        fetcher
+            .map
+            .insert(echo.clone(), Arc::new(ECHO.as_bytes().to_vec()));
+        fetcher
+            .map
+            .insert(hello.clone(), Arc::new(constant(b"hello").into_bytes()));
+        fetcher
+            .map
+            .insert(fail.clone(), Arc::new(FAIL.as_bytes().to_vec()));
+
+        let dir = temp_dir("wasm-cache");
+        let ser = dir.path().to_path_buf();
+        let cache = Box::new(WasmModuleMemCache::new_with_ser(ser.clone()));
+        let mut service = WasmService::new(Arc::new(fetcher), cache);
+
+        // each module is found by its own point
+        let host = service
+            .provision(&echo, WasmHostConfig::default())
             .await
             .unwrap();
+        let mut process = host
+            .execute_with_data(&["echo"], "hello you happy people".as_bytes())
+            .await
+            .unwrap();
+        let mut out = String::new();
+        process.stdout.read_to_string(&mut out).await.unwrap();
+        assert_eq!(out, "hello you happy people");
 
-        //        let mut process = host.execute(&["list"]).await.unwrap();
-
-        // process.stdin.close();
-        println!("it worked i guess?");
+        let host = service
+            .provision(&hello, WasmHostConfig::default())
+            .await
+            .unwrap();
+        let mut process = host.execute(&["hello"]).await.unwrap();
+        let mut out = String::new();
+        process.stdout.read_to_string(&mut out).await.unwrap();
+        assert_eq!(out, "hello");
 
-        let mut both = AsyncReadExt::chain(process.stdout, process.stderr);
+        let host = service
+            .provision(&fail, WasmHostConfig::default())
+            .await
+            .unwrap();
+        assert!(host.execute(&["fail"]).await.is_err());
 
-        let mut out = String::new();
-        both.read_to_string(&mut out).await.unwrap();
+        // every compiled module was also serialized
+        let mut dir = tokio::fs::read_dir(&ser).await.unwrap();
+        let mut count = 0;
+        while let Some(_) = dir.next_entry().await.unwrap() {
+            count += 1;
+        }
+        assert_eq!(count, 3);
+    }
 
-        println!("{}", out);
+    #[tokio::test]
+    pub async fn test_mechtron() {
+        let wasm = Point::from_str("hyper:repo:boot:1.0.0:/wasm/pong.wasm").unwrap();
+        let pong = ReflectedCore::ok_body(Substance::Text("pong".to_string()));
+        let mut fetcher = MapFetcher::new();
+        fetcher.map.insert(
+            wasm.clone(),
+            Arc::new(constant(bincode::serialize(&pong).unwrap().as_slice()).into_bytes()),
+        );
+        let service = WasmService::new(Arc::new(fetcher), Box::new(WasmModuleMemCache::new()));
+        let factory = WasmMechtronHostFactory::new(service, WasmHostConfig::default());
+
+        let point = Point::from_str("localhost:app:mechtron").unwrap();
+        let config = MechtronConfig {
+            wasm,
+            name: "pong".to_string(),
+        };
+        let host = factory.provision(&point, &config).await.unwrap();
+
+        let mut ping = DirectedProto::ping();
+        ping.to(point.clone().to_surface());
+        ping.from(Point::from_str("localhost:app").unwrap().to_surface());
+        ping.body(Substance::Text("ping".to_string()));
+        let reflected = host.handle(ping.build().unwrap()).await.unwrap();
+        assert_eq!(reflected, pong);
+    }
 
-        let mut err = String::new();
-        both.read_to_string(&mut err).await.unwrap();
+    /// a module that failed to compile is compiled again the next time it is asked for
+    #[tokio::test]
+    pub async fn test_compile_err_not_cached() {
+        let store = Store::new(Singlepass::default());
+        let point = Point::from_str("hyper:repo:boot:1.0.0:/wasm/echo.wasm").unwrap();
+        let key = WasmKey::new(&point, ECHO.as_bytes());
+        let mut cache = WasmModuleMemCache::new();
 
-        println!("{}", err);
+        assert!(cache.get(&key, b"(module", &store).await.is_err());
+        assert!(cache.get(&key, ECHO.as_bytes(), &store).await.is_ok());
     }
 }
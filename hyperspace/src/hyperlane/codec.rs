@@ -0,0 +1,212 @@
+//! Wave frames on a hyperlane are a big endian `u32` length followed by that many bytes.
+//! After the version handshake each side sends an offer such as `bincode,json;zstd`, the
+//! codecs it can decode followed by the compressions it can inflate.  From then on every
+//! wave frame starts with a single header byte (`0` plain, `1` zstd) followed by the wave
+//! in the negotiated codec, so a client that only speaks `json` can join a hyperlane
+use starlane_space::err::SpaceErr;
+use starlane_space::wave::Wave;
+use std::str::FromStr;
+
+/// the default largest frame a hyperlane will read before closing the connection
+pub static MAX_FRAME_SIZE: usize = 32 * 1024 * 1024;
+
+/// waves at least this large are compressed when the remote accepts compression
+pub static COMPRESS_THRESHOLD: usize = 16 * 1024;
+
+/// a wave serialization.  When both sides offer more than one codec the one listed first
+/// here wins
+#[derive(Clone, Copy, Debug, Eq, PartialEq, strum_macros::Display, strum_macros::EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum FrameCodec {
+    Bincode,
+    /// self describing, for clients that are not written in Rust
+    Json,
+}
+
+impl FrameCodec {
+    pub fn all() -> Vec<FrameCodec> {
+        vec![FrameCodec::Bincode, FrameCodec::Json]
+    }
+
+    pub fn encode(&self, wave: &Wave) -> Result<Vec<u8>, SpaceErr> {
+        match self {
+            FrameCodec::Bincode => Ok(bincode::serialize(wave)?),
+            FrameCodec::Json => serde_json::to_vec(wave).map_err(SpaceErr::map),
+        }
+    }
+
+    pub fn decode(&self, data: &[u8]) -> Result<Wave, SpaceErr> {
+        match self {
+            FrameCodec::Bincode => Ok(bincode::deserialize(data)?),
+            FrameCodec::Json => serde_json::from_slice(data).map_err(SpaceErr::map),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, strum_macros::Display, strum_macros::EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum FrameCompression {
+    Zstd,
+}
+
+impl FrameCompression {
+    fn header(&self) -> u8 {
+        match self {
+            FrameCompression::Zstd => 1,
+        }
+    }
+}
+
+/// what this side of a hyperlane offers during the handshake
+#[derive(Clone, Debug)]
+pub struct FrameConfig {
+    pub max_frame_size: usize,
+    pub codecs: Vec<FrameCodec>,
+    pub compression: Vec<FrameCompression>,
+    pub compress_threshold: usize,
+}
+
+impl Default for FrameConfig {
+    fn default() -> Self {
+        Self {
+            max_frame_size: MAX_FRAME_SIZE.clone(),
+            codecs: FrameCodec::all(),
+            compression: vec![FrameCompression::Zstd],
+            compress_threshold: COMPRESS_THRESHOLD.clone(),
+        }
+    }
+}
+
+impl FrameConfig {
+    pub fn offer(&self) -> String {
+        let list = |items: Vec<String>| items.join(",");
+        format!(
+            "{};{}",
+            list(self.codecs.iter().map(|c| c.to_string()).collect()),
+            list(self.compression.iter().map(|c| c.to_string()).collect())
+        )
+    }
+
+    /// agree on a [`FrameProtocol`] with the remote's offer.  Codecs and compressions
+    /// this side does not know are ignored
+    pub fn negotiate(&self, offer: &str) -> Result<FrameProtocol, SpaceErr> {
+        let (codecs, compression) = offer.split_once(';').unwrap_or((offer, ""));
+        let codecs: Vec<FrameCodec> = codecs
+            .split(',')
+            .filter_map(|codec| FrameCodec::from_str(codec.trim()).ok())
+            .collect();
+        let remote_compression: Vec<FrameCompression> = compression
+            .split(',')
+            .filter_map(|compression| FrameCompression::from_str(compression.trim()).ok())
+            .collect();
+
+        let codec = FrameCodec::all()
+            .into_iter()
+            .find(|codec| self.codecs.contains(codec) && codecs.contains(codec))
+            .ok_or(SpaceErr::str(format!(
+                "no codec in common. offered: '{}' accepted: '{}'",
+                offer,
+                self.offer()
+            )))?;
+
+        Ok(FrameProtocol {
+            codec,
+            compression: remote_compression.first().cloned(),
+            compress_threshold: self.compress_threshold,
+            max_frame_size: self.max_frame_size,
+        })
+    }
+}
+
+/// how waves are written and read once the handshake has completed
+#[derive(Clone, Debug)]
+pub struct FrameProtocol {
+    pub codec: FrameCodec,
+    /// the compression the remote accepts, if any
+    pub compression: Option<FrameCompression>,
+    pub compress_threshold: usize,
+    pub max_frame_size: usize,
+}
+
+impl FrameProtocol {
+    pub fn encode(&self, wave: &Wave) -> Result<Vec<u8>, SpaceErr> {
+        let data = self.codec.encode(wave)?;
+        match &self.compression {
+            Some(compression) if data.len() >= self.compress_threshold => {
+                let mut frame = vec![compression.header()];
+                frame.append(&mut zstd::bulk::compress(data.as_slice(), 0)?);
+                Ok(frame)
+            }
+            _ => {
+                let mut frame = Vec::with_capacity(data.len() + 1);
+                frame.push(0);
+                frame.extend(data);
+                Ok(frame)
+            }
+        }
+    }
+
+    pub fn decode(&self, frame: &[u8]) -> Result<Wave, SpaceErr> {
+        match frame.split_first() {
+            Some((0, data)) => self.codec.decode(data),
+            Some((1, data)) => {
+                // inflating is bounded by the same limit as reading
+                let data = zstd::bulk::decompress(data, self.max_frame_size)?;
+                self.codec.decode(data.as_slice())
+            }
+            Some((header, _)) => Err(SpaceErr::str(format!("unknown frame header: {}", header))),
+            None => Err(SpaceErr::str("empty frame")),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use crate::hyperlane::codec::{FrameCodec, FrameCompression, FrameConfig};
+    use starlane_space::hyper::Knock;
+    use starlane_space::substance::{Bin, Substance};
+    use starlane_space::wave::{PingCore, Wave, WaveVariantDef};
+
+    fn wave(size: usize) -> Wave {
+        let mut knock = Knock::default();
+        knock.auth = Box::new(Substance::Bin(Bin::from(vec![7u8; size])));
+        let wave: WaveVariantDef<PingCore> = knock.into();
+        wave.to_wave()
+    }
+
+    #[test]
+    pub fn test_negotiate() {
+        let config = FrameConfig::default();
+        assert_eq!(config.offer(), "bincode,json;zstd");
+
+        let protocol = config.negotiate("json").unwrap();
+        assert_eq!(protocol.codec, FrameCodec::Json);
+        assert_eq!(protocol.compression, None);
+
+        let protocol = config.negotiate("msgpack,json,bincode;zstd").unwrap();
+        assert_eq!(protocol.codec, FrameCodec::Bincode);
+        assert_eq!(protocol.compression, Some(FrameCompression::Zstd));
+
+        assert!(config.negotiate("msgpack;zstd").is_err());
+    }
+
+    #[test]
+    pub fn test_round_trip() {
+        let config = FrameConfig::default();
+        for offer in ["bincode;zstd", "json;zstd", "json"] {
+            let protocol = config.negotiate(offer).unwrap();
+            for size in [16, 64 * 1024] {
+                let wave = wave(size);
+                let frame = protocol.encode(&wave).unwrap();
+                let compressed =
+                    protocol.compression.is_some() && size >= config.compress_threshold;
+                assert_eq!(frame[0], if compressed { 1 } else { 0 });
+                let decoded = protocol.decode(frame.as_slice()).unwrap();
+                assert_eq!(
+                    protocol.codec.encode(&decoded).unwrap(),
+                    protocol.codec.encode(&wave).unwrap()
+                );
+            }
+        }
+    }
+}
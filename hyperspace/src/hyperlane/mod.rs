@@ -1,4 +1,7 @@
+pub mod codec;
 pub mod tcp;
+#[cfg(unix)]
+pub mod unix;
 
 use async_trait::async_trait;
 use dashmap::DashMap;
@@ -7,19 +10,23 @@ use once_cell::sync::Lazy;
 use starlane_macros::{push_loc, push_mark};
 /// `quic` is meant to be a drop in replacement for `tcp`.  the Quic networking protocol
 /// has many advantages over TCP and Starlane will benefit from quic immensely...
-//pub mod quic;
+pub mod quic;
+use crate::password;
+use crate::registry::Registry;
 use starlane_space::err::SpaceErr;
+use starlane_space::kind::BaseKind;
+use starlane_space::loc::ToBaseKind;
 use starlane_space::hyper::{Greet, InterchangeKind, Knock};
 use starlane_space::loc::{Layer, PointFactory, Surface, ToSurface};
 use starlane_space::log::{Logger, Tracker};
 use starlane_space::point::Point;
-use starlane_space::substance::{Substance, Token};
+use starlane_space::substance::{Substance, SubstanceMap, Token};
 use starlane_space::wave::core::ext::ExtMethod;
 use starlane_space::wave::exchange::asynch::{
     Exchanger, ProtoTransmitter, ProtoTransmitterBuilder, Router, TxRouter,
 };
 use starlane_space::wave::exchange::SetStrategy;
-use starlane_space::wave::{Agent, DirectedProto, HyperWave, Wave};
+use starlane_space::wave::{Agent, DirectedProto, HyperWave, Scope, Wave};
 use starlane_space::VERSION;
 use std::collections::{HashMap, HashSet};
 use std::ops::{Deref, DerefMut};
@@ -221,6 +228,21 @@ impl HyperwayEndpoint {
         });
     }
 
+    /// stamps every wave sent through this endpoint with `scope` (see [ScopeTransform])
+    pub fn scoped(mut self, scope: Scope) -> Self {
+        let (tx, mut rx) = mpsc::channel(1024);
+        let end_tx = std::mem::replace(&mut self.tx, tx);
+        let transform = ScopeTransform::new(scope);
+        tokio::spawn(async move {
+            while let Some(wave) = rx.recv().await {
+                if end_tx.send(transform.filter(wave)).await.is_err() {
+                    break;
+                }
+            }
+        });
+        self
+    }
+
     pub fn add_drop_tx(&mut self, drop_tx: oneshot::Sender<()>) {
         self.drop_tx.replace(drop_tx);
     }
@@ -245,6 +267,8 @@ impl Drop for HyperwayEndpoint {
 pub struct HyperwayStub {
     pub agent: Agent,
     pub remote: Surface,
+    /// every wave entering through this hyperway is stamped with this [Scope]
+    pub scope: Scope,
 }
 
 impl From<Greet> for HyperwayStub {
@@ -252,6 +276,7 @@ impl From<Greet> for HyperwayStub {
         Self {
             agent: greet.agent,
             remote: greet.surface,
+            scope: greet.scope,
         }
     }
 }
@@ -261,11 +286,21 @@ impl HyperwayStub {
         Self {
             agent: remote.to_agent(),
             remote,
+            scope: Scope::Full,
         }
     }
 
     pub fn new(remote: Surface, agent: Agent) -> Self {
-        Self { agent, remote }
+        Self {
+            agent,
+            remote,
+            scope: Scope::Full,
+        }
+    }
+
+    pub fn with_scope(mut self, scope: Scope) -> Self {
+        self.scope = scope;
+        self
     }
 }
 
@@ -310,6 +345,26 @@ impl HyperTransform for AgentTransform {
     }
 }
 
+/// overrides whatever [Scope] the remote put on a wave with the [Scope] of its session so a
+/// client cannot widen its own scope
+#[derive(Clone)]
+pub struct ScopeTransform {
+    scope: Scope,
+}
+
+impl ScopeTransform {
+    pub fn new(scope: Scope) -> Self {
+        Self { scope }
+    }
+}
+
+impl HyperTransform for ScopeTransform {
+    fn filter(&self, mut wave: Wave) -> Wave {
+        wave.set_scope(self.scope.clone());
+        wave
+    }
+}
+
 #[derive(Clone)]
 pub struct LayerTransform {
     layer: Layer,
@@ -725,6 +780,7 @@ impl HyperGreeter for SimpleGreeter {
             agent: stub.agent,
             hop: self.hop.clone(),
             transport: self.transport.clone(),
+            scope: stub.scope.clone(),
         })
     }
 }
@@ -738,11 +794,23 @@ pub trait HyperAuthenticator: Send + Sync + Clone + Sized {
 pub struct TokenAuthenticator {
     pub token: Token,
     pub agent: Agent,
+    pub scope: Scope,
 }
 
 impl TokenAuthenticator {
     pub fn new(agent: Agent, token: Token) -> Self {
-        Self { agent, token }
+        Self {
+            agent,
+            token,
+            scope: Scope::Full,
+        }
+    }
+
+    /// limit sessions opened with this token to `scope`, for example a least privilege
+    /// token handed to a CI job
+    pub fn with_scope(mut self, scope: Scope) -> Self {
+        self.scope = scope;
+        self
     }
 }
 
@@ -756,6 +824,7 @@ impl HyperAuthenticator for TokenAuthenticator {
                     remote: knock
                         .remote
                         .ok_or::<SpaceErr>("expected a remote entry selection".into())?,
+                    scope: self.scope.clone(),
                 })
             } else {
                 Err(SpaceErr::new(500, "invalid token"))
@@ -804,6 +873,7 @@ impl HyperAuthenticator for TokenAuthenticatorWithRemoteWhitelist {
                     Ok(HyperwayStub {
                         agent: self.agent.clone(),
                         remote,
+                        scope: Scope::Full,
                     })
                 } else {
                     Err(SpaceErr::new(500, "remote is not part of the whitelist"))
@@ -827,6 +897,7 @@ impl HyperAuthenticator for AnonHyperAuthenticator {
         Ok(HyperwayStub {
             agent: Agent::Anonymous,
             remote,
+            scope: Scope::Full,
         })
     }
 }
@@ -856,6 +927,7 @@ impl HyperAuthenticator for AnonHyperAuthenticatorAssignEndPoint {
         Ok(HyperwayStub {
             agent: Agent::Anonymous,
             remote,
+            scope: Scope::Full,
         })
     }
 }
@@ -890,6 +962,111 @@ impl HyperAuthenticator for TokensFromHeavenHyperAuthenticatorAssignEndPoint {
     }
 }
 
+/// authenticates a [`Knock`] against the `User` particles of a `UserBase`.  The knock's auth
+/// is either a [`Substance::Map`] holding a `username` and a `password`, or a
+/// [`Substance::Token`] of the form `<username>:<api-token>`.  The secret is checked against
+/// the user's hashed `password` or `api-token` property and the user's [`Point`] becomes the
+/// [`Agent`] of the hyperway
+#[derive(Clone)]
+pub struct CredentialHyperAuthenticator {
+    userbase: Point,
+    registry: Registry,
+    remote_point_factory: Arc<dyn PointFactory>,
+    logger: Logger,
+}
+
+impl CredentialHyperAuthenticator {
+    pub fn new(
+        userbase: Point,
+        registry: Registry,
+        remote_point_factory: Arc<dyn PointFactory>,
+        logger: Logger,
+    ) -> Self {
+        Self {
+            userbase,
+            registry,
+            remote_point_factory,
+            logger,
+        }
+    }
+
+    /// the reason a knock was refused is logged, the knocker only learns that it was refused
+    fn denied(&self, reason: String) -> SpaceErr {
+        self.logger.warn(reason);
+        SpaceErr::new(401, "invalid credentials")
+    }
+
+    async fn user(&self, username: &str, property: &str, secret: &str) -> Result<Point, SpaceErr> {
+        let user = self
+            .userbase
+            .push(username)
+            .map_err(|_| self.denied(format!("illegal username: '{}'", username)))?;
+        let record = self
+            .registry
+            .record(&user)
+            .await
+            .map_err(|_| self.denied(format!("user not found: '{}'", user.to_string())))?;
+        if record.details.stub.kind.to_base() != BaseKind::User {
+            return Err(self.denied(format!("not a User: '{}'", user.to_string())));
+        }
+        let properties = self
+            .registry
+            .get_properties(&user)
+            .await
+            .map_err(|err| self.denied(err.to_string()))?;
+        match properties.get(property) {
+            Some(hash) if password::verify(secret, hash.value.as_str()) => Ok(user),
+            _ => Err(self.denied(format!(
+                "{} mismatch for '{}'",
+                property,
+                user.to_string()
+            ))),
+        }
+    }
+}
+
+#[async_trait]
+impl HyperAuthenticator for CredentialHyperAuthenticator {
+    async fn auth(&self, knock: Knock) -> Result<HyperwayStub, SpaceErr> {
+        let text = |map: &SubstanceMap, key: &str| match map.get(key) {
+            Some(Substance::Text(text)) => Some(text.clone()),
+            _ => None,
+        };
+        let user = match &*knock.auth {
+            Substance::Map(map) => {
+                let username = text(map, "username")
+                    .ok_or(SpaceErr::new(401, "expected a 'username'"))?;
+                let password = text(map, "password")
+                    .ok_or(SpaceErr::new(401, "expected a 'password'"))?;
+                self.user(username.as_str(), "password", password.as_str())
+                    .await?
+            }
+            Substance::Token(token) => {
+                let (username, secret) = token
+                    .split_once(':')
+                    .ok_or(SpaceErr::new(401, "expected token '<username>:<api-token>'"))?;
+                self.user(username, "api-token", secret).await?
+            }
+            _ => {
+                return Err(SpaceErr::new(
+                    401,
+                    "expected Substance: Map(username,password) or Token",
+                ))
+            }
+        };
+
+        let remote = match knock.remote {
+            Some(remote) => remote,
+            None => self.remote_point_factory.create().await?.to_surface(),
+        };
+        Ok(HyperwayStub {
+            agent: Agent::Point(user),
+            remote,
+            scope: Scope::Full,
+        })
+    }
+}
+
 pub struct TokenDispensingHyperwayInterchange {
     pub agent: Agent,
     pub logger: Logger,
@@ -936,6 +1113,7 @@ impl TokenDispensingHyperwayInterchange {
         let stub = HyperwayStub {
             agent: self.agent.clone(),
             remote: remote_point,
+            scope: Scope::Full,
         };
         self.tokens.insert(token.clone(), stub.clone());
         Ok((token, stub))
@@ -982,6 +1160,11 @@ pub trait HyperGate: Send + Sync {
         kind: InterchangeKind,
         stub: HyperwayStub,
     ) -> Result<HyperwayEndpoint, SpaceErr>;
+
+    /// enter a client whose [`Agent`] was already established by its transport (a verified
+    /// client certificate or the peer uid of a unix socket) but whose [`Knock`] did not select
+    /// a remote.  The gate's authenticator assigns the remote and the `agent` is kept
+    async fn assign(&self, knock: Knock, agent: Agent) -> Result<HyperwayEndpoint, SpaceErr>;
 }
 
 pub struct HopRouter {
@@ -1103,6 +1286,17 @@ impl HyperGate for HyperGateSelector {
             .jump(kind, stub)
             .await
     }
+
+    async fn assign(&self, knock: Knock, agent: Agent) -> Result<HyperwayEndpoint, SpaceErr> {
+        if let Some(gate) = self.map.get(&knock.kind) {
+            gate.value().assign(knock, agent).await
+        } else {
+            Err(SpaceErr::new(
+                500,
+                format!("interchange not available: {}", knock.kind.to_string()).as_str(),
+            ))
+        }
+    }
 }
 
 pub trait HyperwayConfigurator: Send + Sync {
@@ -1164,6 +1358,7 @@ where
             greet.agent.clone(),
             self.logger.clone(),
         );
+        hyperway.transform_inbound(Box::new(ScopeTransform::new(greet.scope.clone())));
         self.configurator.config(&greet, &mut hyperway);
 
         self.interchange.add(hyperway).await;
@@ -1172,6 +1367,7 @@ where
         let stub = HyperwayStub {
             agent: greet.agent.clone(),
             remote: greet.surface.clone(),
+            scope: greet.scope.clone(),
         };
 
         let mut ext = self.logger.result_ctx(
@@ -1213,6 +1409,13 @@ where
         let greet = self.greeter.greet(stub).await?;
         self.enter(greet).await
     }
+
+    async fn assign(&self, knock: Knock, agent: Agent) -> Result<HyperwayEndpoint, SpaceErr> {
+        let mut stub = self.auth.auth(knock).await?;
+        stub.agent = agent;
+        let greet = self.greeter.greet(stub).await?;
+        self.enter(greet).await
+    }
 }
 
 #[derive(Clone)]
@@ -1242,12 +1445,17 @@ where
     }
 
     async fn enter(&self, greet: Greet) -> Result<HyperwayEndpoint, SpaceErr> {
-        let stub = HyperwayStub::new(greet.surface.clone(), greet.agent.clone());
+        let stub: HyperwayStub = greet.clone().into();
         let ext = self
             .interchange
             .mount(stub.clone(), Some(greet.into()))
             .await?;
-        Ok(ext)
+        // the mounted hyperway was added without knowing this session so its scope is
+        // applied here.  A full scope leaves waves as they were
+        match stub.scope {
+            Scope::Full => Ok(ext),
+            scope => Ok(ext.scoped(scope)),
+        }
     }
 }
 
@@ -1273,6 +1481,14 @@ where
         let ext = self.enter(greet).await?;
         Ok(ext)
     }
+
+    async fn assign(&self, knock: Knock, agent: Agent) -> Result<HyperwayEndpoint, SpaceErr> {
+        let mut stub = self.auth.auth(knock).await?;
+        stub.agent = agent;
+        let greet = self.greeter.greet(stub).await?;
+        let ext = self.enter(greet).await?;
+        Ok(ext)
+    }
 }
 
 pub struct HyperClient {
@@ -2000,7 +2216,7 @@ pub mod test_util {
     use crate::hyperlane::{
         AnonHyperAuthenticator, HyperClient, HyperGate, HyperGateSelector, HyperGreeter, Hyperway,
         HyperwayEndpointFactory, HyperwayInterchange, HyperwayStub, LocalHyperwayGateUnlocker,
-        MountInterchangeGate,
+        MountInterchangeGate, ScopeTransform,
     };
     use starlane_macros::{create_mark, logger, push_loc, push_mark};
     use starlane_space::err::SpaceErr;
@@ -2030,7 +2246,7 @@ pub mod test_util {
     impl SingleInterchangePlatform {
         pub async fn new() -> Self {
             let point = Point::from_str("point").unwrap();
-            let logger = logger!(&point);
+            let logger = push_mark!(Logger::default().push(&point));
             let interchange = Arc::new(HyperwayInterchange::new(point.clone(), push_mark!(logger)));
 
             interchange
@@ -2102,7 +2318,7 @@ pub mod test_util {
                 Logger::default(),
             );
 
-            let logger = logger!(Point::from_str("less-client").unwrap());
+            let logger = push_mark!(Logger::default().push(Point::from_str("less-client").unwrap()));
 
             let less_client = HyperClient::new_with_exchanger(
                 self.less_factory,
@@ -2286,6 +2502,7 @@ pub mod test_util {
                     .to_surface()
                     .with_layer(Layer::Core),
                 transport: stub.remote.clone(),
+                scope: stub.scope.clone(),
             })
         }
     }
@@ -2303,18 +2520,26 @@ pub mod test {
 
     use crate::hyperlane::test_util::{SingleInterchangePlatform, TestGreeter, WaveTest};
     use crate::hyperlane::{
-        AnonHyperAuthenticator, Bridge, HyperClient, HyperConnectionDetails, HyperGate,
+        AnonHyperAuthenticator, Bridge, CredentialHyperAuthenticator, HyperAuthenticator,
+        HyperClient, HyperConnectionDetails, HyperGate,
         HyperGateSelector, HyperRouter, Hyperlane, Hyperway, HyperwayEndpoint,
         HyperwayEndpointFactory, HyperwayInterchange, HyperwayStub, LocalHyperwayGateUnlocker,
-        MountInterchangeGate,
+        MountInterchangeGate, ScopeTransform,
     };
+    use crate::password;
+    use crate::registry::mem::registry::MemoryRegistry;
+    use crate::registry::{Registration, Registry};
     use starlane_macros::{create_mark, logger, push_mark};
+    use starlane_space::command::common::{PropertyMod, SetProperties};
+    use starlane_space::command::direct::create::{PointFactoryU64, Strategy};
     use starlane_space::err::SpaceErr;
-    use starlane_space::hyper::InterchangeKind;
+    use starlane_space::hyper::{InterchangeKind, Knock};
+    use starlane_space::kind::Kind;
+    use starlane_space::particle::Status;
     use starlane_space::loc::{Layer, ToSurface};
     use starlane_space::point::Point;
     use starlane_space::settings::Timeouts;
-    use starlane_space::substance::Substance;
+    use starlane_space::substance::{Substance, SubstanceMap, Token};
     use starlane_space::wave::core::cmd::CmdMethod;
     use starlane_space::wave::core::ext::ExtMethod;
     use starlane_space::wave::core::{Method, ReflectedCore};
@@ -2323,9 +2548,10 @@ pub mod test {
     };
     use starlane_space::wave::exchange::SetStrategy;
     use starlane_space::wave::{
-        Agent, DirectedProto, HyperWave, PongCore, ReflectedKind, ReflectedProto, Wave,
+        Agent, DirectedProto, HyperWave, PongCore, ReflectedKind, ReflectedProto, Scope, Wave,
         WaveVariantDef,
     };
+    use std::collections::HashSet;
     pub static LESS: Lazy<Point> =
         Lazy::new(|| Point::from_str("space:users:less").expect("point"));
     pub static FAE: Lazy<Point> = Lazy::new(|| Point::from_str("space:users:fae").expect("point"));
@@ -2396,6 +2622,155 @@ pub mod test {
         assert_eq!(wave.id(), wave_id);
     }
 
+    #[tokio::test]
+    pub async fn test_scope_transform() {
+        let hyperway = Hyperway::new(
+            LESS.clone().to_surface(),
+            LESS.to_agent(),
+            Default::default(),
+        );
+        let scope = Scope::Grants(HashSet::new());
+        hyperway.transform_inbound(Box::new(ScopeTransform::new(scope.clone())));
+
+        // the remote tries to widen its own scope
+        let mut wave = hello_wave();
+        wave.set_scope(Scope::Full);
+        hyperway.inbound.send(wave).await;
+        let wave = tokio::time::timeout(
+            Duration::from_secs(5u64),
+            hyperway.inbound.rx(None).await.recv(),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(*wave.scope(), scope);
+        assert_eq!(*wave.agent(), LESS.to_agent());
+    }
+
+    #[tokio::test]
+    pub async fn test_mount_gate_scope() {
+        let interchange = Arc::new(HyperwayInterchange::new(
+            Point::from_str("point").unwrap(),
+            Default::default(),
+        ));
+        interchange
+            .add(Hyperway::new(
+                LESS.clone().to_surface(),
+                LESS.to_agent(),
+                Default::default(),
+            ))
+            .await;
+        interchange
+            .add(Hyperway::new(
+                FAE.clone().to_surface(),
+                FAE.to_agent(),
+                Default::default(),
+            ))
+            .await;
+        let gate = MountInterchangeGate::new(
+            AnonHyperAuthenticator::new(),
+            TestGreeter::new(),
+            interchange.clone(),
+            Default::default(),
+        );
+
+        let scope = Scope::Grants(HashSet::new());
+        let stub =
+            HyperwayStub::new(LESS.clone().to_surface(), LESS.to_agent()).with_scope(scope.clone());
+        let less = gate.jump(InterchangeKind::Singleton, stub).await.unwrap();
+        let mut fae = interchange
+            .mount(
+                HyperwayStub::new(FAE.clone().to_surface(), FAE.to_agent()),
+                None,
+            )
+            .await
+            .unwrap();
+
+        // the client tries to widen its own scope
+        let mut wave = hello_wave();
+        wave.set_scope(Scope::Full);
+        less.tx.send(wave).await.unwrap();
+        let wave = tokio::time::timeout(Duration::from_secs(5u64), fae.rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(*wave.scope(), scope);
+    }
+
+    #[tokio::test]
+    pub async fn test_credential_authenticator() {
+        let userbase = Point::from_str("space:users").unwrap();
+        let registry: Registry = Arc::new(MemoryRegistry::new());
+        let register = |point: Point, kind: Kind, properties: SetProperties| Registration {
+            point,
+            kind,
+            registry: Default::default(),
+            properties,
+            owner: Point::root(),
+            strategy: Strategy::Commit,
+            status: Status::Ready,
+        };
+        let mut properties = SetProperties::new();
+        for (key, secret) in [("password", "correct horse"), ("api-token", "battery staple")] {
+            properties.push(PropertyMod::Set {
+                key: key.to_string(),
+                value: password::hash(secret),
+                lock: false,
+            });
+        }
+        registry
+            .register(&register(LESS.clone(), Kind::User, properties.clone()))
+            .await
+            .unwrap();
+        registry
+            .register(&register(userbase.push("app").unwrap(), Kind::App, properties))
+            .await
+            .unwrap();
+
+        let auth = CredentialHyperAuthenticator::new(
+            userbase.clone(),
+            registry,
+            Arc::new(PointFactoryU64::new(
+                Point::from_str("space:remotes").unwrap(),
+                "remote-".to_string(),
+            )),
+            Default::default(),
+        );
+        let login = |username: &str, password: &str| {
+            let mut map = SubstanceMap::new();
+            map.map
+                .insert("username".to_string(), Substance::Text(username.to_string()));
+            map.map
+                .insert("password".to_string(), Substance::Text(password.to_string()));
+            Knock {
+                auth: Box::new(Substance::Map(map)),
+                ..Default::default()
+            }
+        };
+        let token = |token: &str| Knock {
+            auth: Box::new(Substance::Token(Token::new(token))),
+            ..Default::default()
+        };
+
+        let stub = auth.auth(login("less", "correct horse")).await.unwrap();
+        assert_eq!(stub.agent, Agent::Point(LESS.clone()));
+        assert_eq!(stub.remote.point.parent().unwrap().to_string(), "space:remotes");
+
+        let stub = auth.auth(token("less:battery staple")).await.unwrap();
+        assert_eq!(stub.agent, Agent::Point(LESS.clone()));
+
+        // each secret only works for its own property
+        let err = auth.auth(login("less", "battery staple")).await.err().unwrap();
+        assert!(matches!(err, SpaceErr::Status { status: 401, .. }));
+        assert!(auth.auth(token("less:correct horse")).await.is_err());
+
+        // an unknown user and a particle that is not a User are refused the same way
+        let unknown = auth.auth(login("fae", "correct horse")).await.err().unwrap();
+        let not_user = auth.auth(login("app", "correct horse")).await.err().unwrap();
+        assert_eq!(unknown.to_string(), err.to_string());
+        assert_eq!(not_user.to_string(), err.to_string());
+    }
+
     /*
     #[tokio::mem]
     pub async fn test_hyperway_ext() {
@@ -2685,6 +3060,7 @@ pub mod test {
                 HyperwayStub {
                     remote: FAE.to_surface().with_layer(Layer::Core),
                     agent: Agent::HyperUser,
+                    scope: Scope::Full,
                 },
                 None,
             )
@@ -2702,6 +3078,7 @@ pub mod test {
                 HyperwayStub {
                     remote: LESS.to_surface().with_layer(Layer::Core),
                     agent: Agent::HyperUser,
+                    scope: Scope::Full,
                 },
                 None,
             )
@@ -2712,6 +3089,7 @@ pub mod test {
                 HyperwayStub {
                     remote: FAE.to_surface().with_layer(Layer::Core),
                     agent: Agent::HyperUser,
+                    scope: Scope::Full,
                 },
                 None,
             )
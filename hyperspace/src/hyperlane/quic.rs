@@ -1,312 +1,463 @@
-use std::io;
-use std::net::SocketAddr;
+use crate::hyperlane::codec::{FrameConfig, FrameProtocol};
+use crate::hyperlane::tcp::{
+    client_tls_config, enter, ClientCertAuth, Error, Frame, HyperlaneTcpServerConfig,
+};
+use crate::hyperlane::{
+    HyperConnectionDetails, HyperConnectionStatus, HyperGateSelector, HyperwayEndpoint,
+    HyperwayEndpointFactory,
+};
+use async_trait::async_trait;
+use quinn::crypto::rustls::{QuicClientConfig, QuicServerConfig};
+use quinn::{Connection, Endpoint, Incoming, RecvStream, SendStream, VarInt};
+use rustls::pki_types::CertificateDer;
+use starlane_space::err::SpaceErr;
+use starlane_space::hyper::Knock;
+use starlane_space::log::Logger;
+use starlane_space::wave::{PingCore, Wave, WaveVariantDef};
+use starlane_space::VERSION;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::sync::Arc;
-use std::task::Poll;
 use std::time::Duration;
-
-fn generate_self_signed_cert() -> Result<(rustls::Certificate, rustls::PrivateKey), SpaceErr> {
-    let cert = rcgen::generate_simple_self_signed(vec!["cosmic-hyperlane".to_string()])?;
-    let key = rustls::PrivateKey(cert.serialize_private_key_der());
-    Ok((rustls::Certificate(cert.serialize_der()?), key))
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+
+/// a quic server listens on the same address, with the same certificates and client
+/// authentication as its tcp counterpart
+pub type HyperlaneQuicServerConfig = HyperlaneTcpServerConfig;
+
+pub struct HyperlaneQuicClient {
+    host: String,
+    cert_dir: String,
+    knock: Knock,
+    logger: Logger,
+    verify: bool,
+    client_cert: Option<String>,
 }
 
-fn configure_client() -> ClientConfig {
-    let crypto = rustls::ClientConfig::builder()
-        .with_safe_defaults()
-        .with_custom_certificate_verifier(SkipServerVerification::new())
-        .with_no_client_auth();
+impl HyperlaneQuicClient {
+    pub fn new<H, S>(host: H, cert_dir: S, knock: Knock, verify: bool, logger: Logger) -> Self
+    where
+        S: ToString,
+        H: ToString,
+    {
+        Self {
+            host: host.to_string(),
+            cert_dir: cert_dir.to_string(),
+            knock,
+            verify,
+            logger,
+            client_cert: None,
+        }
+    }
 
-    ClientConfig::new(Arc::new(crypto))
+    /// present the `cert.der` and `key.der` found in `dir` to servers that require
+    /// client certificates
+    pub fn with_client_cert<S: ToString>(mut self, dir: S) -> Self {
+        self.client_cert = Some(dir.to_string());
+        self
+    }
 }
 
-// Implementation of `ServerCertVerifier` that verifies everything as trustworthy.
-struct SkipServerVerification;
+#[async_trait]
+impl HyperwayEndpointFactory for HyperlaneQuicClient {
+    async fn create(
+        &self,
+        status_tx: mpsc::Sender<HyperConnectionDetails>,
+    ) -> Result<HyperwayEndpoint, SpaceErr> {
+        let tls = client_tls_config(&self.cert_dir, self.verify, &self.client_cert)?;
+        let crypto = QuicClientConfig::try_from(tls).map_err(SpaceErr::map)?;
+
+        let addr = tokio::net::lookup_host(self.host.clone())
+            .await?
+            .next()
+            .ok_or(SpaceErr::str(format!("could not resolve '{}'", self.host)))?;
+        let bind = match addr {
+            SocketAddr::V4(_) => SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 0),
+            SocketAddr::V6(_) => "[::]:0".parse().map_err(SpaceErr::map)?,
+        };
+        let mut endpoint = Endpoint::client(bind)?;
+        endpoint.set_default_client_config(quinn::ClientConfig::new(Arc::new(crypto)));
+
+        let host = self.host.split(":").next().unwrap().to_string();
+        let connection = endpoint
+            .connect(addr, host.as_str())
+            .map_err(SpaceErr::map)?
+            .await
+            .map_err(SpaceErr::map)?;
+
+        let (mut send, mut recv) = connection.open_bi().await.map_err(SpaceErr::map)?;
+        let protocol = handshake(
+            &mut send,
+            &mut recv,
+            &FrameConfig::default(),
+            status_tx.clone(),
+            self.logger.clone(),
+        )
+        .await?;
+
+        let endpoint = QuicMuxer::new(connection, protocol, self.logger.clone());
 
-impl SkipServerVerification {
-    fn new() -> Arc<Self> {
-        Arc::new(Self)
+        let wave: WaveVariantDef<PingCore> = self.knock.clone().into();
+        let wave = wave.to_wave();
+        endpoint.tx.send(wave).await?;
+
+        Ok(endpoint)
     }
 }
 
-impl rustls::client::ServerCertVerifier for SkipServerVerification {
-    fn verify_server_cert(
-        &self,
-        _end_entity: &rustls::Certificate,
-        _intermediates: &[rustls::Certificate],
-        _server_name: &rustls::ServerName,
-        _scts: &mut dyn Iterator<Item=&[u8]>,
-        _ocsp_response: &[u8],
-        _now: std::time::SystemTime,
-    ) -> Result<rustls::client::ServerCertVerified, rustls::Error> {
-        Ok(rustls::client::ServerCertVerified::assertion())
+/// the version handshake is performed on the first bidirectional stream of a connection
+/// exactly as [`crate::hyperlane::tcp::FrameMuxer::handshake`] performs it over tcp
+async fn handshake(
+    send: &mut SendStream,
+    recv: &mut RecvStream,
+    config: &FrameConfig,
+    status_tx: mpsc::Sender<HyperConnectionDetails>,
+    logger: Logger,
+) -> Result<FrameProtocol, SpaceErr> {
+    Frame::from_version(&VERSION.clone())
+        .to_stream(send)
+        .await?;
+    let in_version = tokio::time::timeout(
+        Duration::from_secs(30),
+        Frame::from_stream(recv, config.max_frame_size),
+    )
+    .await??
+    .to_version()?;
+
+    if in_version == *VERSION {
+        Frame::from_string("Ok".to_string()).to_stream(send).await?;
+    } else {
+        logger.warn("version mismatch");
+        status_tx
+            .send(HyperConnectionDetails::new(
+                HyperConnectionStatus::Handshake,
+                "version mismatch",
+            ))
+            .await?;
+        let msg = format!(
+            "Err(\"expected version {}. encountered version {}\")",
+            VERSION.to_string(),
+            in_version.to_string()
+        );
+        Frame::from_string(msg.clone()).to_stream(send).await?;
+        return Err(msg.into());
     }
-}
 
-pub enum HyperServerCall {}
+    let result = tokio::time::timeout(
+        Duration::from_secs(30),
+        Frame::from_stream(recv, config.max_frame_size),
+    )
+    .await??
+    .to_string()?;
+    if "Ok".to_string() != result {
+        return logger.result(Err(format!(
+            "remote did not indicate Ok. expected: 'Ok' encountered '{}'",
+            result
+        )
+        .into()));
+    }
 
-pub struct HyperServerQuic {
-    pub endpoint: Endpoint,
+    Frame::from_string(config.offer()).to_stream(send).await?;
+    let offer = tokio::time::timeout(
+        Duration::from_secs(30),
+        Frame::from_stream(recv, config.max_frame_size),
+    )
+    .await??
+    .to_string()?;
+    let protocol = logger.result(config.negotiate(offer.as_str()))?;
+
+    send.finish().map_err(SpaceErr::map)?;
+    Ok(protocol)
 }
 
-impl HyperServerQuic {
-    pub async fn new(addr: SocketAddr, gate: Arc<VersionGate>) -> Result<Self, QuicErr> {
-        let (cert, key) = generate_self_signed_cert()?;
-        let server_config = ServerConfig::with_single_cert(vec![cert], key)?;
-        let (endpoint, mut incoming) = Endpoint::server(server_config, addr)?;
+/// every wave travels on its own unidirectional stream so a large wave never holds up
+/// the waves queued behind it
+pub struct QuicMuxer {
+    connection: Connection,
+    protocol: Arc<FrameProtocol>,
+    tx: mpsc::Sender<Wave>,
+    rx: mpsc::Receiver<Wave>,
+    terminate_rx: mpsc::Receiver<()>,
+    logger: Logger,
+}
 
-        tokio::spawn(async move {
-            while let Poll::Ready(Some(conn)) = incoming.poll_next().await {
-                let gate = gate.clone();
-                tokio::spawn(async move {
-                    async fn connect(
-                        conn: Connecting,
-                        gate: Arc<VersionGate>,
-                    ) -> Result<
-                        (
-                            NewConnection,
-                            mpsc::Sender<UltraWave>,
-                            mpsc::Receiver<UltraWave>,
-                        ),
-                        ConErr,
-                    > {
-                        let mut connection: NewConnection = conn.await?;
-                        let recv = tokio::time::timeout(
-                            Duration::from_secs(30),
-                            connection.uni_streams.next(),
-                        )
-                            .await?
-                            .ok_or(SpaceErr::server_error())??;
-                        let version = recv.read_to_end(2 * 1024).await?;
-                        let version = PrimitiveFrame::from(version);
-                        let version = version.try_into()?;
-                        let entry_router = match gate.unlock(version).await {
-                            Ok(entry_router) => {
-                                let mut send = connection.connection.open_uni().await?;
-                                let ok = PrimitiveFrame::from("Ok".to_string());
-                                send.write_all(ok.data.as_bytes()).await?;
-                                send.finish().await?;
-                                entry_router
-                            }
-                            Err(err) => {
-                                let mut send = connection.connection.open_uni().await?;
-                                let frame = PrimitiveFrame::from(err);
-                                send.write_all(frame.data.as_bytes()).await?;
-                                send.finish().await?;
-                                /// send an error and disconnect
-                                return Err(ConErr::new());
-                            }
-                        };
-
-                        let recv = tokio::time::timeout(
-                            Duration::from_secs(30),
-                            connection.uni_streams.next(),
-                        )
-                            .await?
-                            .ok_or(SpaceErr::server_error())??;
-                        let req = recv.read_to_end(32 * 1024).await?;
-                        let req = PrimitiveFrame::from(req);
-                        let req = req.try_into()?;
-                        let stub = req.as_stub();
-                        match entry_router.knock(req).await {
-                            Ok((tx, rx)) => {
-                                let mut send = connection.connection.open_uni().await?;
-                                let resp = stub.ok();
-                                let frame = PrimitiveFrame::from(resp);
-                                send.write_all(frame.data.as_bytes()).await?;
-                                send.finish().await?;
-                                Ok((connection, tx, rx))
-                            }
-                            Err(err) => {
-                                let mut send = connection.connection.open_uni().await?;
-                                let frame = PrimitiveFrame::from(err);
-                                send.write_all(frame.data.as_bytes()).await?;
-                                send.finish().await?;
-                                /// send a response and disconnect
-                                Err(ConErr::new())
-                            }
-                        }
-                    }
+impl QuicMuxer {
+    pub fn new(
+        connection: Connection,
+        protocol: FrameProtocol,
+        logger: Logger,
+    ) -> HyperwayEndpoint {
+        let (in_tx, in_rx) = mpsc::channel(1024);
+        let (out_tx, out_rx) = mpsc::channel(1024);
+        let (terminate_tx, terminate_rx) = mpsc::channel(1);
+        let muxer = Self {
+            connection,
+            protocol: Arc::new(protocol),
+            tx: in_tx,
+            rx: out_rx,
+            terminate_rx,
+            logger: logger.clone(),
+        };
+        {
+            let logger = logger.clone();
+            tokio::spawn(async move {
+                logger.result(muxer.mux().await).unwrap_or_default();
+            });
+        }
 
-                    match connect(conn, gate).await {
-                        Ok((connection, tx, mut rx)) => {
-                            let uni_streams = connection.uni_streams;
-                            let connection = connection.connection;
-                            tokio::spawn(async move {
-                                while let Poll::Ready(Some(recv)) = uni_streams.next().await {
-                                    let wave = recv.read_to_end(32 * 1024).await?;
-                                    let wave = PrimitiveFrame::from(wave);
-                                    let wave = wave.try_into()?;
-                                    tx.send(wave).await;
-                                }
-                            });
+        let (oneshot_terminate_tx, oneshot_terminate_rx) = oneshot::channel();
+        tokio::spawn(async move {
+            oneshot_terminate_rx.await.unwrap_or_default();
+            terminate_tx.send(()).await.unwrap_or_default();
+        });
+        HyperwayEndpoint::new_with_drop(out_tx, in_rx, oneshot_terminate_tx, logger)
+    }
 
+    /// outbound streams are opened in the order the waves are sent and the peer accepts
+    /// streams in the order they were opened, so waves are delivered in order even though
+    /// their streams are written and read concurrently
+    pub async fn mux(mut self) -> Result<(), SpaceErr> {
+        let (ordered_tx, mut ordered_rx) = mpsc::channel::<JoinHandle<Option<Wave>>>(1024);
+        {
+            let tx = self.tx.clone();
+            tokio::spawn(async move {
+                while let Some(read) = ordered_rx.recv().await {
+                    if let Ok(Some(wave)) = read.await {
+                        tx.send(wave).await.unwrap_or_default();
+                    }
+                }
+            });
+        }
+        loop {
+            tokio::select! {
+                wave = self.rx.recv() => {
+                    match wave {
+                        None => {
+                            self.connection.close(VarInt::from_u32(0), b"endpoint dropped");
+                            return Ok(());
+                        }
+                        Some(wave) => {
+                            let send = self.connection.open_uni().await.map_err(SpaceErr::map)?;
+                            let protocol = self.protocol.clone();
+                            let logger = self.logger.clone();
                             tokio::spawn(async move {
-                                while let Some(wave) = rx.recv().await {
-                                    let mut send = connection.open_uni().await?;
-                                    let wave = PrimitiveFrame::from(wave);
-                                    send.write_all(wave.data.as_bytes()).await?;
-                                    send.finish().await;
-                                }
+                                logger.result(write_wave(send, &protocol, wave).await).unwrap_or_default();
                             });
                         }
-                        Err(_) => {
-                            // nothing to do here.
-                        }
                     }
-                });
-                // Save connection somewhere, start transferring, receiving data, see DataTransfer tutorial.
+                }
+                recv = self.connection.accept_uni() => {
+                    let recv = recv.map_err(SpaceErr::map)?;
+                    let protocol = self.protocol.clone();
+                    let logger = self.logger.clone();
+                    let read = tokio::spawn(async move {
+                        logger.result(read_wave(recv, &protocol).await).ok()
+                    });
+                    ordered_tx.send(read).await.unwrap_or_default();
+                }
+                _ = self.terminate_rx.recv() => {
+                    self.logger.warn(format!("terminated"));
+                    self.connection.close(VarInt::from_u32(0), b"terminated");
+                    return Ok(());
+                }
             }
-        });
-
-        Ok(Self { endpoint })
-    }
-
-    pub fn close(self) {
-        self.endpoint.close(
-            VarInt::from_u64(0u64).unwrap(),
-            "no reason given".as_bytes(),
-        )
+        }
     }
 }
 
-pub struct QuicErr {
-    pub message: String,
-}
-
-impl QuicErr {
-    pub fn new<S: ToString>(m: S) -> Self {
-        Self {
-            message: m.to_string(),
-        }
-    }
+async fn write_wave(
+    mut send: SendStream,
+    protocol: &FrameProtocol,
+    wave: Wave,
+) -> Result<(), SpaceErr> {
+    let frame = Frame {
+        data: protocol.encode(&wave)?,
+    };
+    send.write_all(frame.data.as_slice())
+        .await
+        .map_err(SpaceErr::map)?;
+    send.finish().map_err(SpaceErr::map)?;
+    Ok(())
 }
 
-impl ToString for QuicErr {
-    fn to_string(&self) -> String {
-        self.message.clone()
-    }
+async fn read_wave(mut recv: RecvStream, protocol: &FrameProtocol) -> Result<Wave, SpaceErr> {
+    let data = recv
+        .read_to_end(protocol.max_frame_size)
+        .await
+        .map_err(SpaceErr::map)?;
+    protocol.decode(data.as_slice())
 }
 
-impl From<rustls::Error> for QuicErr {
-    fn from(err: Error) -> Self {
-        QuicErr::new(err.to_string())
-    }
+pub struct HyperlaneQuicServerApi {
+    endpoint: Endpoint,
 }
 
-impl From<io::Error> for QuicErr {
-    fn from(err: io::Error) -> Self {
-        QuicErr::new(err.to_string())
+impl HyperlaneQuicServerApi {
+    /// the address the server is bound to, useful when it was configured with port `0`
+    pub fn local_addr(&self) -> Result<SocketAddr, Error> {
+        Ok(self.endpoint.local_addr()?)
     }
-}
 
-impl From<SpaceErr> for QuicErr {
-    fn from(err: SpaceErr) -> Self {
-        QuicErr::new(err.to_string())
+    pub fn close(&self) {
+        self.endpoint
+            .close(VarInt::from_u32(0), "server closed".as_bytes())
     }
 }
 
-pub struct HyperClientQuic {
+pub struct HyperlaneQuicServer {
+    gate: Arc<HyperGateSelector>,
     endpoint: Endpoint,
-    connection: Connection,
+    logger: Logger,
+    client_auth: Option<Arc<ClientCertAuth>>,
 }
 
-impl HyperClientQuic {
+impl HyperlaneQuicServer {
     pub async fn new(
-        endpoint: Endpoint,
-        server_addr: SocketAddr,
-        knock: Knock,
-        deliver_tx: mpsc::Sender<UltraWave>,
-        logger: PointLogger,
-    ) -> Result<Self, SpaceErr> {
-        // Connect to the blah passing in the blah name which is supposed to be in the blah certificate.
-        let new_connection = endpoint.connect(server_addr, "cosmic-hyperlane")?.await?;
-
-        let mut send = new_connection.connection.open_uni().await?;
-        let version = PrimitiveFrame::from(VERSION.clone());
-        send.write_all(version.data.as_bytes()).await?;
-        send.finish().await?;
-
-        let recv = tokio::time::timeout(Duration::from_secs(30), new_connection.uni_streams.next())
-            .await?
-            .ok_or(SpaceErr::server_error())??;
-        recv.read_to_end(1024).await?;
-        // let's hope it said 'Ok' ...
-
-        let req = knock.into();
-        let req = req.try_into()?;
-
-        let mut send = new_connection.connection.open_uni().await?;
-        send.write_all(req.data.as_bytes()).await?;
-        send.finish().await?;
+        config: HyperlaneQuicServerConfig,
+        gate: Arc<HyperGateSelector>,
+        logger: Logger,
+    ) -> Result<Self, Error> {
+        let tls = config.tls_config()?;
+        let crypto = QuicServerConfig::try_from(tls).map_err(Error::new)?;
+        let server_config = quinn::ServerConfig::with_crypto(Arc::new(crypto));
+
+        let addr = config.addr();
+        let endpoint = Endpoint::server(server_config, addr.clone())
+            .map_err(|e| Error::new(format!("could not bind to '{}': {}", addr, e)))?;
+
+        Ok(Self {
+            gate,
+            endpoint,
+            logger,
+            client_auth: config.client_auth.map(Arc::new),
+        })
+    }
 
-        let recv = tokio::time::timeout(Duration::from_secs(30), new_connection.uni_streams.next())
-            .await?
-            .ok_or(SpaceErr::server_error())??;
-        let resp = recv.read_to_end(1024).await?;
-        let resp: Pong = resp.try_into()?;
+    pub fn start(self) -> Result<HyperlaneQuicServerApi, Error> {
+        let api = HyperlaneQuicServerApi {
+            endpoint: self.endpoint.clone(),
+        };
+        tokio::spawn(async move {
+            self.run().await;
+        });
+        Ok(api)
+    }
 
-        if !resp.core.is_ok() {
-            Err(SpaceErr::from_status(resp.core.status.as_u16()))
-        } else {
-            let connection = new_connection.connection;
-            let uni_streams = new_connection.uni_streams;
+    async fn run(self) {
+        while let Some(incoming) = self.endpoint.accept().await {
+            let gate = self.gate.clone();
+            let logger = self.logger.clone();
+            let client_auth = self.client_auth.clone();
 
             tokio::spawn(async move {
-                while let Some(Ok(recv)) = uni_streams.next().await {
-                    async fn process(
-                        recv: RecvStream,
-                        delivery_tx: mpsc::Sender<UltraWave>,
-                    ) -> Result<(), SpaceErr> {
-                        let wave = recv.read_to_end(32 * 1024).await?;
-                        let wave = PrimitiveFrame::from(wave);
-                        let wave = wave.try_into()?;
-                        delivery_tx.send(wave).await;
-                    }
-                    if let Err(err) = process(recv, deliver_tx.clone()).await {
-                        logger.error(err);
-                    }
+                async fn serve(
+                    incoming: Incoming,
+                    gate: Arc<HyperGateSelector>,
+                    client_auth: Option<Arc<ClientCertAuth>>,
+                    logger: Logger,
+                ) -> Result<(), Error> {
+                    let connection = incoming.await.map_err(SpaceErr::map)?;
+
+                    // quinn has already verified the certificate chain
+                    let certs = connection
+                        .peer_identity()
+                        .and_then(|identity| identity.downcast::<Vec<CertificateDer>>().ok());
+                    let agent = match (&client_auth, certs.as_deref().map(Vec::as_slice)) {
+                        (Some(client_auth), Some([cert, ..])) => client_auth.agent_for(cert)?,
+                        _ => None,
+                    };
+
+                    let (mut send, mut recv) =
+                        tokio::time::timeout(Duration::from_secs(30), connection.accept_bi())
+                            .await?
+                            .map_err(SpaceErr::map)?;
+
+                    let (status_tx, mut status_rx): (
+                        mpsc::Sender<HyperConnectionDetails>,
+                        mpsc::Receiver<HyperConnectionDetails>,
+                    ) = mpsc::channel(1024);
+                    tokio::spawn(async move { while let Some(_) = status_rx.recv().await {} });
+
+                    let protocol = handshake(
+                        &mut send,
+                        &mut recv,
+                        &FrameConfig::default(),
+                        status_tx,
+                        logger.clone(),
+                    )
+                    .await?;
+                    let mux = QuicMuxer::new(connection, protocol, logger.clone());
+
+                    enter(mux, &gate, agent, &logger).await
                 }
+                logger
+                    .result(serve(incoming, gate, client_auth, logger.clone()).await)
+                    .unwrap_or_default();
             });
-
-            Ok(Self {
-                endpoint,
-                connection,
-            })
         }
     }
-
-    pub async fn send(&self, wave: UltraWave) -> Result<(), SpaceErr> {
-        let wave: PrimitiveFrame = wave.try_into()?;
-        let mut send = self.connection.open_uni().await?;
-        send.write_all(wave.data.as_bytes()).await?;
-        send.finish().await?;
-        Ok(())
-    }
-}
-
-pub struct ConErr {}
-
-impl ConErr {
-    pub fn new() -> Self {
-        Self {}
-    }
 }
 
 #[cfg(test)]
 mod tests {
-    use dashmap::DashMap;
-
-    use crate::hyperlane::{HyperGateSelector, VersionGate};
-    use starlane_space::err::SpaceErr;
-
-    use crate::HyperServerQuic;
+    use crate::hyperlane::quic::{
+        HyperlaneQuicClient, HyperlaneQuicServer, HyperlaneQuicServerConfig,
+    };
+    use crate::hyperlane::tcp::{CertGenerator, Error};
+    use crate::hyperlane::test_util::{LargeFrameTest, SingleInterchangePlatform, FAE, LESS};
+    use crate::test_util::temp_dir;
+    use starlane_macros::push_loc;
+    use starlane_space::loc::ToSurface;
+    use starlane_space::log::Logger;
+    use starlane_space::point::Point;
+    use std::str::FromStr;
 
     #[tokio::test]
-    pub async fn test() -> Result<(), SpaceErr> {
-        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 4343);
-        let gate = Arc::new(VersionGate::new(HyperGateSelector::new(Arc::new(
-            DashMap::new(),
-        ))));
-        let server = HyperServerQuic::new(addr, gate).await?;
+    async fn test_large_frame() -> Result<(), Error> {
+        // another test may have installed it already
+        rustls::crypto::aws_lc_rs::default_provider()
+            .install_default()
+            .unwrap_or_default();
+        let platform = SingleInterchangePlatform::new().await;
+
+        let certs = temp_dir("hyperlane-quic");
+        let dir = certs.path().display().to_string();
+        CertGenerator::gen(vec!["localhost".to_string()])?
+            .write_to_dir(dir.clone())
+            .await?;
+        let logger = Logger::default().push(Point::from_str("quic-blah").unwrap());
+        let server = HyperlaneQuicServer::new(
+            HyperlaneQuicServerConfig::new(0, dir.clone()),
+            platform.gate.clone(),
+            logger.clone(),
+        )
+        .await?;
+        let api = server.start()?;
+        let port = api.local_addr()?.port();
+
+        let less_logger = push_loc!((logger, &*LESS));
+        let less_client = Box::new(HyperlaneQuicClient::new(
+            format!("localhost:{}", port),
+            dir.clone(),
+            platform.knock(LESS.to_surface()),
+            true,
+            less_logger,
+        ));
+
+        let fae_logger = push_loc!((logger, &*FAE));
+        let fae_client = Box::new(HyperlaneQuicClient::new(
+            format!("localhost:{}", port),
+            dir,
+            platform.knock(FAE.to_surface()),
+            true,
+            fae_logger,
+        ));
+
+        let test = LargeFrameTest::new(fae_client, less_client);
+
+        test.go().await.unwrap();
+        api.close();
+
+        Ok(())
     }
 }
@@ -1,25 +1,33 @@
+use crate::hyperlane::codec::{FrameCodec, FrameConfig, FrameProtocol};
 use crate::hyperlane::{
     HyperConnectionDetails, HyperConnectionStatus, HyperGate, HyperGateSelector, HyperwayEndpoint,
     HyperwayEndpointFactory,
 };
 use async_trait::async_trait;
 use rcgen::{generate_simple_self_signed, RcgenError};
-use rustls::pki_types::ServerName;
-use rustls::{RootCertStore, ServerConfig};
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::crypto::CryptoProvider;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName, UnixTime};
+use rustls::server::WebPkiClientVerifier;
+use rustls::{DigitallySignedStruct, RootCertStore, ServerConfig, SignatureScheme};
+use serde::{Deserialize, Serialize};
 use starlane_space::err::SpaceErr;
 use starlane_space::hyper::Knock;
 use starlane_space::log::Logger;
 use starlane_space::substance::Substance;
+use starlane_space::wave::Agent;
 use starlane_space::wave::{PingCore, Wave, WaveVariantDef};
 use starlane_space::VERSION;
+use std::collections::HashMap;
 use std::io;
 use std::io::{BufReader, Read};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::str::FromStr;
 use std::string::FromUtf8Error;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::fs::File;
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 use tokio::net::{TcpListener, TcpStream};
 use tokio::sync::{broadcast, mpsc, oneshot};
 use tokio::time::error::Elapsed;
@@ -32,6 +40,7 @@ pub struct HyperlaneTcpClient {
     knock: Knock,
     logger: Logger,
     verify: bool,
+    client_cert: Option<String>,
 }
 
 impl HyperlaneTcpClient {
@@ -46,6 +55,48 @@ impl HyperlaneTcpClient {
             knock,
             verify,
             logger,
+            client_cert: None,
+        }
+    }
+
+    /// present the `cert.der` and `key.der` found in `dir` to servers that require
+    /// client certificates
+    pub fn with_client_cert<S: ToString>(mut self, dir: S) -> Self {
+        self.client_cert = Some(dir.to_string());
+        self
+    }
+
+    pub(crate) fn tls_config(&self) -> Result<rustls::ClientConfig, SpaceErr> {
+        client_tls_config(&self.cert_dir, self.verify, &self.client_cert)
+    }
+}
+
+/// the rustls client config shared by the tcp and quic hyperlane clients
+pub(crate) fn client_tls_config(
+    cert_dir: &String,
+    verify: bool,
+    client_cert: &Option<String>,
+) -> Result<rustls::ClientConfig, SpaceErr> {
+    let builder = if verify {
+        let mut root = RootCertStore::empty();
+        for cert in read_certs(format!("{}/cert.der", cert_dir))? {
+            root.add(cert).map_err(SpaceErr::map)?;
+        }
+        rustls::ClientConfig::builder().with_root_certificates(root)
+    } else {
+        rustls::ClientConfig::builder()
+            .dangerous()
+            .with_custom_certificate_verifier(SkipServerVerification::new())
+    };
+
+    match client_cert {
+        None => Ok(builder.with_no_client_auth()),
+        Some(dir) => {
+            let certs = read_certs(format!("{}/cert.der", dir))?;
+            let key = read_private_key(format!("{}/key.der", dir))?;
+            builder
+                .with_client_auth_cert(certs, key)
+                .map_err(SpaceErr::map)
         }
     }
 }
@@ -56,53 +107,16 @@ impl HyperwayEndpointFactory for HyperlaneTcpClient {
         &self,
         status_tx: mpsc::Sender<HyperConnectionDetails>,
     ) -> Result<HyperwayEndpoint, SpaceErr> {
-        let mut root_certs = RootCertStore::empty();
-
-        let ca_file = format!("{}/cert.der", self.cert_dir);
-        //        let key_file = format!("{}/key.der", self.cert_dir);
-
-        let certs = rustls_pemfile::certs(&mut BufReader::new(
-            &mut std::fs::File::open(ca_file).expect("cert file"),
-        ))
-        .collect::<Result<Vec<_>, _>>()?;
-        /*        let private_key =
-                   rustls_pemfile::private_key(&mut BufReader::new(&mut File::open(key_file)?))?
-                       .unwrap();
-
-        */
-
-        let mut root = RootCertStore::empty();
-        for c in certs {
-            root.add(c).expect("failed to add cert to root");
-        }
+        let client_config = self.tls_config()?;
 
-        let mut client_config = Arc::new(
-            rustls::ClientConfig::builder()
-                .with_root_certificates(root)
-                .with_no_client_auth(),
-        );
-
-        /*
-            ClientConfig::builder()
-                .with_root_certificates(root_certs)
-            with_safe_default_protocol_versions()
-                .with_safe_default_cipher_suites()
-                .with_safe_default_kx_groups()
-                .with_safe_default_protocol_versions()
-                .unwrap()
-                .with_no_client_auth(),
-
-        );
-             */
-
-        let mut connector: TlsConnector = TlsConnector::from(client_config);
+        let connector = TlsConnector::from(Arc::new(client_config));
         let stream = tokio::net::TcpStream::connect(self.host.clone()).await?;
 
         let host = self.host.split(":").next().unwrap().to_string();
-        let server_name = ServerName::try_from(host.clone()).unwrap();
+        let server_name = ServerName::try_from(host.clone()).map_err(SpaceErr::map)?;
         let tokio_tls_connector = connector.connect(server_name, stream).await?;
 
-        let mut stream = FrameStream::new(tokio_tls_connector.into());
+        let mut stream = FrameStream::new(tokio_tls_connector);
 
         let endpoint =
             FrameMuxer::handshake(stream, status_tx.clone(), self.logger.clone()).await?;
@@ -115,6 +129,79 @@ impl HyperwayEndpointFactory for HyperlaneTcpClient {
     }
 }
 
+fn read_certs(path: String) -> Result<Vec<CertificateDer<'static>>, io::Error> {
+    let mut file = BufReader::new(std::fs::File::open(path)?);
+    rustls_pemfile::certs(&mut file).collect()
+}
+
+fn read_private_key(path: String) -> Result<PrivateKeyDer<'static>, io::Error> {
+    let mut file = BufReader::new(std::fs::File::open(path)?);
+    rustls_pemfile::private_key(&mut file)?
+        .ok_or(io::Error::new(io::ErrorKind::InvalidData, "no private key"))
+}
+
+/// accepts any server certificate. Used when a [`HyperlaneTcpClient`] is created with
+/// `verify == false`, the handshake signatures are still checked
+#[derive(Debug)]
+struct SkipServerVerification {
+    provider: Arc<CryptoProvider>,
+}
+
+impl SkipServerVerification {
+    fn new() -> Arc<Self> {
+        Arc::new(Self {
+            provider: Arc::new(rustls::crypto::aws_lc_rs::default_provider()),
+        })
+    }
+}
+
+impl ServerCertVerifier for SkipServerVerification {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.provider
+            .signature_verification_algorithms
+            .supported_schemes()
+    }
+}
+
 pub struct CertGenerator {
     certs: Vec<u8>,
     key: Vec<u8>,
@@ -154,8 +241,11 @@ impl CertGenerator {
     pub async fn write_to_dir(&self, dir: String) -> io::Result<()> {
         let mut certs = File::create(format!("{}/cert.der", dir)).await?;
         certs.write_all(&self.certs()).await?;
+        certs.flush().await?;
         let mut key = File::create(format!("{}/key.der", dir)).await?;
         key.write_all(&self.private_key()).await?;
+        // a tokio File finishes writing in the background unless flushed
+        key.flush().await?;
         Ok(())
     }
 }
@@ -188,18 +278,32 @@ impl Frame {
         )?)
     }
 
-    pub async fn from_stream<'a>(read: &'a mut TlsStream<TcpStream>) -> Result<Frame, SpaceErr> {
+    /// read one frame. A frame larger than `max_size` is refused before it is read, the
+    /// caller is expected to drop the connection
+    pub async fn from_stream<R>(read: &mut R, max_size: usize) -> Result<Frame, SpaceErr>
+    where
+        R: AsyncRead + Unpin,
+    {
         let size = read.read_u32().await? as usize;
-        let mut data = Vec::with_capacity(size as usize);
-
-        while data.len() < size {
-            read.read_buf(&mut data).await?;
+        if size > max_size {
+            return Err(SpaceErr::new(
+                413,
+                format!(
+                    "frame of {} bytes exceeds the limit of {} bytes",
+                    size, max_size
+                ),
+            ));
         }
+        let mut data = vec![0u8; size];
+        read.read_exact(data.as_mut_slice()).await?;
 
         Ok(Self { data })
     }
 
-    pub async fn to_stream<'a>(&self, write: &'a mut TlsStream<TcpStream>) -> Result<(), SpaceErr> {
+    pub async fn to_stream<W>(&self, write: &mut W) -> Result<(), SpaceErr>
+    where
+        W: AsyncWrite + Unpin,
+    {
         write.write_u32(self.data.len() as u32).await?;
         write.write_all(self.data.as_slice()).await?;
         write.flush().await?;
@@ -265,6 +369,10 @@ impl FrameMuxer {
             .into()));
         }
 
+        stream.write_string(stream.config.offer()).await?;
+        let offer = tokio::time::timeout(Duration::from_secs(30), stream.read_string()).await??;
+        stream.protocol = logger.result(stream.config.negotiate(offer.as_str()))?;
+
         Ok(Self::new(stream, logger))
     }
 
@@ -282,7 +390,8 @@ impl FrameMuxer {
         {
             let logger = logger.clone();
             tokio::spawn(async move {
-                logger.result(muxer.mux().await).unwrap();
+                // the error is logged and the connection closed, it must never panic
+                logger.result(muxer.mux().await).unwrap_or_default();
             });
         }
 
@@ -294,55 +403,83 @@ impl FrameMuxer {
         HyperwayEndpoint::new_with_drop(out_tx, in_rx, oneshot_terminate_tx, logger)
     }
 
+    /// runs until the endpoint is dropped or terminated, or the peer sends something that
+    /// can't be read (for example a frame over the size limit). The connection is closed
+    /// when the muxer returns
     pub async fn mux(mut self) -> Result<(), SpaceErr> {
-        loop {
+        let result = loop {
             tokio::select! {
-                            wave = self.rx.recv() => {
-                                match wave {
-                                    None => {
-                               //         return Err(SpaceErr::Msg("rx discon".to_string());
-
-                                      todo!()
-
-                                    },
-                                    Some(wave) => {
-                                       self.stream.write_wave(wave.clone()).await?;
-                                    }
-                                }
+                wave = self.rx.recv() => {
+                    match wave {
+                        None => break Ok(()),
+                        Some(wave) => {
+                            if let Err(err) = self.stream.write_wave(wave).await {
+                                break Err(err);
                             }
-                            wave = self.stream.read_wave() => {
-                                match wave {
-                                   Ok(wave) => {
-                                self.tx.send(wave).await?;
-                                   },
-                                   Err(err) => {
-            //                          self.logger.error(format!("read stream err: {}",err.to_string()));
-                                       return Err(SpaceErr::Msg(err.to_string()));
-                                   }
-                                }
+                        }
+                    }
+                }
+                wave = self.stream.read_wave() => {
+                    match wave {
+                        Ok(wave) => {
+                            if self.tx.send(wave).await.is_err() {
+                                break Ok(());
                             }
-                            _ = self.terminate_rx.recv() => {
-                                 self.logger.warn(format!("terminated"));
-                                 return Ok(())
-                                }
-
+                        }
+                        Err(err) => break Err(err),
+                    }
+                }
+                _ = self.terminate_rx.recv() => {
+                    self.logger.warn(format!("terminated"));
+                    break Ok(());
+                }
             }
-        }
-        Ok(())
+        };
+        self.stream.close().await;
+        result
     }
 }
 
+/// any byte stream a [`FrameStream`] can carry frames over: a tls stream for tcp or a unix
+/// domain socket for local connections
+pub trait FrameIo: AsyncRead + AsyncWrite + Send + Unpin {}
+
+impl<S> FrameIo for S where S: AsyncRead + AsyncWrite + Send + Unpin {}
+
 pub struct FrameStream {
-    stream: TlsStream<TcpStream>,
+    stream: Box<dyn FrameIo>,
+    config: FrameConfig,
+    protocol: FrameProtocol,
 }
 
 impl FrameStream {
-    pub fn new(stream: TlsStream<TcpStream>) -> Self {
-        Self { stream }
+    pub fn new<S>(stream: S) -> Self
+    where
+        S: FrameIo + 'static,
+    {
+        Self::with_config(stream, FrameConfig::default())
+    }
+
+    pub fn with_config<S>(stream: S, config: FrameConfig) -> Self
+    where
+        S: FrameIo + 'static,
+    {
+        // until the handshake has negotiated otherwise waves are plain bincode
+        let protocol = FrameProtocol {
+            codec: FrameCodec::Bincode,
+            compression: None,
+            compress_threshold: config.compress_threshold,
+            max_frame_size: config.max_frame_size,
+        };
+        Self {
+            stream: Box::new(stream),
+            config,
+            protocol,
+        }
     }
 
     pub async fn frame(&mut self) -> Result<Frame, SpaceErr> {
-        Frame::from_stream(&mut self.stream).await
+        Frame::from_stream(&mut self.stream, self.config.max_frame_size).await
     }
 
     pub async fn read_version(&mut self) -> Result<semver::Version, SpaceErr> {
@@ -354,7 +491,8 @@ impl FrameStream {
     }
 
     pub async fn read_wave(&mut self) -> Result<Wave, SpaceErr> {
-        self.frame().await?.to_wave()
+        let frame = self.frame().await?;
+        self.protocol.decode(frame.data.as_slice())
     }
 
     pub async fn write_frame(&mut self, frame: Frame) -> Result<(), SpaceErr> {
@@ -370,7 +508,13 @@ impl FrameStream {
     }
 
     pub async fn write_wave(&mut self, wave: Wave) -> Result<(), SpaceErr> {
-        self.write_frame(Frame::from_wave(wave)?).await
+        let data = self.protocol.encode(&wave)?;
+        self.write_frame(Frame { data }).await
+    }
+
+    /// shut down the write half so the peer sees the connection end
+    pub async fn close(&mut self) {
+        self.stream.shutdown().await.unwrap_or_default();
     }
 }
 
@@ -382,63 +526,152 @@ impl HyperlaneTcpServerApi {
     }
 }
 
+/// where a [`HyperlaneTcpServer`] listens and how it authenticates its clients
+#[derive(Clone)]
+pub struct HyperlaneTcpServerConfig {
+    pub bind: IpAddr,
+    pub port: u16,
+    /// directory holding the server's `cert.der` and `key.der`
+    pub cert_dir: String,
+    pub client_auth: Option<ClientCertAuth>,
+}
+
+impl HyperlaneTcpServerConfig {
+    /// binds to `127.0.0.1` without client certificate verification
+    pub fn new<S: ToString>(port: u16, cert_dir: S) -> Self {
+        Self {
+            bind: IpAddr::V4(Ipv4Addr::LOCALHOST),
+            port,
+            cert_dir: cert_dir.to_string(),
+            client_auth: None,
+        }
+    }
+
+    pub fn with_bind(mut self, bind: IpAddr) -> Self {
+        self.bind = bind;
+        self
+    }
+
+    pub fn with_client_auth(mut self, client_auth: ClientCertAuth) -> Self {
+        self.client_auth = Some(client_auth);
+        self
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        SocketAddr::new(self.bind.clone(), self.port)
+    }
+
+    /// the rustls server config shared by the tcp and quic hyperlane servers
+    pub(crate) fn tls_config(&self) -> Result<ServerConfig, Error> {
+        // load certificate
+        let certs = read_certs(format!("{}/cert.der", self.cert_dir))?;
+        let private_key = read_private_key(format!("{}/key.der", self.cert_dir))?;
+
+        let builder = ServerConfig::builder();
+        let builder = match &self.client_auth {
+            None => builder.with_no_client_auth(),
+            Some(client_auth) => {
+                let mut roots = RootCertStore::empty();
+                for cert in read_certs(client_auth.ca_bundle.clone())? {
+                    roots.add(cert).map_err(Error::new)?;
+                }
+                let verifier = WebPkiClientVerifier::builder(Arc::new(roots));
+                let verifier = if client_auth.required {
+                    verifier
+                } else {
+                    verifier.allow_unauthenticated()
+                };
+                builder.with_client_cert_verifier(verifier.build().map_err(Error::new)?)
+            }
+        };
+        builder
+            .with_single_cert(certs, private_key)
+            .map_err(|e| Error::new(format!("bad certificate/key: {}", e)))
+    }
+}
+
+/// verify client certificates against the CA bundle at `ca_bundle`.
+///
+/// A verified certificate whose subject common name appears in `agents` enters the gate
+/// as that [`Agent`] on a remote the gate's authenticator assigns, any other client must still pass the gate's [`crate::hyperlane::HyperAuthenticator`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClientCertAuth {
+    pub ca_bundle: String,
+    /// reject clients that do not present a certificate
+    #[serde(default = "ClientCertAuth::default_required")]
+    pub required: bool,
+    #[serde(default)]
+    pub agents: HashMap<String, Agent>,
+}
+
+impl ClientCertAuth {
+    fn default_required() -> bool {
+        true
+    }
+
+    pub fn new<S: ToString>(ca_bundle: S) -> Self {
+        Self {
+            ca_bundle: ca_bundle.to_string(),
+            required: true,
+            agents: HashMap::new(),
+        }
+    }
+
+    pub fn optional(mut self) -> Self {
+        self.required = false;
+        self
+    }
+
+    pub fn agent<S: ToString>(mut self, subject: S, agent: Agent) -> Self {
+        self.agents.insert(subject.to_string(), agent);
+        self
+    }
+
+    /// the [`Agent`] mapped to the subject of a client's end entity certificate
+    pub(crate) fn agent_for(&self, cert: &CertificateDer<'_>) -> Result<Option<Agent>, Error> {
+        let (_, cert) = x509_parser::parse_x509_certificate(cert.as_ref())
+            .map_err(|e| Error::new(format!("could not parse client certificate: {}", e)))?;
+        let subject = cert
+            .subject()
+            .iter_common_name()
+            .next()
+            .and_then(|cn| cn.as_str().ok());
+        Ok(subject.and_then(|subject| self.agents.get(subject).cloned()))
+    }
+}
+
 pub struct HyperlaneTcpServer {
     gate: Arc<HyperGateSelector>,
     listener: TcpListener,
     logger: Logger,
     acceptor: TlsAcceptor,
+    client_auth: Option<Arc<ClientCertAuth>>,
     server_kill_tx: broadcast::Sender<()>,
     server_kill_rx: broadcast::Receiver<()>,
 }
 
 impl HyperlaneTcpServer {
     pub async fn new(
-        port: u16,
-        cert_dir: String,
+        config: HyperlaneTcpServerConfig,
         gate: Arc<HyperGateSelector>,
         logger: Logger,
     ) -> Result<Self, Error> {
         let (server_kill_tx, server_kill_rx) = broadcast::channel(1);
 
-        // load certificate
-        let cert_path = format!("{}/cert.der", cert_dir);
-        let key_path = format!("{}/key.der", cert_dir);
-
-        let mut file = BufReader::new(std::fs::File::open(cert_path)?);
-        let ca_certs = rustls_pemfile::certs(&mut file)
-            .map(|i| i.unwrap())
-            .collect();
-
-        let mut file = BufReader::new(std::fs::File::open(key_path)?);
-        let private_key =
-            rustls_pemfile::private_key(&mut file)?.ok_or(Error::new("no private key"))?;
-
-        let server_config = Arc::new(
-            ServerConfig::builder()
-                .with_no_client_auth()
-                .with_single_cert(ca_certs, private_key)
-                .expect("bad certifcate/key"), /*            ServerConfig::builder()
-                                                              .with_safe_default_cipher_suites()
-                                                              .with_safe_default_kx_groups()
-                                                              .with_safe_default_protocol_versions()
-                                                              .unwrap()
-                                                              .with_no_client_auth()
-                                                              .with_single_cert(ca_certs, private_key)
-                                                              .expect("bad certificate/key"),
-
-                                               */
-        );
+        let server_config = config.tls_config()?;
 
-        let mut acceptor = TlsAcceptor::from(server_config);
-        let listener = TcpListener::bind(format!("127.0.0.1:{}", port))
+        let acceptor = TlsAcceptor::from(Arc::new(server_config));
+        let addr = config.addr();
+        let listener = TcpListener::bind(addr.clone())
             .await
-            .unwrap();
+            .map_err(|e| Error::new(format!("could not bind to '{}': {}", addr, e)))?;
 
         Ok(Self {
             acceptor,
             gate,
             listener,
             logger,
+            client_auth: config.client_auth.map(Arc::new),
             server_kill_tx,
             server_kill_rx,
         })
@@ -457,6 +690,7 @@ impl HyperlaneTcpServer {
             let acceptor = self.acceptor.clone();
             let gate = self.gate.clone();
             let logger = self.logger.clone();
+            let client_auth = self.client_auth.clone();
             let mut server_kill_rx = self.server_kill_tx.subscribe();
 
             tokio::spawn(async move {
@@ -464,12 +698,19 @@ impl HyperlaneTcpServer {
                     stream: TcpStream,
                     acceptor: TlsAcceptor,
                     gate: Arc<HyperGateSelector>,
+                    client_auth: Option<Arc<ClientCertAuth>>,
                     server_kill_rx: broadcast::Receiver<()>,
                     logger: Logger,
                 ) -> Result<(), Error> {
                     let mut stream = acceptor.accept(stream).await?;
 
-                    let mut stream = FrameStream::new(stream.into());
+                    // the acceptor has already verified the certificate chain
+                    let agent = match (&client_auth, stream.get_ref().1.peer_certificates()) {
+                        (Some(client_auth), Some([cert, ..])) => client_auth.agent_for(cert)?,
+                        _ => None,
+                    };
+
+                    let mut stream = FrameStream::new(stream);
 
                     let (status_tx, mut status_rx): (
                         mpsc::Sender<HyperConnectionDetails>,
@@ -487,31 +728,48 @@ impl HyperlaneTcpServer {
                             }
                         });
                     }
-                    let mut mux = FrameMuxer::handshake(stream, status_tx, logger.clone()).await?;
-
-                    let knock = tokio::time::timeout(Duration::from_secs(30), mux.rx.recv())
-                        .await?
-                        .ok_or("expected wave")?;
-                    let knock = knock.to_directed()?;
-                    if let Substance::Knock(knock) = knock.body() {
-                        let mut endpoint = gate.knock(knock.clone()).await?;
-                        mux.connect(endpoint);
-                    } else {
-                        let msg = format!(
-                            "expected client Substance::Knock(Knock) encountered '{}'",
-                            knock.body().kind().to_string()
-                        );
-                        return logger.result(Err(SpaceErr::str(msg).into()));
-                    }
+                    let mux = FrameMuxer::handshake(stream, status_tx, logger.clone()).await?;
+
+                    enter(mux, &gate, agent, &logger).await?;
 
                     Ok(())
                 }
-                serve(stream, acceptor, gate, server_kill_rx, logger).await;
+                serve(stream, acceptor, gate, client_auth, server_kill_rx, logger).await;
             });
         }
     }
 }
 
+/// wait for the client's [`Knock`] and connect the muxed endpoint to the gate. A client
+/// that was already identified as an [`Agent`] by its transport keeps that agent but its
+/// remote is always picked by the gate's authenticator, a remote requested in the knock
+/// is never trusted
+pub(crate) async fn enter(
+    mut mux: HyperwayEndpoint,
+    gate: &Arc<HyperGateSelector>,
+    agent: Option<Agent>,
+    logger: &Logger,
+) -> Result<(), Error> {
+    let knock = tokio::time::timeout(Duration::from_secs(30), mux.rx.recv())
+        .await?
+        .ok_or("expected wave")?;
+    let knock = knock.to_directed()?;
+    if let Substance::Knock(knock) = knock.body() {
+        let endpoint = match agent {
+            Some(agent) => gate.assign(knock.clone(), agent).await?,
+            None => gate.knock(knock.clone()).await?,
+        };
+        mux.connect(endpoint);
+        Ok(())
+    } else {
+        let msg = format!(
+            "expected client Substance::Knock(Knock) encountered '{}'",
+            knock.body().kind().to_string()
+        );
+        logger.result(Err(SpaceErr::str(msg).into()))
+    }
+}
+
 pub fn add(left: usize, right: usize) -> usize {
     left + right
 }
@@ -579,19 +837,27 @@ impl From<&str> for Error {
 
 #[cfg(test)]
 mod tests {
-    use crate::hyperlane::tcp::{CertGenerator, Error, HyperlaneTcpClient, HyperlaneTcpServer};
+    use crate::hyperlane::codec::FrameConfig;
+    use crate::hyperlane::tcp::{
+        CertGenerator, ClientCertAuth, Error, Frame, FrameMuxer, FrameStream, HyperlaneTcpClient,
+        HyperlaneTcpServer, HyperlaneTcpServerConfig,
+    };
     use crate::hyperlane::test_util::{
         LargeFrameTest, SingleInterchangePlatform, WaveTest, FAE, LESS,
     };
     use anyhow::anyhow;
     use chrono::{DateTime, Utc};
+    use rcgen::{CertificateParams, DnType, KeyPair};
     use starlane_macros::{logger, push_loc};
-    use starlane_space::err::SpaceErr;
+    use starlane_space::err::{LegacyStatusErr, SpaceErr};
     use starlane_space::loc::ToSurface;
-    use starlane_space::log::{LogAppender, StdOutAppender};
+    use starlane_space::log::{LogAppender, Logger, StdOutAppender};
     use starlane_space::point::Point;
+    use starlane_space::wave::Agent;
     use std::str::FromStr;
     use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::io::AsyncReadExt;
 
     /*
     #[no_mangle]
@@ -611,6 +877,69 @@ mod tests {
 
      */
 
+    #[test]
+    fn test_client_cert_agent() -> Result<(), Error> {
+        let mut params = CertificateParams::new(vec!["localhost".to_string()])?;
+        params.distinguished_name.push(DnType::CommonName, "less");
+        let cert = params.self_signed(&KeyPair::generate()?)?;
+
+        let less = Agent::Point(Point::from_str("less").unwrap());
+        let auth = ClientCertAuth::new("ca.der").agent("less", less.clone());
+        assert_eq!(auth.agent_for(cert.der())?, Some(less));
+
+        let auth = ClientCertAuth::new("ca.der").agent("fae", Agent::Anonymous);
+        assert_eq!(auth.agent_for(cert.der())?, None);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_frame_limit() -> Result<(), Error> {
+        let (mut client, mut server) = tokio::io::duplex(1024);
+        Frame::from_string("01234567".to_string())
+            .to_stream(&mut client)
+            .await?;
+        assert_eq!(
+            Frame::from_stream(&mut server, 8).await?.to_string()?,
+            "01234567"
+        );
+
+        Frame::from_string("012345678".to_string())
+            .to_stream(&mut client)
+            .await?;
+        match Frame::from_stream(&mut server, 8).await {
+            Err(err) => assert_eq!(err.status(), 413),
+            Ok(_) => panic!("expected the frame to exceed the limit"),
+        }
+        Ok(())
+    }
+
+    /// a peer that sends an oversized frame is disconnected instead of panicking the muxer
+    #[tokio::test]
+    async fn test_oversized_frame_closes() -> Result<(), Error> {
+        let (mut client, server) = tokio::io::duplex(1024);
+        let config = FrameConfig {
+            max_frame_size: 8,
+            ..Default::default()
+        };
+        let logger = Logger::default().push(Point::from_str("frame-limit").unwrap());
+        let mut endpoint = FrameMuxer::new(FrameStream::with_config(server, config), logger);
+
+        Frame::from_string("012345678".to_string())
+            .to_stream(&mut client)
+            .await?;
+
+        let closed = tokio::time::timeout(Duration::from_secs(5), endpoint.rx.recv())
+            .await
+            .unwrap();
+        assert!(closed.is_none());
+        let mut rest = vec![];
+        tokio::time::timeout(Duration::from_secs(5), client.read_to_end(&mut rest))
+            .await
+            .unwrap()?;
+        assert!(rest.is_empty());
+        Ok(())
+    }
+
     //#[tokio::test]
     async fn test_tcp() -> Result<(), Error> {
         let platform = SingleInterchangePlatform::new().await;
@@ -621,9 +950,12 @@ mod tests {
         let logger = logger!();
         let logger = push_loc!((logger, Point::from_str("tcp-blah").unwrap()));
         let port = 4344u16;
-        let server =
-            HyperlaneTcpServer::new(port, ".".to_string(), platform.gate.clone(), logger.clone())
-                .await?;
+        let server = HyperlaneTcpServer::new(
+            HyperlaneTcpServerConfig::new(port, "."),
+            platform.gate.clone(),
+            logger.clone(),
+        )
+        .await?;
         let api = server.start()?;
 
         let less_logger = push_loc!((logger, &*LESS));
@@ -661,9 +993,12 @@ mod tests {
         let logger = logger!();
         let logger = push_loc!((logger, Point::from_str("tcp-blah").unwrap()));
         let port = 4345u16;
-        let server =
-            HyperlaneTcpServer::new(port, ".".to_string(), platform.gate.clone(), logger.clone())
-                .await?;
+        let server = HyperlaneTcpServer::new(
+            HyperlaneTcpServerConfig::new(port, "."),
+            platform.gate.clone(),
+            logger.clone(),
+        )
+        .await?;
         let api = server.start()?;
 
         let less_logger = push_loc!((logger, &*LESS));
@@ -0,0 +1,323 @@
+use crate::hyperlane::tcp::{enter, Error, FrameMuxer, FrameStream};
+use crate::hyperlane::{
+    HyperConnectionDetails, HyperGateSelector, HyperwayEndpoint, HyperwayEndpointFactory,
+};
+use async_trait::async_trait;
+use starlane_space::err::SpaceErr;
+use starlane_space::hyper::Knock;
+use starlane_space::log::Logger;
+use starlane_space::wave::{Agent, PingCore, WaveVariantDef};
+use std::collections::HashMap;
+use std::os::unix::fs::{MetadataExt, PermissionsExt};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::net::{UnixListener, UnixStream};
+use tokio::sync::mpsc;
+
+/// connects to a [`HyperlaneUnixServer`] on the same machine. No certificates are needed,
+/// the server identifies the client by the uid of the connecting process
+pub struct HyperlaneUnixClient {
+    path: PathBuf,
+    knock: Knock,
+    logger: Logger,
+}
+
+impl HyperlaneUnixClient {
+    pub fn new<P: Into<PathBuf>>(path: P, knock: Knock, logger: Logger) -> Self {
+        Self {
+            path: path.into(),
+            knock,
+            logger,
+        }
+    }
+}
+
+#[async_trait]
+impl HyperwayEndpointFactory for HyperlaneUnixClient {
+    async fn create(
+        &self,
+        status_tx: mpsc::Sender<HyperConnectionDetails>,
+    ) -> Result<HyperwayEndpoint, SpaceErr> {
+        let stream = UnixStream::connect(&self.path).await?;
+        let stream = FrameStream::new(stream);
+
+        let endpoint =
+            FrameMuxer::handshake(stream, status_tx.clone(), self.logger.clone()).await?;
+
+        let wave: WaveVariantDef<PingCore> = self.knock.clone().into();
+        let wave = wave.to_wave();
+        endpoint.tx.send(wave).await?;
+
+        Ok(endpoint)
+    }
+}
+
+/// the socket path of a [`HyperlaneUnixServer`] and the [`Agent`] each peer uid is
+/// allowed to connect as. Peers whose uid is not mapped are refused
+#[derive(Clone)]
+pub struct HyperlaneUnixServerConfig {
+    pub path: PathBuf,
+    /// the agent of peers running as the same user as the server
+    pub owner: Option<Agent>,
+    pub agents: HashMap<u32, Agent>,
+}
+
+impl HyperlaneUnixServerConfig {
+    /// only processes owned by the server's own user may connect, as [`Agent::HyperUser`]
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Self {
+            path: path.into(),
+            owner: Some(Agent::HyperUser),
+            agents: HashMap::new(),
+        }
+    }
+
+    pub fn agent(mut self, uid: u32, agent: Agent) -> Self {
+        self.agents.insert(uid, agent);
+        self
+    }
+}
+
+pub struct HyperlaneUnixServerApi {
+    path: PathBuf,
+}
+
+impl HyperlaneUnixServerApi {
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+}
+
+pub struct HyperlaneUnixServer {
+    gate: Arc<HyperGateSelector>,
+    listener: UnixListener,
+    path: PathBuf,
+    agents: Arc<HashMap<u32, Agent>>,
+    logger: Logger,
+}
+
+impl HyperlaneUnixServer {
+    pub async fn new(
+        config: HyperlaneUnixServerConfig,
+        gate: Arc<HyperGateSelector>,
+        logger: Logger,
+    ) -> Result<Self, Error> {
+        // a socket left behind by a server that did not shut down cleanly
+        if config.path.exists() {
+            std::fs::remove_file(&config.path)?;
+        }
+        if let Some(parent) = config.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let listener = UnixListener::bind(&config.path).map_err(|e| {
+            Error::new(format!(
+                "could not bind to '{}': {}",
+                config.path.display(),
+                e
+            ))
+        })?;
+
+        let metadata = std::fs::metadata(&config.path)?;
+        // nobody but the owner may connect unless other uids have been granted an agent
+        let mode = if config.agents.is_empty() {
+            0o600
+        } else {
+            0o666
+        };
+        std::fs::set_permissions(&config.path, PermissionsExt::from_mode(mode))?;
+
+        // the socket file was just created by this process so its owner is our own uid
+        let mut agents = config.agents;
+        if let Some(owner) = config.owner {
+            agents.insert(metadata.uid(), owner);
+        }
+
+        Ok(Self {
+            gate,
+            listener,
+            path: config.path,
+            agents: Arc::new(agents),
+            logger,
+        })
+    }
+
+    pub fn start(self) -> Result<HyperlaneUnixServerApi, Error> {
+        let api = HyperlaneUnixServerApi {
+            path: self.path.clone(),
+        };
+        tokio::spawn(async move {
+            self.run().await;
+        });
+        Ok(api)
+    }
+
+    async fn run(self) {
+        loop {
+            let stream = match self.listener.accept().await {
+                Ok((stream, _)) => stream,
+                Err(err) => {
+                    self.logger.error(format!("unix socket accept: {}", err));
+                    continue;
+                }
+            };
+            let gate = self.gate.clone();
+            let agents = self.agents.clone();
+            let logger = self.logger.clone();
+
+            tokio::spawn(async move {
+                async fn serve(
+                    stream: UnixStream,
+                    gate: Arc<HyperGateSelector>,
+                    agents: Arc<HashMap<u32, Agent>>,
+                    logger: Logger,
+                ) -> Result<(), Error> {
+                    // SO_PEERCRED
+                    let uid = stream.peer_cred()?.uid();
+                    let agent = agents.get(&uid).cloned().ok_or(Error::new(format!(
+                        "unix socket peer uid {} is not mapped to an agent",
+                        uid
+                    )))?;
+
+                    let stream = FrameStream::new(stream);
+
+                    let (status_tx, mut status_rx): (
+                        mpsc::Sender<HyperConnectionDetails>,
+                        mpsc::Receiver<HyperConnectionDetails>,
+                    ) = mpsc::channel(1024);
+                    tokio::spawn(async move { while let Some(_) = status_rx.recv().await {} });
+
+                    let mux = FrameMuxer::handshake(stream, status_tx, logger.clone()).await?;
+
+                    enter(mux, &gate, Some(agent), &logger).await
+                }
+                if let Err(err) = serve(stream, gate, agents, logger.clone()).await {
+                    logger.warn(err.to_string());
+                }
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::hyperlane::test_util::{
+        LargeFrameTest, SingleInterchangePlatform, TestGreeter, FAE, LESS,
+    };
+    use crate::hyperlane::unix::{
+        HyperlaneUnixClient, HyperlaneUnixServer, HyperlaneUnixServerConfig,
+    };
+    use crate::hyperlane::{
+        AnonHyperAuthenticatorAssignEndPoint, DefaultHyperwayConfigurator, HyperClient,
+        HyperGate, HyperGateSelector, HyperwayInterchange, InterchangeGate,
+    };
+    use crate::test_util::temp_dir;
+    use dashmap::DashMap;
+    use starlane_macros::push_loc;
+    use starlane_space::command::direct::create::PointFactoryU64;
+    use starlane_space::hyper::{InterchangeKind, Knock};
+    use starlane_space::loc::ToSurface;
+    use starlane_space::log::Logger;
+    use starlane_space::point::Point;
+    use starlane_space::substance::Substance;
+    use starlane_space::wave::Agent;
+    use std::str::FromStr;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_large_frame() -> Result<(), crate::hyperlane::tcp::Error> {
+        let platform = SingleInterchangePlatform::new().await;
+
+        let logger = Logger::default().push(Point::from_str("unix-blah").unwrap());
+        let dir = temp_dir("hyperlane-unix");
+        let path = dir.path().join("hyperlane.sock");
+        let server = HyperlaneUnixServer::new(
+            HyperlaneUnixServerConfig::new(path.clone()),
+            platform.gate.clone(),
+            logger.clone(),
+        )
+        .await?;
+        server.start()?;
+
+        let less_logger = push_loc!((logger, &*LESS));
+        let less_client = Box::new(HyperlaneUnixClient::new(
+            path.clone(),
+            platform.knock(LESS.to_surface()),
+            less_logger,
+        ));
+
+        let fae_logger = push_loc!((logger, &*FAE));
+        let fae_client = Box::new(HyperlaneUnixClient::new(
+            path,
+            platform.knock(FAE.to_surface()),
+            fae_logger,
+        ));
+
+        let test = LargeFrameTest::new(fae_client, less_client);
+
+        test.go().await.unwrap();
+
+        Ok(())
+    }
+
+    /// the gate assigns the remote whether or not the knock requests one and the peer uid
+    /// agent is kept
+    #[tokio::test]
+    async fn test_assign_remote() -> Result<(), crate::hyperlane::tcp::Error> {
+        let point = Point::from_str("point").unwrap();
+        let logger = Logger::default().push(point.clone());
+        let interchange = Arc::new(HyperwayInterchange::new(point.clone(), logger.clone()));
+        let remotes = point.push("remotes").unwrap();
+        let auth = AnonHyperAuthenticatorAssignEndPoint::new(
+            Arc::new(PointFactoryU64::new(remotes.clone(), "remote-".to_string())),
+            logger.clone(),
+        );
+        let gate: Arc<dyn HyperGate> = Arc::new(InterchangeGate::new(
+            auth,
+            TestGreeter::new(),
+            DefaultHyperwayConfigurator,
+            interchange,
+            logger.clone(),
+        ));
+        let gates = Arc::new(DashMap::new());
+        gates.insert(InterchangeKind::Singleton, gate);
+        let gate = Arc::new(HyperGateSelector::new(gates));
+
+        let dir = temp_dir("hyperlane-unix-assign");
+        let path = dir.path().join("hyperlane.sock");
+        let server = HyperlaneUnixServer::new(
+            HyperlaneUnixServerConfig::new(path.clone()),
+            gate,
+            logger.clone(),
+        )
+        .await?;
+        server.start()?;
+
+        // a remote requested by the client is ignored, the gate assigns it
+        let claimed = point.push("claimed").unwrap().to_surface();
+        let mut clients = vec![];
+        for (remote, assigned) in [(None, "remote-0"), (Some(claimed), "remote-1")] {
+            let knock = Knock {
+                kind: InterchangeKind::Singleton,
+                auth: Box::new(Substance::Empty),
+                remote,
+            };
+            let client = HyperClient::new(
+                Box::new(HyperlaneUnixClient::new(path.clone(), knock, logger.clone())),
+                logger.clone(),
+            )
+            .unwrap();
+            let greet = tokio::time::timeout(Duration::from_secs(10), client.wait_for_greet())
+                .await
+                .unwrap()
+                .unwrap();
+
+            assert_eq!(greet.agent, Agent::HyperUser);
+            assert_eq!(greet.surface, remotes.push(assigned).unwrap().to_surface());
+            clients.push(client);
+        }
+
+        Ok(())
+    }
+}
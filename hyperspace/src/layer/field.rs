@@ -9,11 +9,11 @@ use starlane_space::err::{CoreReflector, LegacyStatusErr, SpaceErr};
 use starlane_space::loc::{Layer, Surface, ToSurface};
 use starlane_space::log::{Logger, Trackable};
 use starlane_space::parse::model::{PipelineSegmentVar, PipelineVar};
-use starlane_space::parse::{Env, RegexCapturesResolver};
+use starlane_space::parse::Env;
 use starlane_space::particle::traversal::{Traversal, TraversalLayer};
 use starlane_space::point::Point;
 use starlane_space::selector::PayloadBlock;
-use starlane_space::substance::Substance;
+use starlane_space::substance::{Call, Substance};
 use starlane_space::util::{log, ToResolved};
 use starlane_space::wave::core::{Method, ReflectedCore};
 use starlane_space::wave::exchange::asynch::ProtoTransmitter;
@@ -89,7 +89,6 @@ impl Field {
 
     pub fn pipex(&self, traversal: Traversal<DirectedWave>, pipeline: PipelineVar, env: Env) {
         PipeEx::new(
-            self.skel.clone(),
             self.port.clone(),
             traversal,
             pipeline,
@@ -124,14 +123,11 @@ impl TraversalLayer for Field {
     async fn directed_core_bound(&self, directed: Traversal<DirectedWave>) -> Result<(), SpaceErr> {
         let bind = self.bind(&directed).await?;
 
-        match bind.select(&directed.payload) {
-            Ok(route) => {
-                let regex = route.selector.path.clone();
+        match bind.select_with_captures(&directed.payload) {
+            Ok((route, captures)) => {
                 let env = {
-                    let path_regex_capture_resolver =
-                        RegexCapturesResolver::new(regex, directed.core().uri.path().to_string())?;
                     let mut env = Env::new(self.port.point.clone());
-                    env.add_var_resolver(Arc::new(path_regex_capture_resolver));
+                    env.add_var_resolver(Arc::new(captures));
                     env.set_var("doc.bundle", bind.bundle().clone().into());
                     env.set_var("doc", bind.point().clone().into());
                     env
@@ -180,7 +176,6 @@ impl TraversalLayer for Field {
 }
 
 pub struct PipeEx {
-    pub skel: HyperStarSkel,
     pub surface: Surface,
     pub logger: Logger,
     pub env: Env,
@@ -199,7 +194,6 @@ pub struct PipeEx {
 
 impl PipeEx {
     pub fn new(
-        skel: HyperStarSkel,
         port: Surface,
         traversal: Traversal<DirectedWave>,
         pipeline: PipelineVar,
@@ -210,7 +204,6 @@ impl PipeEx {
     ) {
         tokio::spawn(async move {
             let pipex = Self {
-                skel,
                 kind: traversal.directed_kind(),
                 method: traversal.core().method.clone(),
                 uri: traversal.core().uri.clone(),
@@ -308,8 +301,16 @@ impl PipeEx {
                 self.gravity_transmitter.route(reflected.to_wave()).await;
                 Ok(())
             }
-            PipelineStopVar::Call(_) => {
-                unimplemented!()
+            PipelineStopVar::Call(call) => {
+                let call: Call = call.clone().to_resolved(&self.env)?;
+                let path: String = call.kind.path().clone().to_resolved(&self.env)?;
+                let uri = self.uri.join(path.as_str()).map_err(SpaceErr::map)?;
+                let mut proto = self.proto();
+                proto.method(call.kind.method());
+                proto.uri(uri);
+                proto.to(call.point.to_surface().with_layer(Layer::Core));
+
+                self.direct(proto, self.gravity_transmitter.clone()).await
             }
             PipelineStopVar::Point(point) => {
                 let point: Point = point.clone().to_resolved(&self.env)?;
@@ -318,8 +319,16 @@ impl PipeEx {
 
                 self.direct(proto, self.gravity_transmitter.clone()).await
             }
-            PipelineStopVar::Err { .. } => {
-                unimplemented!()
+            PipelineStopVar::Err { status, msg } => {
+                // an error stop ends the pipeline regardless of any segments that follow it
+                self.pipeline.segments.clear();
+                self.status = *status;
+                let reflection = self.reflection.clone()?;
+                let core = ReflectedCore::fail(*status, msg);
+                let reflected = reflection.make(core, self.traversal.to.clone());
+
+                self.gravity_transmitter.route(reflected.to_wave()).await;
+                Ok(())
             }
         }
     }
@@ -360,7 +369,7 @@ impl PipeEx {
                 }
             }
             DirectedKind::Signal => {
-                transmitter.direct(proto).await?;
+                transmitter.direct::<_, ()>(proto).await?;
                 Ok(())
             }
         }
@@ -400,9 +409,161 @@ impl PipeEx {
                 }
             }
             DirectedKind::Signal => {
-                transmitter.direct(proto).await?;
+                transmitter.direct::<()>(proto).await?;
                 Ok(())
             }
         }
     }
 }
+
+#[cfg(test)]
+pub mod test {
+    use crate::layer::field::PipeEx;
+    use async_trait::async_trait;
+    use starlane_space::err::SpaceErr;
+    use starlane_space::hyper::ParticleRecord;
+    use starlane_space::loc::{Layer, Surface, ToSurface};
+    use starlane_space::log::Logger;
+    use starlane_space::parse::{bind_config, Env};
+    use starlane_space::particle::traversal::{Traversal, TraversalDirection};
+    use starlane_space::point::Point;
+    use starlane_space::settings::Timeouts;
+    use starlane_space::substance::Substance;
+    use starlane_space::wave::core::ext::ExtMethod;
+    use starlane_space::wave::core::http2::StatusCode;
+    use starlane_space::wave::core::ReflectedCore;
+    use starlane_space::wave::exchange::asynch::{
+        Exchanger, ProtoTransmitterBuilder, TraversalRouter, TraversalTransmitter, TxRouter,
+    };
+    use starlane_space::wave::exchange::SetStrategy;
+    use starlane_space::wave::{DirectedProto, DirectedWave, Wave};
+    use std::str::FromStr;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::sync::mpsc;
+
+    /// these pipelines never traverse to the Core of the particle they are bound to
+    struct NoTraversal;
+
+    #[async_trait]
+    impl TraversalRouter for NoTraversal {
+        async fn traverse(&self, _: Traversal<Wave>) -> Result<(), SpaceErr> {
+            Err(SpaceErr::server_error("unexpected traversal"))
+        }
+    }
+
+    fn surface() -> Surface {
+        Point::from_str("localhost:app")
+            .unwrap()
+            .to_surface()
+            .with_layer(Layer::Field)
+    }
+
+    fn ping(method: &str) -> DirectedWave {
+        let mut proto = DirectedProto::ping();
+        proto.from(Point::from_str("localhost:client").unwrap().to_surface());
+        proto.to(surface());
+        proto.method(ExtMethod::new(method).unwrap());
+        proto.body(Substance::Text("hello".to_string()));
+        proto.build().unwrap()
+    }
+
+    /// start the pipeline `bind` selects for `directed`.  Every wave the pipeline sends into
+    /// gravity is received by the returned receiver
+    fn pipex(bind: &str, directed: DirectedWave) -> (Exchanger, mpsc::Receiver<Wave>) {
+        let surface = surface();
+        let logger = Logger::default().push(surface.point.clone());
+        let bind = bind_config(bind).unwrap();
+        let pipeline = bind.select(&directed).unwrap().block.clone();
+
+        let exchanger = Exchanger::new(surface.clone(), Timeouts::default(), logger.clone());
+        let (tx, rx) = mpsc::channel(16);
+        let mut gravity =
+            ProtoTransmitterBuilder::new(Arc::new(TxRouter::new(tx)), exchanger.clone());
+        gravity.from = SetStrategy::Override(surface.clone());
+        let shell = TraversalTransmitter::new(Arc::new(NoTraversal), exchanger.clone());
+
+        let traversal = Traversal::new(
+            directed,
+            ParticleRecord::root(),
+            Layer::Field,
+            logger.clone(),
+            TraversalDirection::Core,
+            Some(Layer::Core),
+            surface.clone(),
+            surface.point.clone(),
+        );
+        PipeEx::new(
+            surface.clone(),
+            traversal,
+            pipeline,
+            Env::new(surface.point.clone()),
+            shell,
+            gravity.build(),
+            logger,
+        );
+        (exchanger, rx)
+    }
+
+    async fn recv(rx: &mut mpsc::Receiver<Wave>) -> Wave {
+        tokio::time::timeout(Duration::from_secs(5), rx.recv())
+            .await
+            .unwrap()
+            .unwrap()
+    }
+
+    /// a Call stop directs the wave to the Core of the called particle and its reflection is
+    /// what the pipeline reflects back
+    #[tokio::test]
+    pub async fn test_call_stop() {
+        let bind = r#"Bind(version=1.0.0) {
+              Route -> {
+                 Ext<Go> -> localhost:users^Ext<Fetch> => &;
+              }
+           }"#;
+        let directed = ping("Go");
+        let (exchanger, mut rx) = pipex(bind, directed.clone());
+
+        let call = recv(&mut rx).await.to_directed().unwrap();
+        assert_eq!(
+            call.to().clone().to_single().unwrap(),
+            Point::from_str("localhost:users")
+                .unwrap()
+                .to_surface()
+                .with_layer(Layer::Core)
+        );
+        assert_eq!(call.core().method, ExtMethod::new("Fetch").unwrap().into());
+        assert_eq!(call.core().body, Substance::Text("hello".to_string()));
+
+        let pong = call.reflection().unwrap().make(
+            ReflectedCore::ok_body(Substance::Text("users".to_string())),
+            call.to().clone().to_single().unwrap(),
+        );
+        exchanger.reflected(pong).await.unwrap();
+
+        let reflected = recv(&mut rx).await.to_reflected().unwrap();
+        assert_eq!(reflected.reflection_of(), directed.id());
+        assert_eq!(reflected.core().status, StatusCode::from_u16(200).unwrap());
+        assert_eq!(reflected.core().body, Substance::Text("users".to_string()));
+    }
+
+    /// an Err stop reflects its status and message without directing the wave anywhere
+    #[tokio::test]
+    pub async fn test_err_stop() {
+        let bind = r#"Bind(version=1.0.0) {
+              Route -> {
+                 Ext<Go> -> Err<404>("no such user") => &;
+              }
+           }"#;
+        let directed = ping("Go");
+        let (_, mut rx) = pipex(bind, directed.clone());
+
+        let reflected = recv(&mut rx).await.to_reflected().unwrap();
+        assert_eq!(reflected.reflection_of(), directed.id());
+        assert_eq!(reflected.core(), &ReflectedCore::fail(404, "no such user"));
+
+        // nothing after the Err stop runs
+        let next = tokio::time::timeout(Duration::from_millis(100), rx.recv()).await;
+        assert!(!matches!(next, Ok(Some(_))));
+    }
+}
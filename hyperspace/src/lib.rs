@@ -9,9 +9,12 @@ pub mod star;
 
 pub mod driver;
 pub mod executor;
+#[cfg(test)]
+pub mod test_util;
 pub mod host;
 pub mod hyperlane;
 pub mod base;
+pub mod password;
 pub mod properties;
 pub mod shutdown;
 pub mod tests;
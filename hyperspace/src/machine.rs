@@ -7,7 +7,7 @@ use crate::hyperlane::{
 };
 use crate::base::Platform;
 use crate::registry::Registry;
-use crate::service::{service_conf, Service, ServiceErr, ServiceKind, ServiceSelector, ServiceTemplate};
+use crate::service::{service_conf, Service, ServiceConf, ServiceErr, ServiceKind, ServiceSelector, ServiceTemplate};
 use crate::star::{HyperStar, HyperStarApi, HyperStarSkel, HyperStarTx, StarCon, StarTemplate};
 use crate::template::Templates;
 use async_trait::async_trait;
@@ -208,7 +208,10 @@ where
         let artifacts = platform.artifact_hub();
         let registry = platform.global_registry().await?.clone();
         let machine_api = MachineApi::new(call_tx.clone(), registry, artifacts, &platform);
-        tokio::spawn(async move { Machine::init(platform, call_tx, call_rx).await });
+        // errors standing up the stars and services are returned to the caller, once the
+        // machine is running it is driven by its own task
+        let machine = Machine::init(platform, call_tx, call_rx).await?;
+        tokio::spawn(async move { machine.start().await });
 
         Ok(machine_api)
     }
@@ -217,7 +220,7 @@ where
         platform: P,
         call_tx: mpsc::Sender<MachineCall>,
         call_rx: mpsc::Receiver<MachineCall>,
-    ) -> Result<MachineApi, HyperErr2> {
+    ) -> Result<Self, HyperErr2> {
         let template = platform.machine_template();
         let machine_name = platform.machine_name();
         let artifacts = platform.artifact_hub();
@@ -335,7 +338,7 @@ where
         }
 
         let mut gate_selector = Arc::new(HyperGateSelector::new(gates));
-        skel.platform.start_services(&gate_selector).await;
+        skel.platform.start_services(&gate_selector).await?;
         let gate: Arc<dyn HyperGate> = gate_selector.clone();
 
         let (machine_point, machine_star) = stars
@@ -436,8 +439,7 @@ where
         let fetcher = Arc::new(ClientArtifactFetcher::new(client, skel.registry.clone()));
         //        skel.artifacts.set_fetcher(fetcher).await;
 
-        machine.start().await;
-        Ok(machine_api)
+        Ok(machine)
     }
 
     async fn init0(&self) {
@@ -632,6 +634,13 @@ impl MachineTemplate {
 
 impl Default for MachineTemplate {
     fn default() -> Self {
+        Self::new(service_conf())
+    }
+}
+
+impl MachineTemplate {
+    /// the standard constellation whose FileStore services all run with `config`
+    pub fn new(config: ServiceConf) -> Self {
         let constellation = "central".to_string();
 
         let mut central = StarTemplate::new(StarKey::central(), StarSub::Central);
@@ -683,14 +692,26 @@ impl Default for MachineTemplate {
         stars.push(jump);
         stars.push(fold);
 
-        let config = service_conf();
-        let filestore = ServiceTemplate {
+        let repo = ServiceTemplate {
             name: "repo-filestore".to_string(),
             kind: ServiceKind::FileStore,
             driver: OptSelector::Selector(KindSelector::from_base(BaseKind::Repo)),
+            config: config.clone(),
+        };
+        // FileStore particles and their Files share the same FileStore service
+        let filestore = ServiceTemplate {
+            name: "filestore".to_string(),
+            kind: ServiceKind::FileStore,
+            driver: OptSelector::Selector(KindSelector::from_base(BaseKind::FileStore)),
+            config: config.clone(),
+        };
+        let files = ServiceTemplate {
+            name: "file-filestore".to_string(),
+            kind: ServiceKind::FileStore,
+            driver: OptSelector::Selector(KindSelector::from_base(BaseKind::File)),
             config,
         };
-        let services = Templates::new(vec![filestore]);
+        let services = Templates::new(vec![repo, filestore, files]);
 
         Self { stars, services }
     }
@@ -0,0 +1,86 @@
+use sha2::Sha256;
+use starlane_space::util::uuid;
+
+/// OWASP's recommended iteration count for PBKDF2-HMAC-SHA256
+#[cfg(not(test))]
+pub static PASSWORD_HASH_ROUNDS: u32 = 600_000;
+
+/// unit tests run unoptimized where the full rounds take seconds per hash
+#[cfg(test)]
+pub static PASSWORD_HASH_ROUNDS: u32 = 1000;
+
+static SCHEME: &str = "pbkdf2-sha256";
+
+/// one way hash of `password` with a random salt in the form
+/// `pbkdf2-sha256$<rounds>$<salt>$<hash>`
+pub fn hash(password: &str) -> String {
+    hash_with_rounds(password, PASSWORD_HASH_ROUNDS)
+}
+
+pub fn hash_with_rounds(password: &str, rounds: u32) -> String {
+    let salt = uuid().to_string();
+    let key = derive(password, salt.as_str(), rounds);
+    format!("{}${}${}${}", SCHEME, rounds, salt, hex(&key))
+}
+
+/// true if `value` is already a hash produced by [`hash`]
+pub fn is_hash(value: &str) -> bool {
+    value.starts_with(format!("{}$", SCHEME).as_str())
+}
+
+/// compare `password` against a hash produced by [`hash`] in constant time.  A hash of any
+/// other than [`PASSWORD_HASH_ROUNDS`] rounds never verifies
+pub fn verify(password: &str, hash: &str) -> bool {
+    verify_with_rounds(password, hash, PASSWORD_HASH_ROUNDS)
+}
+
+pub fn verify_with_rounds(password: &str, hash: &str, rounds: u32) -> bool {
+    let mut parts = hash.split('$');
+    match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some(scheme), Some(stored), Some(salt), Some(expected)) if scheme == SCHEME => {
+            if stored.parse::<u32>() != Ok(rounds) {
+                return false;
+            }
+            let key = hex(&derive(password, salt, rounds));
+            key.len() == expected.len()
+                && key
+                    .bytes()
+                    .zip(expected.bytes())
+                    .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+                    == 0
+        }
+        _ => false,
+    }
+}
+
+fn derive(password: &str, salt: &str, rounds: u32) -> [u8; 32] {
+    let mut key = [0u8; 32];
+    pbkdf2::pbkdf2_hmac::<Sha256>(password.as_bytes(), salt.as_bytes(), rounds, &mut key);
+    key
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+pub mod test {
+    use crate::password::{hash_with_rounds, is_hash, verify, verify_with_rounds};
+
+    #[test]
+    pub fn test_verify() {
+        let hash = hash_with_rounds("correct horse", 1000);
+        assert!(is_hash(hash.as_str()));
+        assert!(!is_hash("correct horse"));
+        assert!(verify_with_rounds("correct horse", hash.as_str(), 1000));
+        assert!(!verify_with_rounds("battery staple", hash.as_str(), 1000));
+        assert!(!verify_with_rounds("correct horse", "correct horse", 1000));
+
+        // a hash of any other rounds than PASSWORD_HASH_ROUNDS is rejected
+        assert!(verify("correct horse", hash.as_str()));
+        assert!(!verify("correct horse", hash_with_rounds("correct horse", 1).as_str()));
+
+        // every hash is salted differently
+        assert_ne!(hash, hash_with_rounds("correct horse", 1000));
+    }
+}
@@ -1,30 +1,23 @@
-use once_cell::sync::Lazy;
+use crate::password;
+use starlane_space::command::common::{PropertyMod, SetProperties};
 use starlane_space::err::SpaceErr;
-use starlane_space::kind::BaseKind;
+use starlane_space::kind::{BaseKind, Kind};
 use starlane_space::loc::ToBaseKind;
 use starlane_space::particle::property::{
     AnythingPattern, BoolPattern, EmailPattern, PointPattern, PropertiesConfig, PropertyPermit,
     PropertySource, U64Pattern, UsernamePattern,
 };
+use starlane_space::particle::Properties;
 
-pub static DEFAULT_PROPERTIES_CONFIG: Lazy<PropertiesConfig> =
-    Lazy::new(|| default_properties_config().unwrap());
-pub static USER_PROPERTIES_CONFIG: Lazy<PropertiesConfig> =
-    Lazy::new(|| user_properties_config().unwrap());
-pub static USER_BASE_PROPERTIES_CONFIG: Lazy<PropertiesConfig> =
-    Lazy::new(|| userbase_properties_config().unwrap());
-pub static MECHTRON_PROERTIES_CONFIG: Lazy<PropertiesConfig> =
-    Lazy::new(|| mechtron_properties_config().unwrap());
-pub static UNREQUIRED_BIND_AND_CONFIG_PROERTIES_CONFIG: Lazy<PropertiesConfig> =
-    Lazy::new(|| unrequired_bind_and_config_properties_config().unwrap());
-
-fn default_properties_config() -> Result<PropertiesConfig, SpaceErr> {
+fn default_properties_config(kind: &Kind) -> Result<PropertiesConfig, SpaceErr> {
     let mut builder = PropertiesConfig::builder();
+    builder.kind(kind.clone());
     builder.build()
 }
 
-fn mechtron_properties_config() -> Result<PropertiesConfig, SpaceErr> {
+fn mechtron_properties_config(kind: &Kind) -> Result<PropertiesConfig, SpaceErr> {
     let mut builder = PropertiesConfig::builder();
+    builder.kind(kind.clone());
     builder.add(
         "bind",
         Box::new(PointPattern {}),
@@ -48,8 +41,9 @@ fn mechtron_properties_config() -> Result<PropertiesConfig, SpaceErr> {
     builder.build()
 }
 
-fn unrequired_bind_and_config_properties_config() -> Result<PropertiesConfig, SpaceErr> {
+fn unrequired_bind_and_config_properties_config(kind: &Kind) -> Result<PropertiesConfig, SpaceErr> {
     let mut builder = PropertiesConfig::builder();
+    builder.kind(kind.clone());
     builder.add(
         "bind",
         Box::new(PointPattern {}),
@@ -73,8 +67,9 @@ fn unrequired_bind_and_config_properties_config() -> Result<PropertiesConfig, Sp
     builder.build()
 }
 
-fn user_properties_config() -> Result<PropertiesConfig, SpaceErr> {
+fn user_properties_config(kind: &Kind) -> Result<PropertiesConfig, SpaceErr> {
     let mut builder = PropertiesConfig::builder();
+    builder.kind(kind.clone());
     builder.add(
         "bind",
         Box::new(PointPattern {}),
@@ -115,11 +110,22 @@ fn user_properties_config() -> Result<PropertiesConfig, SpaceErr> {
         false,
         vec![],
     );
+    builder.add(
+        "api-token",
+        Box::new(AnythingPattern {}),
+        false,
+        true,
+        PropertySource::CoreSecret,
+        None,
+        false,
+        vec![],
+    );
     builder.build()
 }
 
-fn userbase_properties_config() -> Result<PropertiesConfig, SpaceErr> {
+fn userbase_properties_config(kind: &Kind) -> Result<PropertiesConfig, SpaceErr> {
     let mut builder = PropertiesConfig::builder();
+    builder.kind(kind.clone());
     builder.add(
         "bind",
         Box::new(PointPattern {}),
@@ -173,13 +179,94 @@ fn userbase_properties_config() -> Result<PropertiesConfig, SpaceErr> {
     builder.build()
 }
 
-pub fn properties_config<K: ToBaseKind>(base: &K) -> &'static PropertiesConfig {
-    match base.to_base() {
-        BaseKind::Space => &UNREQUIRED_BIND_AND_CONFIG_PROERTIES_CONFIG,
-        BaseKind::User => &USER_BASE_PROPERTIES_CONFIG,
-        BaseKind::User => &USER_PROPERTIES_CONFIG,
-        BaseKind::App => &MECHTRON_PROERTIES_CONFIG,
-        BaseKind::Mechtron => &MECHTRON_PROERTIES_CONFIG,
-        _ => &DEFAULT_PROPERTIES_CONFIG,
+/// the [PropertiesConfig] of particles of `kind`
+pub fn properties_config(kind: &Kind) -> PropertiesConfig {
+    match kind.to_base() {
+        BaseKind::Space => unrequired_bind_and_config_properties_config(kind),
+        BaseKind::UserBase => userbase_properties_config(kind),
+        BaseKind::User => user_properties_config(kind),
+        BaseKind::App => mechtron_properties_config(kind),
+        BaseKind::Mechtron => mechtron_properties_config(kind),
+        _ => default_properties_config(kind),
+    }
+    .unwrap()
+}
+
+/// `CoreSecret` properties that are stored as a [`password::hash`] instead of the value
+/// that was set
+pub static HASHED_PROPERTIES: [&str; 2] = ["password", "api-token"];
+
+/// replace the value of every [`HASHED_PROPERTIES`] in `set` with its hash.  A value that
+/// looks like a hash is hashed again so a client can never store a hash it computed itself
+pub fn hash_secrets(config: &PropertiesConfig, set: &SetProperties) -> SetProperties {
+    let mut rtn = set.clone();
+    for (key, property) in set.iter() {
+        if let PropertyMod::Set { value, lock, .. } = property {
+            if is_secret(config, key) && HASHED_PROPERTIES.contains(&key.as_str()) {
+                rtn.push(PropertyMod::Set {
+                    key: key.clone(),
+                    value: password::hash(value.as_str()),
+                    lock: lock.clone(),
+                });
+            }
+        }
+    }
+    rtn
+}
+
+/// true if `key` is a [`PropertySource::CoreSecret`] property in `config`
+pub fn is_secret(config: &PropertiesConfig, key: &str) -> bool {
+    config
+        .get(key)
+        .map_or(false, |def| def.source == PropertySource::CoreSecret)
+}
+
+/// the value agents see in place of a secret they may not read
+pub static REDACTED: &str = "<redacted>";
+
+/// the privilege an agent needs to read the `key` property
+pub fn read_privilege(key: &str) -> String {
+    format!("property:{}:read", key)
+}
+
+/// replace the value of every `CoreSecret` in `properties` with [`REDACTED`] unless `reveal`
+/// allows the key.  Returns the secret keys that were revealed so the read can be audited
+pub fn redact_secrets<F>(
+    config: &PropertiesConfig,
+    properties: &mut Properties,
+    reveal: F,
+) -> Vec<String>
+where
+    F: Fn(&str) -> bool,
+{
+    let mut revealed = vec![];
+    for (key, property) in properties.iter_mut() {
+        if is_secret(config, key) {
+            if reveal(key) {
+                revealed.push(key.clone());
+            } else {
+                property.value = REDACTED.to_string();
+            }
+        }
+    }
+    revealed.sort();
+    revealed
+}
+
+/// replace the value of every `CoreSecret` that `set` sets with [`REDACTED`].  Used for
+/// changes that are announced to watchers, who only need `read` permission to watch
+pub fn redact_set_secrets(config: &PropertiesConfig, set: &SetProperties) -> SetProperties {
+    let mut rtn = set.clone();
+    for (key, property) in set.iter() {
+        if let PropertyMod::Set { lock, .. } = property {
+            if is_secret(config, key) {
+                rtn.push(PropertyMod::Set {
+                    key: key.clone(),
+                    value: REDACTED.to_string(),
+                    lock: lock.clone(),
+                });
+            }
+        }
     }
+    rtn
 }
@@ -7,19 +7,51 @@ use starlane_space::command::direct::query::{Query, QueryResult};
 use starlane_space::command::direct::select::{Select, SubSelect};
 use starlane_space::hyper::{ParticleLocation, ParticleRecord};
 use starlane_space::kind::Kind;
+use starlane_space::log::Logger;
 use starlane_space::particle::{Details, Properties, Status, Stub};
 use starlane_space::point::Point;
 use starlane_space::security::{Access, AccessGrant, IndexedAccessGrant};
 use starlane_space::selector::Selector;
 use starlane_space::substance::SubstanceList;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use crate::base::config::{BaseConfig, BaseSubConfig};
 
+#[cfg(any(test, feature = "test"))]
+pub mod conformance;
 pub mod err;
+pub mod mem;
+pub mod secret;
 
 pub type Registry = Arc<dyn RegistryApi>;
 
-pub trait RegistryConfig: BaseSubConfig {}
+pub trait RegistryConfig: BaseSubConfig {
+    /// the [RegistryKind] backing store this [Registry] should be built from
+    fn kind(&self) -> &RegistryKind;
+
+    /// the base64 [secret::SecretKey] `CoreSecret` properties are sealed with.  When [None]
+    /// secrets are stored as they were set
+    fn secret_key(&self) -> Option<&String> {
+        None
+    }
+}
+
+/// enumerates the builtin [RegistryApi] implementations that a [RegistryConfig] can select
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum RegistryKind {
+    /// `PostgresRegistry` which depends upon [crate::base::provider::ProviderKind::PostgresDatabase]
+    Postgres,
+    /// an embedded `SqliteRegistry` persisted to the database file at `path`
+    Sqlite { path: String },
+    /// a volatile [mem::registry::MemoryRegistry] which is lost when the process exits
+    Memory,
+}
+
+impl Default for RegistryKind {
+    fn default() -> Self {
+        Self::Postgres
+    }
+}
 
 #[async_trait]
 pub trait RegistryApi: Send + Sync {
@@ -53,14 +85,23 @@ pub trait RegistryApi: Send + Sync {
     //    async fn select<'a>(&'a self, select: &'a mut Select) -> Result<SubstanceList, RegErr>;
 
     async fn select<'a>(&'a self, select: &'a mut Select) -> Result<SubstanceList, RegErr> {
-        let point = select.pattern.query_root();
+        let mut point = select.pattern.query_root();
+        let mut sub_select_hops = select.pattern.sub_select_hops();
+
+        // an exact selector (every hop is an exact segment) selects the particle itself,
+        // so it is sub selected from its parent
+        if sub_select_hops.is_empty() {
+            if let (Some(parent), Some(hop)) = (point.parent(), select.pattern.hops.last()) {
+                sub_select_hops.push(hop.clone());
+                point = parent;
+            }
+        }
 
         let hierarchy = self
             .query(&point, &Query::PointHierarchy)
             .await?
             .try_into()?;
 
-        let sub_select_hops = select.pattern.sub_select_hops();
         let sub_select = select
             .clone()
             .sub_select(point.clone(), sub_select_hops, hierarchy);
@@ -108,6 +149,27 @@ impl RegistryWrapper {
     pub fn new(registry: Registry) -> Self {
         Self { registry }
     }
+
+    /// wrap the platform `registry` of any [RegistryKind].  When `config` has a
+    /// [RegistryConfig::secret_key] its `CoreSecret` properties are sealed by a
+    /// [secret::SecretRegistry] before they reach `registry`
+    pub fn from_config<C>(config: &C, registry: Registry, logger: &Logger) -> Result<Self, RegErr>
+    where
+        C: RegistryConfig + ?Sized,
+    {
+        let registry: Registry = match config.secret_key() {
+            Some(key) => Arc::new(secret::SecretRegistry::new(
+                registry,
+                secret::SecretKey::from_base64(key)?,
+            )),
+            None => {
+                logger
+                    .warn("no secret_key configured: CoreSecret properties are stored unencrypted");
+                registry
+            }
+        };
+        Ok(Self::new(registry))
+    }
 }
 
 #[async_trait]
@@ -0,0 +1,530 @@
+//! A conformance suite for [`RegistryApi`] implementations.
+//!
+//! `PostgresRegistry` is the reference implementation.  A new registry backend should pass
+//! [`conformance`] before it is trusted by a `Star`:
+//!
+//! ```ignore
+//! #[tokio::test]
+//! async fn test_conformance() -> Result<(), RegErr> {
+//!     let registry: Registry = Arc::new(MyRegistry::new());
+//!     starlane_hyperspace::registry::conformance::conformance(registry).await
+//! }
+//! ```
+//!
+//! Every check starts by calling [`RegistryApi::scorch`] so the registry under test must
+//! allow scorching.
+use crate::registry::err::RegErr;
+use crate::registry::{Registration, Registry};
+use starlane_space::command::common::{PropertyMod, SetProperties};
+use starlane_space::command::direct::create::Strategy;
+use starlane_space::command::direct::delete::Delete;
+use starlane_space::command::direct::query::Query;
+use starlane_space::command::direct::select::{Select, SelectIntoSubstance, SelectKind};
+use starlane_space::kind::Kind;
+use starlane_space::particle::Status;
+use starlane_space::point::Point;
+use starlane_space::security::{AccessGrant, AccessGrantKind, PermissionsMask, Privilege};
+use starlane_space::selector::{PointHierarchy, Selector};
+use starlane_space::substance::{Substance, SubstanceList};
+use starlane_space::HYPERUSER;
+use std::str::FromStr;
+
+/// run every conformance check against `registry`
+pub async fn conformance(registry: Registry) -> Result<(), RegErr> {
+    register(registry.clone()).await?;
+    strategy(registry.clone()).await?;
+    sequence(registry.clone()).await?;
+    properties(registry.clone()).await?;
+    select(registry.clone()).await?;
+    access(registry.clone()).await?;
+    chown(registry.clone()).await?;
+    delete(registry.clone()).await?;
+    Ok(())
+}
+
+/// registration, record retrieval & duplicate detection
+pub async fn register(registry: Registry) -> Result<(), RegErr> {
+    registry.scorch().await?;
+
+    let localhost = point("localhost");
+    registry
+        .register(&registration(&localhost, Kind::Space, &HYPERUSER))
+        .await?;
+
+    let record = registry.record(&localhost).await?;
+    assert_eq!(record.details.stub.point, localhost);
+    assert_eq!(record.details.stub.kind, Kind::Space);
+    assert_eq!(record.details.stub.status, Status::Pending);
+
+    match registry
+        .register(&registration(&localhost, Kind::Space, &HYPERUSER))
+        .await
+    {
+        Err(RegErr::Dupe) => {}
+        other => panic!("expected RegErr::Dupe received: {:?}", other),
+    }
+
+    let mechtron = point("localhost:mechtron");
+    registry
+        .register(&registration(&mechtron, Kind::Mechtron, &HYPERUSER))
+        .await?;
+    registry.assign_star(&mechtron, &Point::central()).await?;
+    registry.set_status(&mechtron, &Status::Ready).await?;
+
+    let record = registry.record(&mechtron).await?;
+    assert_eq!(record.details.stub.status, Status::Ready);
+    assert_eq!(record.location.star, Some(Point::central()));
+
+    let hierarchy: PointHierarchy = registry
+        .query(&mechtron, &Query::PointHierarchy)
+        .await?
+        .try_into()?;
+    assert_eq!(hierarchy.segments.len(), 2);
+    assert_eq!(hierarchy.segments[0].kind, Kind::Space);
+    assert_eq!(hierarchy.segments[1].kind, Kind::Mechtron);
+
+    assert!(registry.record(&point("localhost:nothing")).await.is_err());
+
+    Ok(())
+}
+
+/// `Strategy::Ensure` & `Strategy::Override` tolerate an existing particle, `Strategy::Commit` does not
+pub async fn strategy(registry: Registry) -> Result<(), RegErr> {
+    registry.scorch().await?;
+
+    let localhost = point("localhost");
+    let mut ensure = registration(&localhost, Kind::Space, &HYPERUSER);
+    ensure.strategy = Strategy::Ensure;
+
+    // ensure on a particle that does not exist yet creates it
+    registry.register(&ensure).await?;
+    registry.record(&localhost).await?;
+
+    // ensure on an existing particle is not an error
+    registry.register(&ensure).await?;
+
+    let mut over = registration(&localhost, Kind::Space, &HYPERUSER);
+    over.strategy = Strategy::Override;
+    registry.register(&over).await?;
+
+    let commit = registration(&localhost, Kind::Space, &HYPERUSER);
+    assert!(registry.register(&commit).await.is_err());
+
+    assert_eq!(select_points(&registry, "**").await?.len(), 1);
+
+    Ok(())
+}
+
+/// sequences are counted per particle starting at `1`
+pub async fn sequence(registry: Registry) -> Result<(), RegErr> {
+    registry.scorch().await?;
+
+    let localhost = point("localhost");
+    let other = point("other");
+    registry
+        .register(&registration(&localhost, Kind::Space, &HYPERUSER))
+        .await?;
+    registry
+        .register(&registration(&other, Kind::Space, &HYPERUSER))
+        .await?;
+
+    assert_eq!(registry.sequence(&localhost).await?, 1);
+    assert_eq!(registry.sequence(&localhost).await?, 2);
+    assert_eq!(registry.sequence(&other).await?, 1);
+    assert_eq!(registry.sequence(&localhost).await?, 3);
+
+    Ok(())
+}
+
+/// properties can be set and unset unless they are locked
+pub async fn properties(registry: Registry) -> Result<(), RegErr> {
+    registry.scorch().await?;
+
+    let localhost = point("localhost");
+    let mut properties = SetProperties::new();
+    properties.push(set("color", "blue", false));
+    properties.push(set("name", "localhost", true));
+    let mut registration = registration(&localhost, Kind::Space, &HYPERUSER);
+    registration.properties = properties;
+    registry.register(&registration).await?;
+
+    let properties = registry.get_properties(&localhost).await?;
+    assert_eq!(properties.get("color").unwrap().value, "blue".to_string());
+    assert!(!properties.get("color").unwrap().locked);
+    assert_eq!(properties.get("name").unwrap().value, "localhost".to_string());
+    assert!(properties.get("name").unwrap().locked);
+
+    let mut properties = SetProperties::new();
+    properties.push(set("color", "red", false));
+    properties.push(set("name", "remotehost", false));
+    properties.push(set("size", "large", false));
+    registry.set_properties(&localhost, &properties).await?;
+
+    let properties = registry.get_properties(&localhost).await?;
+    assert_eq!(properties.get("color").unwrap().value, "red".to_string());
+    assert_eq!(properties.get("size").unwrap().value, "large".to_string());
+    // a locked property cannot be changed
+    assert_eq!(properties.get("name").unwrap().value, "localhost".to_string());
+
+    let mut properties = SetProperties::new();
+    properties.push(PropertyMod::UnSet("color".to_string()));
+    properties.push(PropertyMod::UnSet("name".to_string()));
+    registry.set_properties(&localhost, &properties).await?;
+
+    let properties = registry.record(&localhost).await?.details.properties;
+    assert!(properties.get("color").is_none());
+    assert_eq!(properties.get("size").unwrap().value, "large".to_string());
+    // ... nor can it be removed
+    assert_eq!(properties.get("name").unwrap().value, "localhost".to_string());
+
+    Ok(())
+}
+
+/// select with exact, wildcard and recursive `**` selectors
+pub async fn select(registry: Registry) -> Result<(), RegErr> {
+    registry.scorch().await?;
+    tree(&registry).await?;
+
+    // everything but the root
+    assert_eq!(select_points(&registry, "**").await?.len(), 10);
+    // the root is included by an inclusive selector
+    assert_eq!(select_points(&registry, "+**").await?.len(), 11);
+
+    assert_eq!(
+        select_points(&registry, "localhost:app").await?,
+        vec![point("localhost:app")]
+    );
+
+    let mut children = select_points(&registry, "localhost:app:*").await?;
+    children.sort_by_key(|point| point.to_string());
+    assert_eq!(
+        children,
+        vec![point("localhost:app:mechtron"), point("localhost:app:users")]
+    );
+
+    assert_eq!(select_points(&registry, "localhost:**").await?.len(), 6);
+    assert_eq!(select_points(&registry, "localhost+:**").await?.len(), 7);
+
+    // recursive selectors traverse particles of any kind to reach the ones selected by kind
+    assert_eq!(
+        select_points(&registry, "localhost:app:**<User>").await?,
+        vec![point("localhost:app:users:scott")]
+    );
+    assert_eq!(
+        select_points(&registry, "**<Mechtron>").await?,
+        vec![point("localhost:app:mechtron")]
+    );
+    assert!(select_points(&registry, "localhost:nothing:**")
+        .await
+        .is_err_or_empty());
+
+    // select can also return stubs
+    let mut select = Select {
+        pattern: Selector::from_str("localhost:app:users:*")?,
+        properties: Default::default(),
+        into_substance: SelectIntoSubstance::Stubs,
+        kind: SelectKind::Initial,
+    };
+    let list = registry.select(&mut select).await?;
+    assert_eq!(list.len(), 1);
+    match &**list.first().unwrap() {
+        Substance::Stub(stub) => {
+            assert_eq!(stub.point, point("localhost:app:users:scott"));
+            assert_eq!(stub.kind, Kind::User);
+        }
+        other => panic!("expected a Stub received: {:?}", other),
+    }
+
+    Ok(())
+}
+
+/// access grants, ownership & permission masks
+pub async fn access(registry: Registry) -> Result<(), RegErr> {
+    registry.scorch().await?;
+    tree(&registry).await?;
+
+    let hyperuser = (*HYPERUSER).clone();
+    let superuser = point("localhost:users:superuser");
+    let scott = point("localhost:app:users:scott");
+    let app = point("localhost:app");
+    let mechtron = point("localhost:app:mechtron");
+    let localhost = point("localhost");
+
+    registry
+        .grant(&AccessGrant {
+            kind: AccessGrantKind::Super,
+            on_point: Selector::from_str("localhost+:**")?,
+            to_point: superuser.clone().into(),
+            by_particle: hyperuser.clone(),
+        })
+        .await?;
+
+    registry
+        .grant(&AccessGrant {
+            kind: AccessGrantKind::PermissionsMask(PermissionsMask::from_str("+csd-Rwx")?),
+            on_point: Selector::from_str("localhost:app+:**")?,
+            to_point: Selector::from_str("localhost:app:users:**<User>")?,
+            by_particle: app.clone(),
+        })
+        .await?;
+
+    registry
+        .grant(&AccessGrant {
+            kind: AccessGrantKind::PermissionsMask(PermissionsMask::from_str("+csd-rwX")?),
+            on_point: Selector::from_str("localhost:app:**<Mechtron>")?,
+            to_point: Selector::from_str("localhost:app:users:**<User>")?,
+            by_particle: app.clone(),
+        })
+        .await?;
+
+    registry
+        .grant(&AccessGrant {
+            kind: AccessGrantKind::PermissionsMask(PermissionsMask::from_str("+CSD-RWX")?),
+            on_point: Selector::from_str("localhost:users:superuser")?,
+            to_point: scott.clone().into(),
+            by_particle: app.clone(),
+        })
+        .await?;
+
+    registry
+        .grant(&AccessGrant {
+            kind: AccessGrantKind::Privilege(Privilege::Single("property:email:read".to_string())),
+            on_point: Selector::from_str("localhost:app:users:**<User>")?,
+            to_point: Selector::from_str("localhost:app:**<Mechtron>")?,
+            by_particle: app.clone(),
+        })
+        .await?;
+
+    let access = registry.access(&hyperuser, &superuser).await?;
+    assert!(access.has_super());
+
+    let access = registry.access(&superuser, &localhost).await?;
+    assert!(access.has_super());
+    let access = registry.access(&superuser, &app).await?;
+    assert!(access.has_super());
+
+    let access = registry.access(&app, &scott).await?;
+    assert!(!access.has_super());
+    assert!(access.has_owner());
+    assert!(access.has_full());
+
+    // app is not the owner of superuser so its grant has no effect
+    let access = registry.access(&scott, &superuser).await?;
+    assert!(!access.has_super());
+    assert!(!access.has_full());
+    assert_eq!(access.permissions().to_string(), "csd-rwx".to_string());
+
+    // app does not own itself yet so the grants it made are not honored
+    let access = registry.access(&scott, &app).await?;
+    assert!(!access.has_super());
+    assert_eq!(access.permissions().to_string(), "csd-rwx".to_string());
+
+    let app_pattern = Selector::from_str("localhost:app+:**")?;
+    registry.chown(&app_pattern, &app, &superuser).await?;
+
+    // now the grants work since app owns itself
+    let access = registry.access(&scott, &app).await?;
+    assert!(!access.has_super());
+    assert_eq!(access.permissions().to_string(), "csd-Rwx".to_string());
+
+    // OR permission masks accumulate
+    let access = registry.access(&scott, &mechtron).await?;
+    assert!(!access.has_super());
+    assert_eq!(access.permissions().to_string(), "csd-RwX".to_string());
+
+    // AND permission masks remove permissions
+    registry
+        .grant(&AccessGrant {
+            kind: AccessGrantKind::PermissionsMask(PermissionsMask::from_str("&csd-rwX")?),
+            on_point: Selector::from_str("localhost:app:**<Mechtron>")?,
+            to_point: Selector::from_str("localhost:app:users:**<User>")?,
+            by_particle: app.clone(),
+        })
+        .await?;
+
+    let access = registry.access(&scott, &mechtron).await?;
+    assert!(!access.has_super());
+    assert_eq!(access.permissions().to_string(), "csd-rwX".to_string());
+
+    let access = registry.access(&mechtron, &scott).await?;
+    assert!(!access.has_super());
+    assert_eq!(access.permissions().to_string(), "csd-rwx".to_string());
+    assert!(access.check_privilege("property:email:read").is_ok());
+    assert!(access.check_privilege("property:email:write").is_err());
+
+    let grants = registry
+        .list_access(&None, &Selector::from_str("+**")?)
+        .await?;
+    assert_eq!(grants.len(), 6);
+
+    let grants = registry
+        .list_access(&Some(&scott), &Selector::from_str("+**")?)
+        .await?;
+    assert_eq!(grants.len(), 4);
+
+    // only an agent with full access on the granting particle may remove its grants
+    let grants = registry
+        .list_access(&Some(&mechtron), &Selector::from_str("+**")?)
+        .await?;
+    assert_eq!(grants.len(), 1);
+    let id = grants.first().unwrap().id;
+    assert!(registry.remove_access(id, &scott).await.is_err());
+    registry.remove_access(id, &app).await?;
+
+    let access = registry.access(&mechtron, &scott).await?;
+    assert!(access.check_privilege("property:email:read").is_err());
+
+    Ok(())
+}
+
+/// only a super may change ownership
+pub async fn chown(registry: Registry) -> Result<(), RegErr> {
+    registry.scorch().await?;
+    tree(&registry).await?;
+
+    let superuser = point("localhost:users:superuser");
+    let scott = point("localhost:app:users:scott");
+    let app = point("localhost:app");
+
+    registry
+        .grant(&AccessGrant {
+            kind: AccessGrantKind::Super,
+            on_point: Selector::from_str("localhost+:**")?,
+            to_point: superuser.clone().into(),
+            by_particle: HYPERUSER.clone(),
+        })
+        .await?;
+
+    let app_pattern = Selector::from_str("localhost:app+:**")?;
+    assert!(registry.chown(&app_pattern, &scott, &scott).await.is_err());
+    assert!(!registry.access(&scott, &app).await?.has_owner());
+
+    registry.chown(&app_pattern, &scott, &superuser).await?;
+    assert!(registry.access(&scott, &app).await?.has_owner());
+    assert!(registry
+        .access(&scott, &point("localhost:app:mechtron"))
+        .await?
+        .has_owner());
+    assert!(!registry.access(&scott, &point("localhost")).await?.has_owner());
+
+    Ok(())
+}
+
+/// delete removes every selected particle and returns their points
+pub async fn delete(registry: Registry) -> Result<(), RegErr> {
+    registry.scorch().await?;
+    tree(&registry).await?;
+
+    let mechtron = point("localhost:app:mechtron");
+    let delete = Delete {
+        selector: mechtron.clone().into(),
+    };
+    let list = registry.delete(&delete).await?;
+    assert_eq!(list.len(), 1);
+    assert!(registry.record(&mechtron).await.is_err());
+    assert_eq!(select_points(&registry, "**").await?.len(), 9);
+
+    let delete = Delete {
+        selector: Selector::from_str("localhost:app:**")?,
+    };
+    let list = registry.delete(&delete).await?;
+    assert_eq!(list.len(), 2);
+    assert!(registry.record(&point("localhost:app:users")).await.is_err());
+    assert!(registry.record(&point("localhost:app")).await.is_ok());
+    assert_eq!(select_points(&registry, "**").await?.len(), 7);
+
+    Ok(())
+}
+
+/// registers the particle tree the conformance checks operate on:
+///
+/// ```text
+/// hyperspace
+/// hyperspace:users
+/// hyperspace:users:hyperuser
+/// localhost
+/// localhost:users
+/// localhost:users:superuser
+/// localhost:app                      (owned by localhost:users:superuser)
+/// localhost:app:users                (owned by localhost:app)
+/// localhost:app:users:scott          (owned by localhost:app)
+/// localhost:app:mechtron             (owned by localhost:app)
+/// ```
+pub async fn tree(registry: &Registry) -> Result<(), RegErr> {
+    let hyperuser = (*HYPERUSER).clone();
+    let superuser = point("localhost:users:superuser");
+    let app = point("localhost:app");
+
+    let userbase = Kind::Base;
+
+    let particles = vec![
+        (point("hyperspace"), Kind::Space, hyperuser.clone()),
+        (point("hyperspace:users"), userbase.clone(), hyperuser.clone()),
+        (hyperuser.clone(), Kind::User, hyperuser.clone()),
+        (point("localhost"), Kind::Space, hyperuser.clone()),
+        (point("localhost:users"), userbase.clone(), hyperuser.clone()),
+        (superuser.clone(), Kind::User, hyperuser.clone()),
+        (app.clone(), Kind::App, superuser.clone()),
+        (point("localhost:app:users"), userbase.clone(), app.clone()),
+        (point("localhost:app:users:scott"), Kind::User, app.clone()),
+        (point("localhost:app:mechtron"), Kind::Mechtron, app.clone()),
+    ];
+
+    for (point, kind, owner) in particles {
+        registry.register(&registration(&point, kind, &owner)).await?;
+    }
+
+    Ok(())
+}
+
+pub fn registration(point: &Point, kind: Kind, owner: &Point) -> Registration {
+    Registration {
+        point: point.clone(),
+        kind,
+        registry: Default::default(),
+        properties: Default::default(),
+        owner: owner.clone(),
+        strategy: Strategy::Commit,
+        status: Status::Unknown,
+    }
+}
+
+async fn select_points(registry: &Registry, selector: &str) -> Result<Vec<Point>, RegErr> {
+    let mut select = Select {
+        pattern: Selector::from_str(selector)?,
+        properties: Default::default(),
+        into_substance: SelectIntoSubstance::Points,
+        kind: SelectKind::Initial,
+    };
+    let list: SubstanceList = registry.select(&mut select).await?;
+    let mut points = vec![];
+    for substance in list.list {
+        points.push((*substance).try_into()?);
+    }
+    Ok(points)
+}
+
+fn set(key: &str, value: &str, lock: bool) -> PropertyMod {
+    PropertyMod::Set {
+        key: key.to_string(),
+        value: value.to_string(),
+        lock,
+    }
+}
+
+fn point(point: &str) -> Point {
+    Point::from_str(point).expect("point")
+}
+
+trait ErrOrEmpty {
+    fn is_err_or_empty(&self) -> bool;
+}
+
+impl ErrOrEmpty for Result<Vec<Point>, RegErr> {
+    fn is_err_or_empty(&self) -> bool {
+        match self {
+            Ok(points) => points.is_empty(),
+            Err(_) => true,
+        }
+    }
+}
@@ -28,7 +28,7 @@ pub enum RegErr {
     #[error("{0}")]
     Msg(String),
 
-    #[error("postgres error: {0}")]
+    #[error("sql error: {0}")]
     SqlxErr(#[from] Arc<sqlx::Error>),
 
     #[error("postgres registry db connection pool '{0}' not found")]
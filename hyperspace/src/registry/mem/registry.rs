@@ -1,35 +1,59 @@
-use crate::reg::{Registration, RegistryApi};
 use crate::registry::err::RegErr;
+use crate::registry::{Registration, RegistryApi};
+use async_trait::async_trait;
+use dashmap::DashMap;
 use starlane_space::command::common::{PropertyMod, SetProperties};
+use starlane_space::command::direct::create::Strategy;
 use starlane_space::command::direct::delete::Delete;
 use starlane_space::command::direct::query::{Query, QueryResult};
-use starlane_space::command::direct::select::SubSelect;
+use starlane_space::command::direct::select::{Select, SelectIntoSubstance, SelectKind, SubSelect};
 use starlane_space::hyper::{ParticleLocation, ParticleRecord};
 use starlane_space::particle::{Details, Properties, Property, Status, Stub};
 use starlane_space::point::Point;
-use starlane_space::security::{Access, AccessGrant, IndexedAccessGrant};
-use starlane_space::selector::Selector;
-use starlane_space::substance::SubstanceList;
-use async_trait::async_trait;
-use dashmap::DashMap;
-use std::sync::atomic::AtomicU64;
+use starlane_space::security::{
+    Access, AccessGrant, AccessGrantKind, EnumeratedAccess, IndexedAccessGrant, Permissions,
+    PermissionsMask, PermissionsMaskKind, Privileges,
+};
+use starlane_space::selector::{PointHierarchy, PointKindSeg, Selector};
+use starlane_space::substance::{Substance, SubstanceList};
+use starlane_space::util::ValueMatcher;
+use starlane_space::HYPERUSER;
+use std::collections::HashMap;
+use std::sync::atomic::AtomicI32;
 use std::sync::{atomic, Arc};
 
 impl MemoryRegistryCtx {
     pub fn new() -> Self {
         Self {
-            sequence: Arc::new(AtomicU64::new(0u64)),
+            sequences: Arc::new(DashMap::new()),
             particles: Arc::new(DashMap::new()),
             properties: Arc::new(DashMap::new()),
+            owners: Arc::new(DashMap::new()),
+            access_grants: Arc::new(DashMap::new()),
+            access_grant_id: Arc::new(AtomicI32::new(1)),
         }
     }
+
+    /// remove every particle, property and access grant
+    pub fn clear(&self) {
+        self.sequences.clear();
+        self.particles.clear();
+        self.properties.clear();
+        self.owners.clear();
+        self.access_grants.clear();
+        self.access_grant_id.store(1, atomic::Ordering::Relaxed);
+    }
 }
 
 #[derive(Clone)]
 pub struct MemoryRegistryCtx {
-    pub sequence: Arc<AtomicU64>,
+    pub sequences: Arc<DashMap<Point, u64>>,
     pub particles: Arc<DashMap<Point, ParticleRecord>>,
     pub properties: Arc<DashMap<Point, Properties>>,
+    pub owners: Arc<DashMap<Point, Point>>,
+    /// the in memory equivalent of the `access_grants` table, keyed by grant id
+    pub access_grants: Arc<DashMap<i32, IndexedAccessGrant>>,
+    pub access_grant_id: Arc<AtomicI32>,
 }
 
 pub struct MemoryRegistry {
@@ -45,17 +69,77 @@ impl MemoryRegistry {
     fn ctx(&self) -> &MemoryRegistryCtx {
         &self.ctx
     }
+
+    /// every registered particle whose parent is `point`
+    fn children(&self, point: &Point) -> Vec<Stub> {
+        self.ctx
+            .particles
+            .iter()
+            .filter(|record| record.key().parent().as_ref() == Some(point))
+            .map(|record| record.value().details.stub.clone())
+            .collect()
+    }
+
+    /// access grants indexed under `query_root` (the same lookup the postgres registry
+    /// performs against the `access_grants.query_root` column)
+    fn access_grants_for(&self, query_root: &Point) -> Vec<IndexedAccessGrant> {
+        self.ctx
+            .access_grants
+            .iter()
+            .filter(|grant| grant.on_point.query_root() == *query_root)
+            .map(|grant| grant.value().clone())
+            .collect()
+    }
+
+    fn apply_properties(properties: &mut Properties, mods: &SetProperties) {
+        for (_, property_mod) in mods.iter() {
+            match property_mod {
+                PropertyMod::Set { key, value, lock } => {
+                    if let Some(existing) = properties.get(key) {
+                        if existing.locked {
+                            continue;
+                        }
+                    }
+                    let property = Property {
+                        key: key.clone(),
+                        value: value.clone(),
+                        locked: lock.clone(),
+                    };
+                    properties.insert(key.clone(), property);
+                }
+                PropertyMod::UnSet(key) => {
+                    if let Some(existing) = properties.get(key) {
+                        if !existing.locked {
+                            properties.remove(key);
+                        }
+                    }
+                }
+            }
+        }
+    }
 }
 
 #[async_trait]
 impl RegistryApi for MemoryRegistry {
     async fn scorch<'a>(&'a self) -> Result<(), RegErr> {
+        self.ctx.clear();
         Ok(())
     }
 
     async fn register<'a>(&'a self, registration: &'a Registration) -> Result<(), RegErr> {
-        self.set_properties(&registration.point, &registration.properties)
-            .await?;
+        if self.ctx.particles.contains_key(&registration.point) {
+            // mirrors `PostgresRegistry`: Override is accepted but does not (yet) update the record
+            return if registration.strategy == Strategy::Ensure
+                || registration.strategy == Strategy::Override
+            {
+                Ok(())
+            } else {
+                Err(RegErr::dupe())
+            };
+        }
+
+        let mut properties = Properties::new();
+        Self::apply_properties(&mut properties, &registration.properties);
 
         let details = Details {
             stub: Stub {
@@ -63,12 +147,18 @@ impl RegistryApi for MemoryRegistry {
                 kind: registration.kind.clone(),
                 status: Status::Pending,
             },
-            properties: self.get_properties(&registration.point).await?,
+            properties: properties.clone(),
         };
         let record = ParticleRecord {
-            details: details.clone(),
+            details,
             location: ParticleLocation::default(),
         };
+        self.ctx
+            .properties
+            .insert(registration.point.clone(), properties);
+        self.ctx
+            .owners
+            .insert(registration.point.clone(), registration.owner.clone());
         self.ctx
             .particles
             .insert(registration.point.clone(), record);
@@ -76,13 +166,21 @@ impl RegistryApi for MemoryRegistry {
     }
 
     async fn assign_star<'a>(&'a self, point: &'a Point, star: &'a Point) -> Result<(), RegErr> {
-        let mut record = self.ctx.particles.get_mut(&point).unwrap();
+        let mut record = self
+            .ctx
+            .particles
+            .get_mut(point)
+            .ok_or(RegErr::NotFound(point.clone()))?;
         record.value_mut().location.star = Some(star.clone());
         Ok(())
     }
 
     async fn assign_host<'a>(&'a self, point: &'a Point, host: &'a Point) -> Result<(), RegErr> {
-        let mut record = self.ctx.particles.get_mut(&point).unwrap();
+        let mut record = self
+            .ctx
+            .particles
+            .get_mut(point)
+            .ok_or(RegErr::NotFound(point.clone()))?;
         record.value_mut().location.host = Some(host.clone());
         Ok(())
     }
@@ -102,26 +200,21 @@ impl RegistryApi for MemoryRegistry {
         point: &'a Point,
         properties: &'a SetProperties,
     ) -> Result<(), RegErr> {
-        let mut rtn = Properties::new();
-        for (id, property) in properties.iter() {
-            match property {
-                PropertyMod::Set { key, value, lock } => {
-                    let property = Property {
-                        key: key.clone(),
-                        value: value.clone(),
-                        locked: lock.clone(),
-                    };
-                    rtn.insert(id.clone(), property);
-                }
-                PropertyMod::UnSet(_) => {}
-            }
+        if !self.ctx.particles.contains_key(point) {
+            return Err(RegErr::NotFound(point.clone()));
         }
-        self.ctx.properties.insert(point.clone(), rtn);
+        let mut current = self.ctx.properties.entry(point.clone()).or_default();
+        Self::apply_properties(current.value_mut(), properties);
         Ok(())
     }
 
     async fn sequence<'a>(&'a self, point: &'a Point) -> Result<u64, RegErr> {
-        Ok(self.ctx.sequence.fetch_add(1, atomic::Ordering::Relaxed))
+        if !self.ctx.particles.contains_key(point) {
+            return Err(RegErr::NotFound(point.clone()));
+        }
+        let mut sequence = self.ctx.sequences.entry(point.clone()).or_insert(0u64);
+        *sequence.value_mut() += 1;
+        Ok(*sequence.value())
     }
 
     async fn get_properties<'a>(&'a self, point: &'a Point) -> Result<Properties, RegErr> {
@@ -132,11 +225,15 @@ impl RegistryApi for MemoryRegistry {
     }
 
     async fn record<'a>(&'a self, point: &'a Point) -> Result<ParticleRecord, RegErr> {
+        if point.is_local_root() {
+            return Ok(ParticleRecord::root());
+        }
+
         let properties = self.get_properties(point).await?;
         let mut record = self
             .ctx
             .particles
-            .get(&point)
+            .get(point)
             .ok_or(RegErr::NotFound(point.clone()))?
             .value()
             .clone();
@@ -149,23 +246,231 @@ impl RegistryApi for MemoryRegistry {
         point: &'a Point,
         query: &'a Query,
     ) -> Result<QueryResult, RegErr> {
-        todo!()
+        match query {
+            Query::PointHierarchy => {
+                let mut hierarchy = PointHierarchy::new(point.route.clone(), vec![]);
+                let mut segments = vec![];
+                for segment in &point.segments {
+                    segments.push(segment.clone());
+                    let point = Point {
+                        route: point.route.clone(),
+                        segments: segments.clone(),
+                    };
+                    let record = self.record(&point).await?;
+                    hierarchy = hierarchy.push(PointKindSeg {
+                        segment: segment.clone(),
+                        kind: record.details.stub.kind,
+                    });
+                }
+                Ok(QueryResult::PointHierarchy(hierarchy))
+            }
+        }
     }
 
     async fn delete<'a>(&'a self, delete: &'a Delete) -> Result<SubstanceList, RegErr> {
-        todo!()
+        let mut select = delete.clone().into();
+        let list = self.select(&mut select).await?;
+        for point in list.iter() {
+            if let Substance::Point(point) = &**point {
+                self.ctx.particles.remove(point);
+                self.ctx.properties.remove(point);
+                self.ctx.sequences.remove(point);
+                self.ctx.owners.remove(point);
+                // grants reference the granting particle (`by_particle`) so they cannot outlive it
+                self.ctx
+                    .access_grants
+                    .retain(|_, grant| grant.by_particle != *point);
+            }
+        }
+        Ok(list)
     }
 
     async fn sub_select<'a>(&'a self, sub_select: &'a SubSelect) -> Result<Vec<Stub>, RegErr> {
-        todo!()
+        // find every child that matches the present hop.  These matches are used to query
+        // children for additional matches if there are more hops. All of these matches are
+        // filtered to see if they match the ENTIRE select before returning results.
+        let hop = match sub_select.hops.first() {
+            None => return Ok(vec![]),
+            Some(hop) => hop.clone(),
+        };
+
+        let mut matching_so_far: Vec<Stub> = self
+            .children(&sub_select.point)
+            .into_iter()
+            .filter(|stub| {
+                let segment = match stub.point.last_segment() {
+                    None => return false,
+                    Some(segment) => segment,
+                };
+                if hop.segment_selector.is_recursive() {
+                    // intermediate particles of a recursive hop may be of any kind
+                    hop.segment_selector.is_match(&segment)
+                } else {
+                    hop.is_match(&PointKindSeg {
+                        segment,
+                        kind: stub.kind.clone(),
+                    })
+                    .is_ok()
+                }
+            })
+            .collect();
+
+        let mut hops = sub_select.hops.clone();
+        if !hop.segment_selector.is_recursive() {
+            hops.remove(0);
+        }
+
+        let mut child_stub_matches = vec![];
+        for stub in &matching_so_far {
+            if let Option::Some(last_segment) = stub.point.last_segment() {
+                let point = sub_select.point.push_segment(last_segment.clone())?;
+                let hierarchy = sub_select.hierarchy.push(PointKindSeg {
+                    segment: last_segment,
+                    kind: stub.kind.clone(),
+                });
+                let sub_select = sub_select.sub_select(point, hops.clone(), hierarchy);
+                let mut more_stubs = self.sub_select(&sub_select).await?;
+                child_stub_matches.append(&mut more_stubs);
+            }
+        }
+
+        // the records matched the present hop (which we needed for deeper searches) however
+        // they may or may not match the ENTIRE select pattern therefore they must be filtered
+        matching_so_far.retain(|stub| {
+            let hierarchy = sub_select.hierarchy.push(PointKindSeg {
+                segment: stub
+                    .point
+                    .last_segment()
+                    .expect("expecting at least one segment"),
+                kind: stub.kind.clone(),
+            });
+            sub_select.pattern.matches_found(&hierarchy)
+        });
+
+        matching_so_far.append(&mut child_stub_matches);
+
+        Ok(matching_so_far)
     }
 
     async fn grant<'a>(&'a self, access_grant: &'a AccessGrant) -> Result<(), RegErr> {
-        todo!()
+        if !self.ctx.particles.contains_key(&access_grant.by_particle) {
+            return Err(RegErr::NotFound(access_grant.by_particle.clone()));
+        }
+        let id = self
+            .ctx
+            .access_grant_id
+            .fetch_add(1, atomic::Ordering::Relaxed);
+        let access_grant = IndexedAccessGrant {
+            id,
+            access_grant: access_grant.clone(),
+        };
+        self.ctx.access_grants.insert(id, access_grant);
+        Ok(())
     }
 
     async fn access<'a>(&'a self, to: &'a Point, on: &'a Point) -> Result<Access, RegErr> {
-        Ok(Access::Super)
+        //if 'to' owns 'on' then grant Owner access
+        let has_owner = match self.ctx.owners.get(on) {
+            None => false,
+            Some(owner) => *owner.value() == *to,
+        };
+
+        if *HYPERUSER == *to {
+            if has_owner {
+                return Ok(Access::Super);
+            } else {
+                return Ok(Access::SuperOwner);
+            }
+        }
+
+        if *to == *on && has_owner {
+            return Ok(Access::Owner);
+        }
+
+        let to_kind_path: PointHierarchy =
+            self.query(&to, &Query::PointHierarchy).await?.try_into()?;
+        let on_kind_path: PointHierarchy =
+            self.query(&on, &Query::PointHierarchy).await?.try_into()?;
+
+        let mut traversal = on.clone();
+        let mut privileges = Privileges::none();
+        let mut permissions = Permissions::none();
+        let mut level_ands: Vec<Vec<PermissionsMask>> = vec![];
+        loop {
+            let mut access_grants: Vec<AccessGrant> = self
+                .access_grants_for(&traversal)
+                .into_iter()
+                .map(|a| a.into())
+                .collect();
+            access_grants.retain(|access_grant| {
+                access_grant.to_point.matches_found(&to_kind_path)
+                    && access_grant.on_point.matches_found(&on_kind_path)
+            });
+            // check for any superusers
+            for access_grant in &access_grants {
+                let by_access = self.access(&access_grant.by_particle, &on).await?;
+                match &access_grant.kind {
+                    AccessGrantKind::Super => {
+                        if by_access.has_super() {
+                            if has_owner {
+                                return Ok(Access::SuperOwner);
+                            } else {
+                                return Ok(Access::Super);
+                            }
+                        }
+                    }
+                    AccessGrantKind::Privilege(privilege) => {
+                        if by_access.has_full() {
+                            privileges = privileges | privilege;
+                        }
+                    }
+                    AccessGrantKind::PermissionsMask(mask) => {
+                        if by_access.has_full() {
+                            if let PermissionsMaskKind::Or = mask.kind {
+                                permissions.or(&mask.permissions);
+                            }
+                        }
+                    }
+                }
+            }
+            let ands: Vec<PermissionsMask> = access_grants
+                .into_iter()
+                .filter_map(|a| match a.kind {
+                    AccessGrantKind::PermissionsMask(mask) => match mask.kind {
+                        PermissionsMaskKind::And => Some(mask),
+                        PermissionsMaskKind::Or => None,
+                    },
+                    _ => None,
+                })
+                .collect();
+            // save for later when we traverse back down
+            level_ands.push(ands);
+
+            // now reduce the segments of the traversal or break if it's root
+            if traversal.is_root() {
+                break;
+            } else {
+                traversal.segments.pop();
+            }
+        }
+
+        if has_owner {
+            return Ok(Access::Owner);
+        }
+
+        level_ands.reverse();
+        for level in level_ands {
+            for mask in level {
+                permissions.and(&mask.permissions);
+            }
+        }
+
+        let access = EnumeratedAccess {
+            privileges,
+            permissions,
+        };
+
+        Ok(Access::Enumerated(access))
     }
 
     async fn chown<'a>(
@@ -174,7 +479,30 @@ impl RegistryApi for MemoryRegistry {
         owner: &'a Point,
         by: &'a Point,
     ) -> Result<(), RegErr> {
-        todo!()
+        let mut select = Select {
+            pattern: on.clone(),
+            properties: Default::default(),
+            into_substance: SelectIntoSubstance::Points,
+            kind: SelectKind::Initial,
+        };
+
+        let selection = self.select(&mut select).await?;
+        let mut points = vec![];
+        for on in selection.list {
+            let on: Point = (*on).try_into()?;
+            let access = self.access(by, &on).await?;
+
+            if !access.has_super() {
+                return Err("only a super can change owners".into());
+            }
+            points.push(on);
+        }
+
+        // all ownership changes are applied only after every one of them has been authorized
+        for on in points {
+            self.ctx.owners.insert(on, owner.clone());
+        }
+        Ok(())
     }
 
     async fn list_access<'a>(
@@ -182,10 +510,67 @@ impl RegistryApi for MemoryRegistry {
         to: &'a Option<&'a Point>,
         on: &'a Selector,
     ) -> Result<Vec<IndexedAccessGrant>, RegErr> {
-        todo!()
+        let mut select = Select {
+            pattern: on.clone(),
+            properties: Default::default(),
+            into_substance: SelectIntoSubstance::Points,
+            kind: SelectKind::Initial,
+        };
+
+        let to: Option<PointHierarchy> = match to {
+            None => None,
+            Some(to) => Some(self.query(*to, &Query::PointHierarchy).await?.try_into()?),
+        };
+
+        let selection = self.select(&mut select).await?;
+        let mut all_access_grants = HashMap::new();
+        for on in selection.list {
+            let on: Point = (*on).try_into()?;
+            let mut access_grants = self.access_grants_for(&on);
+            access_grants.retain(|a| match to.as_ref() {
+                None => true,
+                Some(to) => a.to_point.matches_found(to),
+            });
+            for access_grant in access_grants {
+                all_access_grants.insert(access_grant.id.clone(), access_grant);
+            }
+        }
+        let mut all_access_grants: Vec<IndexedAccessGrant> =
+            all_access_grants.into_values().collect();
+
+        all_access_grants.sort();
+
+        Ok(all_access_grants)
     }
 
     async fn remove_access<'a>(&'a self, id: i32, to: &'a Point) -> Result<(), RegErr> {
-        todo!()
+        let access_grant = self
+            .ctx
+            .access_grants
+            .get(&id)
+            .map(|grant| grant.value().clone())
+            .ok_or(RegErr::Msg(format!("access grant {} not found", id)))?;
+        let access = self.access(to, &access_grant.by_particle).await?;
+        if access.has_full() {
+            self.ctx.access_grants.remove(&id);
+            Ok(())
+        } else {
+            Err(RegErr::Msg(format!("'{}' could not revoked grant {} because it does not have full access (super or owner) on {}", to.to_string(), id, access_grant.by_particle.to_string()).to_string()))
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use crate::registry::conformance;
+    use crate::registry::err::RegErr;
+    use crate::registry::mem::registry::MemoryRegistry;
+    use crate::registry::Registry;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    pub async fn test_conformance() -> Result<(), RegErr> {
+        let registry: Registry = Arc::new(MemoryRegistry::new());
+        conformance::conformance(registry).await
     }
 }
@@ -0,0 +1,341 @@
+//! Envelope encryption of `CoreSecret` properties.
+//!
+//! A [`SecretRegistry`] wraps another [`Registry`] and seals the value of every
+//! [`PropertySource::CoreSecret`] property before it reaches the backing store.  Each value
+//! is encrypted with its own random data key and that data key is in turn encrypted with the
+//! platform's [`SecretKey`], so the backing store (a postgres `properties` table, a sqlite
+//! file or a [`super::mem::registry::MemoryRegistry`]) never holds a secret in plain text.
+//!
+//! Sealed values are opened again by [`RegistryApi::get_properties`] &
+//! [`RegistryApi::record`] so the rest of the star (for example
+//! [`crate::hyperlane::CredentialHyperAuthenticator`]) sees the value that was set.  Keeping
+//! secrets from agents is the job of the command layer which redacts them, see
+//! [`crate::properties::redact_secrets`]
+use crate::properties::{is_secret, properties_config};
+use crate::registry::err::RegErr;
+use crate::registry::{Registration, Registry, RegistryApi};
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use async_trait::async_trait;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use starlane_space::command::common::{PropertyMod, SetProperties};
+use starlane_space::command::direct::delete::Delete;
+use starlane_space::command::direct::query::{Query, QueryResult};
+use starlane_space::command::direct::select::{Select, SubSelect};
+use starlane_space::hyper::ParticleRecord;
+use starlane_space::kind::Kind;
+use starlane_space::particle::property::PropertySource;
+use starlane_space::particle::{Properties, Status, Stub};
+use starlane_space::point::Point;
+use starlane_space::security::{Access, AccessGrant, IndexedAccessGrant};
+use starlane_space::selector::Selector;
+use starlane_space::substance::SubstanceList;
+use std::fmt::{Debug, Formatter};
+
+static SEALED: &str = "sealed:v1$";
+
+/// the platform's 256 bit key encryption key.  It is supplied by
+/// [`crate::registry::RegistryConfig::secret_key`] as base64
+#[derive(Clone)]
+pub struct SecretKey(Key<Aes256Gcm>);
+
+impl SecretKey {
+    pub fn generate() -> Self {
+        Self(Aes256Gcm::generate_key(OsRng))
+    }
+
+    pub fn from_base64(key: &str) -> Result<Self, RegErr> {
+        let key = STANDARD
+            .decode(key.trim())
+            .map_err(|e| RegErr::Msg(format!("secret key is not valid base64: {}", e)))?;
+        if key.len() != 32 {
+            return Err(RegErr::Msg(format!(
+                "secret key must be 32 bytes but was {} bytes",
+                key.len()
+            )));
+        }
+        Ok(Self(*Key::<Aes256Gcm>::from_slice(key.as_slice())))
+    }
+
+    pub fn to_base64(&self) -> String {
+        STANDARD.encode(self.0.as_slice())
+    }
+
+    /// true if `value` was produced by [`SecretKey::seal`]
+    pub fn is_sealed(value: &str) -> bool {
+        value.starts_with(SEALED)
+    }
+
+    /// encrypt `value` with a fresh data key and wrap the data key with this key.  The result
+    /// has the form `sealed:v1$<wrapped data key>$<ciphertext>`
+    pub fn seal(&self, value: &str) -> Result<String, RegErr> {
+        let data_key = Aes256Gcm::generate_key(OsRng);
+        let wrapped = encrypt(&self.0, data_key.as_slice())?;
+        let ciphertext = encrypt(&data_key, value.as_bytes())?;
+        Ok(format!(
+            "{}{}${}",
+            SEALED,
+            STANDARD.encode(wrapped),
+            STANDARD.encode(ciphertext)
+        ))
+    }
+
+    /// reverse [`SecretKey::seal`].  Values that are not sealed are returned as is so
+    /// secrets written before a key was configured can still be read
+    pub fn open(&self, value: &str) -> Result<String, RegErr> {
+        let sealed = match value.strip_prefix(SEALED) {
+            None => return Ok(value.to_string()),
+            Some(sealed) => sealed,
+        };
+        let (wrapped, ciphertext) = sealed
+            .split_once('$')
+            .ok_or(RegErr::Msg("malformed sealed secret".to_string()))?;
+        let decode = |part: &str| {
+            STANDARD
+                .decode(part)
+                .map_err(|e| RegErr::Msg(format!("malformed sealed secret: {}", e)))
+        };
+        let data_key = decrypt(&self.0, decode(wrapped)?.as_slice())?;
+        if data_key.len() != 32 {
+            return Err(RegErr::Msg("malformed sealed secret".to_string()));
+        }
+        let data_key = Key::<Aes256Gcm>::from_slice(data_key.as_slice());
+        let value = decrypt(data_key, decode(ciphertext)?.as_slice())?;
+        String::from_utf8(value).map_err(|e| RegErr::Msg(e.to_string()))
+    }
+}
+
+impl Debug for SecretKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SecretKey(..)")
+    }
+}
+
+/// `nonce` followed by the ciphertext
+fn encrypt(key: &Key<Aes256Gcm>, plain: &[u8]) -> Result<Vec<u8>, RegErr> {
+    let nonce = Aes256Gcm::generate_nonce(OsRng);
+    let mut rtn = nonce.to_vec();
+    rtn.append(
+        &mut Aes256Gcm::new(key)
+            .encrypt(&nonce, plain)
+            .map_err(|_| RegErr::Msg("could not encrypt secret".to_string()))?,
+    );
+    Ok(rtn)
+}
+
+fn decrypt(key: &Key<Aes256Gcm>, data: &[u8]) -> Result<Vec<u8>, RegErr> {
+    if data.len() < 12 {
+        return Err(RegErr::Msg("malformed sealed secret".to_string()));
+    }
+    let (nonce, ciphertext) = data.split_at(12);
+    Aes256Gcm::new(key)
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| RegErr::Msg("could not decrypt secret (wrong secret key?)".to_string()))
+}
+
+/// a [`RegistryApi`] that seals `CoreSecret` properties with a [`SecretKey`] before handing
+/// them to the wrapped registry
+pub struct SecretRegistry {
+    registry: Registry,
+    key: SecretKey,
+}
+
+impl SecretRegistry {
+    pub fn new(registry: Registry, key: SecretKey) -> Self {
+        Self { registry, key }
+    }
+
+    fn seal(&self, kind: &Kind, properties: &SetProperties) -> Result<SetProperties, RegErr> {
+        let config = properties_config(kind);
+        let mut rtn = properties.clone();
+        for (key, property) in properties.iter() {
+            if let PropertyMod::Set { value, lock, .. } = property {
+                if is_secret(&config, key) && !SecretKey::is_sealed(value) {
+                    rtn.push(PropertyMod::Set {
+                        key: key.clone(),
+                        value: self.key.seal(value)?,
+                        lock: lock.clone(),
+                    });
+                }
+            }
+        }
+        Ok(rtn)
+    }
+
+    fn open(&self, mut properties: Properties) -> Result<Properties, RegErr> {
+        for property in properties.values_mut() {
+            if SecretKey::is_sealed(&property.value) {
+                property.value = self.key.open(&property.value)?;
+            }
+        }
+        Ok(properties)
+    }
+}
+
+#[async_trait]
+impl RegistryApi for SecretRegistry {
+    async fn scorch<'a>(&'a self) -> Result<(), RegErr> {
+        self.registry.scorch().await
+    }
+
+    async fn register<'a>(&'a self, registration: &'a Registration) -> Result<(), RegErr> {
+        let mut registration = registration.clone();
+        registration.properties = self.seal(&registration.kind, &registration.properties)?;
+        self.registry.register(&registration).await
+    }
+
+    async fn assign_star<'a>(&'a self, point: &'a Point, star: &'a Point) -> Result<(), RegErr> {
+        self.registry.assign_star(point, star).await
+    }
+
+    async fn assign_host<'a>(&'a self, point: &'a Point, host: &'a Point) -> Result<(), RegErr> {
+        self.registry.assign_host(point, host).await
+    }
+
+    async fn set_status<'a>(&'a self, point: &'a Point, status: &'a Status) -> Result<(), RegErr> {
+        self.registry.set_status(point, status).await
+    }
+
+    async fn set_properties<'a>(
+        &'a self,
+        point: &'a Point,
+        properties: &'a SetProperties,
+    ) -> Result<(), RegErr> {
+        let kind = self.registry.record(point).await?.details.stub.kind;
+        let properties = self.seal(&kind, properties)?;
+        self.registry.set_properties(point, &properties).await
+    }
+
+    async fn sequence<'a>(&'a self, point: &'a Point) -> Result<u64, RegErr> {
+        self.registry.sequence(point).await
+    }
+
+    async fn get_properties<'a>(&'a self, point: &'a Point) -> Result<Properties, RegErr> {
+        self.open(self.registry.get_properties(point).await?)
+    }
+
+    async fn record<'a>(&'a self, point: &'a Point) -> Result<ParticleRecord, RegErr> {
+        let mut record = self.registry.record(point).await?;
+        record.details.properties = self.open(record.details.properties)?;
+        Ok(record)
+    }
+
+    async fn query<'a>(
+        &'a self,
+        point: &'a Point,
+        query: &'a Query,
+    ) -> Result<QueryResult, RegErr> {
+        self.registry.query(point, query).await
+    }
+
+    async fn delete<'a>(&'a self, delete: &'a Delete) -> Result<SubstanceList, RegErr> {
+        self.registry.delete(delete).await
+    }
+
+    async fn select<'a>(&'a self, select: &'a mut Select) -> Result<SubstanceList, RegErr> {
+        self.registry.select(select).await
+    }
+
+    async fn sub_select<'a>(&'a self, sub_select: &'a SubSelect) -> Result<Vec<Stub>, RegErr> {
+        self.registry.sub_select(sub_select).await
+    }
+
+    async fn grant<'a>(&'a self, access_grant: &'a AccessGrant) -> Result<(), RegErr> {
+        self.registry.grant(access_grant).await
+    }
+
+    async fn access<'a>(&'a self, to: &'a Point, on: &'a Point) -> Result<Access, RegErr> {
+        self.registry.access(to, on).await
+    }
+
+    async fn chown<'a>(
+        &'a self,
+        on: &'a Selector,
+        owner: &'a Point,
+        by: &'a Point,
+    ) -> Result<(), RegErr> {
+        self.registry.chown(on, owner, by).await
+    }
+
+    async fn list_access<'a>(
+        &'a self,
+        to: &'a Option<&'a Point>,
+        on: &'a Selector,
+    ) -> Result<Vec<IndexedAccessGrant>, RegErr> {
+        self.registry.list_access(to, on).await
+    }
+
+    async fn remove_access<'a>(&'a self, id: i32, to: &'a Point) -> Result<(), RegErr> {
+        self.registry.remove_access(id, to).await
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use crate::registry::conformance::registration;
+    use crate::registry::err::RegErr;
+    use crate::registry::mem::registry::MemoryRegistry;
+    use crate::registry::secret::{SecretKey, SecretRegistry};
+    use crate::registry::{Registry, RegistryApi};
+    use starlane_space::command::common::{PropertyMod, SetProperties};
+    use starlane_space::kind::Kind;
+    use starlane_space::point::Point;
+    use starlane_space::HYPERUSER;
+    use std::str::FromStr;
+    use std::sync::Arc;
+
+    #[test]
+    pub fn test_seal() {
+        let key = SecretKey::generate();
+        let sealed = key.seal("correct horse").unwrap();
+        assert!(SecretKey::is_sealed(sealed.as_str()));
+        assert_ne!(sealed, key.seal("correct horse").unwrap());
+        assert_eq!(key.open(sealed.as_str()).unwrap(), "correct horse");
+        assert_eq!(key.open("plain").unwrap(), "plain");
+
+        let key = SecretKey::from_base64(key.to_base64().as_str()).unwrap();
+        assert_eq!(key.open(sealed.as_str()).unwrap(), "correct horse");
+
+        assert!(SecretKey::generate().open(sealed.as_str()).is_err());
+        assert!(SecretKey::from_base64("c2hvcnQ=").is_err());
+    }
+
+    #[tokio::test]
+    pub async fn test_secret_registry() -> Result<(), RegErr> {
+        let backing: Registry = Arc::new(MemoryRegistry::new());
+        let registry = SecretRegistry::new(backing.clone(), SecretKey::generate());
+
+        let user = Point::from_str("localhost:users:scott")?;
+        registry
+            .register(&registration(&user, Kind::User, &HYPERUSER))
+            .await?;
+
+        let mut properties = SetProperties::new();
+        properties.push(PropertyMod::Set {
+            key: "password".to_string(),
+            value: "correct horse".to_string(),
+            lock: false,
+        });
+        properties.push(PropertyMod::Set {
+            key: "email".to_string(),
+            value: "scott@starlane.io".to_string(),
+            lock: false,
+        });
+        registry.set_properties(&user, &properties).await?;
+
+        let stored = backing.get_properties(&user).await?;
+        assert!(SecretKey::is_sealed(&stored.get("password").unwrap().value));
+        assert_eq!(stored.get("email").unwrap().value, "scott@starlane.io");
+
+        let opened = registry.get_properties(&user).await?;
+        assert_eq!(opened.get("password").unwrap().value, "correct horse");
+        let record = registry.record(&user).await?;
+        assert_eq!(
+            record.details.properties.get("password").unwrap().value,
+            "correct horse"
+        );
+
+        Ok(())
+    }
+}
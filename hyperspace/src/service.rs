@@ -2,7 +2,7 @@ pub mod standard;
 
 use std::env;
 use std::env::current_dir;
-use crate::executor::dialect::filestore::{FileStore, FileStoreErr, FILE_STORE_ROOT};
+use crate::executor::dialect::filestore::{FileStore, FileStoreApi, FileStoreErr, NativeFileStore, FILE_STORE_ROOT};
 use crate::executor::{ExeConf, Executor};
 use crate::host::err::HostErr;
 use crate::machine::MachineErr;
@@ -24,7 +24,7 @@ use std::future::Future;
 use std::hash::Hash;
 use std::io::Read;
 use std::ops::{Deref, DerefMut};
-use std::path::absolute;
+use std::path::{absolute, PathBuf};
 use once_cell::sync::Lazy;
 use thiserror::Error;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
@@ -58,6 +58,13 @@ impl Service<ServiceRunnerConf> {
     }
 }
 
+impl FileStoreService {
+    /// a [FileStoreApi] at the root of this FileStore
+    pub fn api(self) -> FileStoreApi {
+        FileStoreApi::new(PathBuf::from("/"), std::sync::Arc::new(self.runner))
+    }
+}
+
 impl<R> Deref for Service<R> {
     type Target = R;
 
@@ -69,12 +76,17 @@ impl<R> Deref for Service<R> {
 #[derive(Clone)]
 pub enum ServiceRunnerConf {
     Exe(ExeConf),
+    /// a [FileStore::Native] rooted at this directory
+    NativeFileStore(PathBuf),
 }
 
 impl ServiceRunnerConf {
     pub fn filestore(&self) -> Result<FileStore, ServiceErr> {
         match self {
             ServiceRunnerConf::Exe(exe) => Ok(exe.create()?),
+            ServiceRunnerConf::NativeFileStore(root) => {
+                Ok(FileStore::Native(NativeFileStore::new(root.clone())))
+            }
         }
     }
 }
@@ -264,7 +276,18 @@ pub static STARLANE_DATA_DIR: Lazy<String> = Lazy::new(|| {
 });
 
 
+/// the default FileStore: [cli_service_conf]
 pub fn service_conf() -> ServiceConf {
+    cli_service_conf()
+}
+
+/// a FileStore that accesses [STARLANE_DATA_DIR] in process
+pub fn native_service_conf() -> ServiceConf {
+    ServiceConf::NativeFileStore(STARLANE_DATA_DIR.to_string().into())
+}
+
+/// a FileStore that runs the `starlane-cli-filestore-service` executable for every operation
+pub fn cli_service_conf() -> ServiceConf {
     let mut builder = HostEnv::builder();
     builder.pwd(
         absolute(env::current_dir().unwrap())
@@ -273,21 +296,13 @@ pub fn service_conf() -> ServiceConf {
             .unwrap()
             .to_string(),
     );
-    println!("{}", env::current_dir().unwrap().to_str().unwrap());
-    println!("  FILE_STORE_ROOT: {}", FILE_STORE_ROOT);
-    println!("STARLANE_DATA_DIR: {}", STARLANE_DATA_DIR.as_str());
-    builder.env(
-        FILE_STORE_ROOT,
-        STARLANE_DATA_DIR.to_string(),
-    );
+    builder.env(FILE_STORE_ROOT, STARLANE_DATA_DIR.to_string());
     let env = builder.build();
     let path = "../target/debug/starlane-cli-filestore-service".to_string();
-    let args: Option<Vec<String>> = Option::None;
 
     let stub = ExeStub::new(path.into(), env);
 
     ServiceConf::Exe(ExeConf::Host(Host::Cli(HostCli::Os(stub))))
-
 }
 
 
@@ -298,18 +313,25 @@ pub mod tests {
     use crate::host::{ExeStub, Host};
 
     use crate::executor::cli::HostEnv;
-    use crate::executor::dialect::filestore::{FileStore, FileStoreIn, FileStoreOut, FILE_STORE_ROOT};
-    use crate::executor::{ExeConf, Executor};
+    use crate::executor::dialect::filestore::{
+        FileKind, FileStat, FileStore, FileStoreErr, FileStoreIn, FileStoreOut, FILE_STORE_ROOT,
+    };
+    use crate::executor::{Body, ExeConf, Executor};
+    use crate::test_util::temp_dir;
     use crate::host::HostCli;
     use crate::service::{service_conf, Service, ServiceConf, ServiceErr, ServiceKind, ServiceTemplate};
     use starlane_space::kind::BaseKind;
     use starlane_space::selector::KindSelector;
     use starlane_space::util::OptSelector;
+    use std::os::unix::fs::PermissionsExt;
     use std::path::{absolute, PathBuf};
     use std::{env, io};
     use std::env::current_dir;
     use once_cell::sync::Lazy;
+    use std::str::FromStr;
+    use std::time::{Duration, UNIX_EPOCH};
     use tokio::fs;
+    use tokio::io::AsyncReadExt;
 
 
 
@@ -428,6 +450,257 @@ pub mod tests {
          */
 
     
+    #[tokio::test]
+    pub async fn test_native_filestore() {
+        let dir = temp_dir("native-filestore");
+        let root = dir.path().join("root");
+        let filestore = ServiceConf::NativeFileStore(root.clone()).filestore().unwrap();
+
+        filestore.execute(FileStoreIn::Init).await.unwrap();
+        assert!(root.is_dir());
+
+        filestore
+            .execute(FileStoreIn::Mkdir { path: "blah".into() })
+            .await
+            .unwrap();
+        let content = "HEllo from me";
+        filestore
+            .execute(FileStoreIn::Write {
+                path: "blah/somefile.txt".into(),
+                state: content.into(),
+            })
+            .await
+            .unwrap();
+
+        if let FileStoreOut::Read(mut stream) = filestore
+            .execute(FileStoreIn::Read {
+                path: "blah/somefile.txt".into(),
+            })
+            .await
+            .unwrap()
+        {
+            let mut read = String::new();
+            stream.read_to_string(&mut read).await.unwrap();
+            assert_eq!(content, read);
+        } else {
+            assert!(false);
+        }
+
+        if let FileStoreOut::List(paths) = filestore
+            .execute(FileStoreIn::List { path: "blah".into() })
+            .await
+            .unwrap()
+        {
+            assert_eq!(paths, vec![root.join("blah/somefile.txt")]);
+        } else {
+            assert!(false);
+        }
+
+        // the sub root sees the same files
+        let sub = filestore.sub_root("blah".into()).await.unwrap();
+        assert!(matches!(
+            sub.execute(FileStoreIn::Exists {
+                path: "somefile.txt".into()
+            })
+            .await
+            .unwrap(),
+            FileStoreOut::Exists(true)
+        ));
+
+        filestore
+            .execute(FileStoreIn::Remove {
+                path: "blah/somefile.txt".into(),
+            })
+            .await
+            .unwrap();
+        assert!(matches!(
+            filestore
+                .execute(FileStoreIn::Exists {
+                    path: "blah/somefile.txt".into()
+                })
+                .await
+                .unwrap(),
+            FileStoreOut::Exists(false)
+        ));
+
+        assert!(filestore
+            .execute(FileStoreIn::Read {
+                path: "../escape.txt".into()
+            })
+            .await
+            .is_err());
+
+        // a symlink inside the root must not lead out of it
+        let outside = dir.path().join("outside");
+        std::fs::create_dir_all(&outside).unwrap();
+        std::fs::write(outside.join("secret.txt"), "secret").unwrap();
+        std::os::unix::fs::symlink(&outside, root.join("link")).unwrap();
+        std::os::unix::fs::symlink(outside.join("missing"), root.join("dangling")).unwrap();
+        for path in ["link/secret.txt", "link/new.txt", "link", "dangling"] {
+            assert!(matches!(
+                filestore.execute(FileStoreIn::Read { path: path.into() }).await,
+                Err(FileStoreErr::PathEscapesFileStoreBoundary(_))
+            ));
+        }
+        assert!(filestore
+            .execute(FileStoreIn::Write {
+                path: "link/new.txt".into(),
+                state: content.into(),
+            })
+            .await
+            .is_err());
+        assert!(!outside.join("new.txt").exists());
+        assert!(filestore.sub_root("link".into()).await.is_err());
+    }
+
+    #[tokio::test]
+    pub async fn test_native_filestore_streams() {
+        let dir = temp_dir("native-filestore-streams");
+        let root = dir.path().to_path_buf();
+        let filestore = ServiceConf::NativeFileStore(root.clone()).filestore().unwrap();
+        filestore.execute(FileStoreIn::Init).await.unwrap();
+
+        filestore
+            .execute(FileStoreIn::Write {
+                path: "file.txt".into(),
+                state: Body::stream(&b"hello "[..]),
+            })
+            .await
+            .unwrap();
+        filestore
+            .execute(FileStoreIn::Append {
+                path: "file.txt".into(),
+                state: "world".into(),
+            })
+            .await
+            .unwrap();
+
+        let read_range = |offset, len| FileStoreIn::ReadRange {
+            path: "file.txt".into(),
+            offset,
+            len,
+        };
+        for (offset, len, expect) in [(0, None, "hello world"), (6, Some(3), "wor")] {
+            if let FileStoreOut::ReadRange(mut stream) =
+                filestore.execute(read_range(offset, len)).await.unwrap()
+            {
+                let mut read = String::new();
+                stream.read_to_string(&mut read).await.unwrap();
+                assert_eq!(expect, read);
+            } else {
+                assert!(false);
+            }
+        }
+
+        filestore
+            .execute(FileStoreIn::Copy {
+                from: "file.txt".into(),
+                to: "copy.txt".into(),
+            })
+            .await
+            .unwrap();
+        filestore
+            .execute(FileStoreIn::Rename {
+                from: "copy.txt".into(),
+                to: "moved.txt".into(),
+            })
+            .await
+            .unwrap();
+        assert!(!root.join("copy.txt").exists());
+
+        if let FileStoreOut::Stat(stat) = filestore
+            .execute(FileStoreIn::Stat {
+                path: "moved.txt".into(),
+            })
+            .await
+            .unwrap()
+        {
+            assert_eq!(stat.kind, FileKind::File);
+            assert_eq!(stat.size, 11);
+            assert!(stat.modified.is_some());
+        } else {
+            assert!(false);
+        }
+    }
+
+    /// a cli FileStore that fails (with a message on stderr) for anything it cannot find
+    fn failing_cli(root: &PathBuf) -> FileStore {
+        let script = root.join("filestore.sh");
+        std::fs::write(
+            &script,
+            "#!/bin/sh\n\
+             case \"$1\" in\n\
+             read|read-range) cat \"$FILE_STORE_ROOT/$2\" ;;\n\
+             rename) mv \"$FILE_STORE_ROOT/$2\" \"$FILE_STORE_ROOT/$3\" ;;\n\
+             *) exit 0 ;;\n\
+             esac\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let mut builder = HostEnv::builder();
+        builder.pwd(root.to_str().unwrap());
+        builder.env(FILE_STORE_ROOT, root.to_str().unwrap());
+        builder.env("PATH", "/usr/bin:/bin");
+        let stub = ExeStub::new(script.to_str().unwrap().to_string(), builder.build());
+        ExeConf::Host(Host::Cli(HostCli::Os(stub))).create().unwrap()
+    }
+
+    #[tokio::test]
+    pub async fn test_cli_filestore_failures() {
+        let dir = temp_dir("cli-filestore");
+        let root = dir.path().to_path_buf();
+        let filestore = failing_cli(&root);
+
+        for input in [
+            FileStoreIn::Read {
+                path: "missing.txt".into(),
+            },
+            FileStoreIn::ReadRange {
+                path: "missing.txt".into(),
+                offset: 0,
+                len: None,
+            },
+            FileStoreIn::Rename {
+                from: "missing.txt".into(),
+                to: "moved.txt".into(),
+            },
+        ] {
+            match filestore.execute(input).await {
+                Err(FileStoreErr::CliFailed(stderr)) => assert!(stderr.contains("missing.txt")),
+                Err(err) => panic!("expected CliFailed not {}", err),
+                Ok(_) => panic!("expected a missing path to fail"),
+            }
+        }
+
+        std::fs::write(root.join("empty.txt"), "").unwrap();
+        if let FileStoreOut::Read(mut stream) = filestore
+            .execute(FileStoreIn::Read {
+                path: "empty.txt".into(),
+            })
+            .await
+            .unwrap()
+        {
+            let mut read = vec![];
+            stream.read_to_end(&mut read).await.unwrap();
+            assert!(read.is_empty());
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    pub fn test_file_stat() {
+        let stat = FileStat {
+            kind: FileKind::Dir,
+            size: 4096,
+            modified: Some(UNIX_EPOCH + Duration::from_secs(1718000000)),
+        };
+        assert_eq!("dir 4096 1718000000", stat.to_string());
+        assert_eq!(stat, FileStat::from_str(stat.to_string().as_str()).unwrap());
+        assert_eq!(None, FileStat::from_str("file 3 -").unwrap().modified);
+        assert!(FileStat::from_str("file three -").is_err());
+    }
+
     /// disabled while trying to get `starlane` to compile after being in moth balls for 6 months...
     //#[tokio::test]
     pub async fn test_filestore() {
@@ -478,8 +751,9 @@ pub mod tests {
                 path: "blah/somefile.txt".into(),
             };
             let mut child = executor.execute(args).await.unwrap();
-            if let FileStoreOut::Read(bin) = child {
-                let read = String::from_utf8(bin).unwrap();
+            if let FileStoreOut::Read(mut stream) = child {
+                let mut read = String::new();
+                stream.read_to_string(&mut read).await.unwrap();
                 println!("content: {}", read);
                 assert_eq!(content, read);
             } else {
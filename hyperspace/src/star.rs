@@ -15,13 +15,16 @@ use crate::template::Templates;
 use anyhow::{Context, Error};
 use async_recursion::async_recursion;
 use async_trait::async_trait;
+use dashmap::mapref::entry::Entry;
 use dashmap::DashMap;
 use itertools::Itertools;
 use starlane_macros::{log_span, push_loc, push_mark};
 use starlane_space::command::common::StateSrc;
 use starlane_space::command::direct::create::{Create, Strategy};
 use starlane_space::err::{CoreReflector, ParseErrs, SpaceErr, SpatialError};
-use starlane_space::hyper::{Assign, AssignmentKind, HyperSubstance, Provision, Search};
+use starlane_space::hyper::{
+    Assign, AssignmentKind, HyperEvent, HyperSubstance, Provision, Search, StatusChanged,
+};
 use starlane_space::hyper::{MountKind, ParticleLocation};
 use starlane_space::kind::{Kind, StarStub, StarSub};
 use starlane_space::loc::{
@@ -45,7 +48,7 @@ use starlane_space::wave::exchange::asynch::{
 use starlane_space::wave::exchange::SetStrategy;
 use starlane_space::wave::Wave;
 use starlane_space::wave::{
-    Agent, DirectedProto, Handling, HandlingKind, PongCore, Priority, Recipients, Reflectable,
+    Agent, BounceBacks, DirectedProto, Handling, HandlingKind, PongCore, Priority, Recipients, Reflectable,
     ReflectedWave, Retries, Ripple, Scope, SignalCore, SingularRipple, ToReflected, WaitTime,
     WaveId, WaveKind, WaveVariantDef,
 };
@@ -59,6 +62,14 @@ use std::time::Duration;
 use thiserror::Error;
 use tokio::sync::{broadcast, mpsc, oneshot, watch};
 
+pub mod golden;
+pub mod handling;
+pub mod watchers;
+
+use crate::star::golden::GoldenPath;
+use crate::star::handling::{DurableJournal, OutboundQueue};
+use crate::star::watchers::StarWatchers;
+
 #[derive(Clone)]
 pub struct ParticleStates {
     topic: Arc<DashMap<Surface, Arc<dyn TopicHandler>>>,
@@ -121,7 +132,7 @@ pub struct HyperStarSkel {
     pub kind: StarSub,
     pub logger: Logger,
     pub registry: Registry,
-    pub golden_path: Arc<DashMap<StarKey, StarKey>>,
+    pub golden_path: GoldenPath,
     pub traverse_to_next_tx: mpsc::Sender<Traversal<Wave>>,
     pub inject_tx: mpsc::Sender<TraversalInjection>,
     //    pub machine: MachineSkel,
@@ -138,6 +149,7 @@ pub struct HyperStarSkel {
     pub status_rx: watch::Receiver<Status>,
     pub template: StarTemplate,
     pub star_transmitter: ProtoTransmitter,
+    pub watchers: StarWatchers,
 
     #[cfg(test)]
     pub diagnostic_interceptors: DiagnosticInterceptors,
@@ -182,11 +194,11 @@ impl HyperStarSkel {
         );
 
         let mut adjacents = HashMap::new();
-        let mut golden_path = Arc::new(DashMap::new());
+        let golden_path = GoldenPath::default();
         // prime the searcher by mapping the immediate lanes
         for hyperway in template.connections.clone() {
             adjacents.insert(hyperway.key().clone().to_point(), hyperway.stub().clone());
-            golden_path.insert(hyperway.key().clone(), hyperway.key().clone());
+            golden_path.adjacent(hyperway.key().clone().to_point());
         }
 
         let gravity_router = TxRouter::new(star_tx.gravity_tx.clone());
@@ -242,6 +254,7 @@ impl HyperStarSkel {
             status_tx: star_tx.status_tx.clone(),
             status_rx: star_tx.status_rx.clone(),
             star_transmitter,
+            watchers: StarWatchers::new(),
             #[cfg(test)]
             diagnostic_interceptors: DiagnosticInterceptors::new(),
             template,
@@ -252,6 +265,29 @@ impl HyperStarSkel {
         format!("{}/{}/", self.machine_api.data_dir, self.point.to_string())
     }
 
+    /// ripple `event` to the watchers of its [`Watch`]
+    pub async fn emit(&self, event: HyperEvent) -> Result<(), StarErr> {
+        let mut ripple = DirectedProto::ripple();
+        ripple.method(HypMethod::Event);
+        ripple.to(Recipients::Watchers(event.watch()));
+        ripple.bounce_backs(BounceBacks::None);
+        ripple.body(event.into());
+        self.gravity_transmitter
+            .direct::<_, ()>(ripple)
+            .await?;
+        Ok(())
+    }
+
+    /// record the new `status` of `point` and notify its [`Aspect::State`] watchers
+    pub async fn set_status(&self, point: &Point, status: &Status) -> Result<(), StarErr> {
+        self.registry.set_status(point, status).await?;
+        self.emit(HyperEvent::StatusChanged(StatusChanged {
+            point: point.clone(),
+            status: status.clone(),
+        }))
+        .await
+    }
+
     /*
     pub async fn create_star_particle(&self, point: Point, kind: Kind ) -> Result<(),StarErr> {
 
@@ -364,6 +400,7 @@ pub enum HyperStarCall {
     ToHyperway(WaveVariantDef<SignalCore>),
     Shard(Wave),
     StartWrangling,
+    ReplayJournal,
     Wrangle(oneshot::Sender<Result<StarWrangles, SpaceErr>>),
     Bounce {
         key: StarKey,
@@ -536,6 +573,11 @@ impl HyperStarApi {
         self.tx.send(HyperStarCall::StartWrangling).await;
     }
 
+    /// resend any durable waves that were journaled but never delivered
+    pub async fn replay_journal(&self) {
+        self.tx.send(HyperStarCall::ReplayJournal).await;
+    }
+
     pub async fn bounce(&self, key: StarKey) -> Result<(), SpaceErr> {
         let (rtn, mut rtn_rx) = oneshot::channel();
         self.tx.send(HyperStarCall::Bounce { key, rtn }).await?;
@@ -607,16 +649,112 @@ impl HyperStarApi {
     }
 }
 
+/// sends transports from the [`OutboundQueue`] into the hyperway
+/// wrapping each in a hop to go to one and only one star
+struct HyperwayRouter {
+    skel: HyperStarSkel,
+    point: Point,
+    adjacents: HashSet<Point>,
+    forwarders: Vec<Point>,
+    gravity: Surface,
+    transmitter: ProtoTransmitter,
+    outbound: OutboundQueue,
+    journal: DurableJournal,
+    searching: Arc<DashMap<Point, Vec<WaveVariantDef<SignalCore>>>>,
+    logger: Logger,
+}
+
+impl HyperwayRouter {
+    #[track_caller]
+    async fn route(&self, transport: WaveVariantDef<SignalCore>) -> Result<(), StarErr> {
+        let logger = push_mark!(self.logger);
+        let to = if self.point == transport.to.point {
+            // it's a bit of a strange case, but even if this star is sending a transport message
+            // to itself, it still makes use of the Hyperway Interchange, which will bounce it back
+            // The reason for this is that it is the Hyperway that handles things like Priority,
+            // Urgency and durability, whereas within the star itself all waves are
+            // treated equally.
+            self.point.clone().to_surface()
+        } else if self.adjacents.contains(&transport.to.point) {
+            transport.to.clone()
+        } else if self.forwarders.len() == 1 {
+            self.forwarders.first().unwrap().clone().to_surface()
+        } else if let Some(via) = self.skel.golden_path.next_hop(&transport.to.point) {
+            via.to_surface()
+        } else if self.forwarders.is_empty() {
+            Err(StarErr::MissingAdjacentForwarder)?
+        } else {
+            self.search(transport);
+            return Ok(());
+        };
+        logger.result(
+            self.transmitter
+                .direct::<_, ()>(transport.clone().wrap_in_hop(self.gravity.clone(), to))
+                .await,
+        )?;
+        self.journal.handed_off(&transport).await
+    }
+
+    /// park the transport until a search ripple has found a route to its star.  Only one
+    /// search per star is in flight at a time, transports to a star that is already being
+    /// searched for wait on the same search.  The search runs in its own task since its
+    /// ripple must itself drain through the [`OutboundQueue`]
+    fn search(&self, transport: WaveVariantDef<SignalCore>) {
+        let star = transport.to.point.clone();
+        match self.searching.entry(star.clone()) {
+            Entry::Occupied(mut parked) => {
+                parked.get_mut().push(transport);
+                return;
+            }
+            Entry::Vacant(vacant) => {
+                vacant.insert(vec![transport]);
+            }
+        }
+
+        let skel = self.skel.clone();
+        let outbound = self.outbound.clone();
+        let searching = self.searching.clone();
+        tokio::spawn(async move {
+            let result = Self::discover(&skel, &star).await;
+            let parked = searching
+                .remove(&star)
+                .map(|(_, parked)| parked)
+                .unwrap_or_default();
+            match result {
+                Ok(_) => {
+                    for transport in parked {
+                        outbound.push(transport);
+                    }
+                }
+                Err(err) => skel.err(&err),
+            }
+        });
+    }
+
+    async fn discover(skel: &HyperStarSkel, star: &Point) -> Result<(), StarErr> {
+        let key = StarKey::try_from(star.clone())?;
+        let wrangler = Wrangler::new(skel.clone(), Search::Star(key.clone()));
+        for (via, discovery) in wrangler.discover(false).await? {
+            if discovery.star_key == key {
+                skel.golden_path.discovered(star.clone(), via, discovery.hops);
+            }
+        }
+        match skel.golden_path.next_hop(star) {
+            Some(_) => Ok(()),
+            None => Err(StarErr::NoRouteToStar(star.clone())),
+        }
+    }
+}
+
 pub struct HyperStar {
     skel: HyperStarSkel,
     star_tx: mpsc::Sender<HyperStarCall>,
     star_rx: mpsc::Receiver<HyperStarCall>,
     drivers: DriversApi,
     injector: Surface,
-    forwarders: Vec<Point>,
-    hyperway_transmitter: ProtoTransmitter,
+    outbound: OutboundQueue,
+    journal: DurableJournal,
     gravity: Surface,
-    hyper_router: Arc<dyn Router>,
     layer_traversal_engine: LayerTraversalEngine,
     global_handler: DirectedHandlerShell<GlobalCommandExecutionHandler>,
 }
@@ -671,6 +809,37 @@ impl HyperStar {
         hyperway_transmitter.scope = SetStrategy::Override(Scope::Full);
         let hyperway_transmitter = hyperway_transmitter.build();
 
+        let gravity = skel.point.clone().to_surface().with_layer(Layer::Gravity);
+        let journal = DurableJournal::new(format!("{}journal", skel.data_dir()));
+        let outbound = OutboundQueue::new();
+
+        // drain the outbound queue into the hyperway one transport at a time so
+        // that a backlog is always released in priority order
+        {
+            let router = HyperwayRouter {
+                skel: skel.clone(),
+                point: skel.point.clone(),
+                adjacents: skel.adjacents.keys().cloned().collect(),
+                forwarders,
+                gravity: gravity.clone(),
+                transmitter: hyperway_transmitter,
+                outbound: outbound.clone(),
+                journal: journal.clone(),
+                searching: Arc::new(DashMap::new()),
+                logger: push_mark!(skel.logger),
+            };
+            let outbound = outbound.clone();
+            let skel = skel.clone();
+            tokio::spawn(async move {
+                loop {
+                    let transport = outbound.pop().await;
+                    if let Err(err) = router.route(transport).await {
+                        skel.err(&err);
+                    }
+                }
+            });
+        }
+
         let mut injector = skel
             .point()
             .clone()
@@ -699,8 +868,6 @@ impl HyperStar {
             to_gravity_traversal_tx,
         );
 
-        let gravity = skel.point.clone().to_surface().with_layer(Layer::Gravity);
-
         // relay from hyper_rx
         {
             let star_tx = star_tx.clone();
@@ -743,6 +910,7 @@ impl HyperStar {
                                     .unwrap();
                                 star_driver.init_item(skel.point.to_point()).await;
                                 api.start_wrangling().await;
+                                api.replay_journal().await;
                                 // seeing if wrangling can wait on MachineApi...
                                 status_tx.send(Status::Ready).await;
                             }
@@ -829,10 +997,9 @@ impl HyperStar {
                 star_rx,
                 drivers,
                 injector,
-                hyperway_transmitter,
-                forwarders,
+                outbound,
+                journal,
                 gravity,
-                hyper_router,
                 layer_traversal_engine,
                 global_handler,
             };
@@ -917,6 +1084,12 @@ impl HyperStar {
                     HyperStarCall::StartWrangling => {
                         self.start_wrangling().await;
                     }
+                    HyperStarCall::ReplayJournal => match self.replay_journal().await {
+                        Ok(_) => {}
+                        Err(err) => {
+                            self.skel.err(&err);
+                        }
+                    },
                 }
             }
         });
@@ -961,6 +1134,11 @@ impl HyperStar {
         }
 
         if transport.to.point == self.skel.point {
+            // a returning reflection means a journaled durable wave has been delivered
+            if let Ok(wave) = transport.clone().unwrap_from_transport() {
+                self.journal.reflected(&wave).await?;
+            }
+
             // we are now going to send this transport down the layers to the StarCore
             // where it's contents will be unwrapped from transport and routed to the appropriate particle
             let layer_engine = self.layer_traversal_engine.clone();
@@ -1008,6 +1186,13 @@ impl HyperStar {
             .unwrap_or_default();
         let logger = push_mark!(self.skel.logger);
         logger.track(&wave, || Tracker::new("to_gravity", "Receive"));
+
+        if DurableJournal::is_durable(&wave) {
+            self.journal.record(&wave).await?;
+        } else {
+            self.journal.reflected(&wave).await?;
+        }
+
         if wave.is_directed()
             && wave.to().is_single()
             && wave.to().to_single().unwrap().point == *GLOBAL_EXEC
@@ -1031,6 +1216,7 @@ impl HyperStar {
         let skel = self.skel.clone();
         let locator = SmartLocator::new(self.skel.clone());
         let gravity = self.gravity.clone();
+        let journal = self.journal.clone();
         let logger = push_mark!(self.skel.logger);
         tokio::spawn(async move {
             async fn shard(
@@ -1038,12 +1224,22 @@ impl HyperStar {
                 skel: HyperStarSkel,
                 locator: SmartLocator,
                 gravity: Surface,
+                journal: DurableJournal,
             ) -> Result<(), StarErr> {
                 if wave.track() {
                     println!("\tsharding wave...{}", wave.kind().to_string());
                 }
                 match &mut wave {
                     Wave::Ripple(ripple) => {
+                        // a ripple to the watchers of a particle on this star is expanded
+                        // into a ripple to each of those watchers
+                        if let Recipients::Watchers(watch) = &ripple.to {
+                            let record = skel.registry.record(&watch.point).await?;
+                            if record.location.star.as_ref() == Some(&skel.point) {
+                                ripple.variant.to =
+                                    Recipients::Multi(skel.watchers.watchers(watch));
+                            }
+                        }
                         let mut map =
                             shard_ripple_by_location(ripple, &skel.adjacents, &skel.registry)
                                 .await?;
@@ -1095,6 +1291,13 @@ impl HyperStar {
                                     wave.to().to_string()
                                 );
                             }
+                            // a durable signal to a particle on this star is delivered
+                            // as soon as it is injected
+                            if let Wave::Signal(signal) = &wave {
+                                if DurableJournal::is_durable(&wave) {
+                                    journal.clear(&signal.id).await?;
+                                }
+                            }
                             let mut inject = TraversalInjection::new(
                                 skel.point.to_surface().with_layer(Layer::Gravity),
                                 wave,
@@ -1121,55 +1324,31 @@ impl HyperStar {
                 Ok(())
             }
             logger
-                .result(shard(wave, skel, locator, gravity).await)
+                .result(shard(wave, skel, locator, gravity, journal).await)
                 .unwrap_or_default();
         });
     }
 
-    // send this transport signal into the hyperway
-    // wrap the transport into a hop to go to one and only one star
-
+    // queue this transport signal for the hyperway.  The outbound queue releases
+    // transports in order of their Handling priority
     #[track_caller]
     async fn to_hyperway(&self, transport: WaveVariantDef<SignalCore>) -> Result<(), StarErr> {
-        let logger = push_mark!(self.skel.logger);
-        if self.skel.point == transport.to.point {
-            // it's a bit of a strange case, but even if this star is sending a transport message
-            // to itself, it still makes use of the Hyperway Interchange, which will bounce it back
-            // The reason for this is that it is the Hyperway that handles things like Priority, Urgency
-            // and hopefully in the future durability, whereas within the star itself all waves are
-            // treated equally.
-            logger.result(
-                self.hyperway_transmitter
-                    .direct(
-                        transport.wrap_in_hop(
-                            self.gravity.clone(),
-                            self.skel.point.clone().to_surface(),
-                        ),
-                    )
-                    .await,
-            )?;
-            Ok(())
-        } else if self.skel.adjacents.contains_key(&transport.to.point) {
-            let to = transport.to.clone();
-            logger.result(
-                self.hyperway_transmitter
-                    .direct(transport.wrap_in_hop(self.gravity.clone(), to))
-                    .await,
-            )?;
-            Ok(())
-        } else if self.forwarders.len() == 1 {
-            let to = self.forwarders.first().unwrap().clone().to_surface();
-            logger.result(
-                self.hyperway_transmitter
-                    .direct(transport.wrap_in_hop(self.gravity.clone(), to))
-                    .await,
-            )?;
-            Ok(())
-        } else if self.forwarders.is_empty() {
-            Err(StarErr::MissingAdjacentForwarder)?
-        } else {
-            unimplemented!("need to now send out a ripple search for the star being transported to")
+        self.outbound.push(transport);
+        Ok(())
+    }
+
+    async fn replay_journal(&self) -> Result<(), StarErr> {
+        for wave in self.journal.pending().await? {
+            self.skel
+                .logger
+                .track(&wave, || Tracker::new("replay_journal", "Replay"));
+            self.skel
+                .gravity_tx
+                .send(wave)
+                .await
+                .map_err(StarErr::journal)?;
         }
+        Ok(())
     }
 
     async fn start_wrangling(&self) {
@@ -1368,7 +1547,11 @@ impl LayerTraversalEngine {
                             }
                         }
                     }
-                    Recipients::Watchers(_) => {}
+                    Recipients::Watchers(_) => {
+                        // watchers are expanded when the ripple is sharded so send it
+                        // back to gravity on this star which holds the watched particle
+                        self.skel.gravity_tx.send(wave.clone()).await;
+                    }
                     Recipients::Stars => {
                         if self.skel.point == wave.from().point {
                             tos.push(self.skel.point.to_surface().with_layer(Layer::Gravity));
@@ -1786,11 +1969,13 @@ async fn shard_ripple_by_location(
 ) -> Result<HashMap<Point, WaveVariantDef<Ripple>>, StarErr> {
     let mut map = HashMap::new();
     for (star, recipients) in shard_by_location(ripple.to.clone(), adjacent, registry).await? {
-        if !ripple.history.contains(&star) {
+        // history only guards against broadcasts looping between stars, explicitly
+        // addressed recipients are always delivered (even on a star already visited)
+        let addressed = matches!(recipients, Recipients::Single(_) | Recipients::Multi(_));
+        if addressed || !ripple.history.contains(&star) {
             let mut ripple = ripple.clone();
             ripple.variant.to = recipients;
             map.insert(star, ripple);
-        } else {
         }
     }
     Ok(map)
@@ -1799,10 +1984,10 @@ async fn shard_ripple_by_location(
 pub async fn ripple_to_singulars<E>(
     ripple: WaveVariantDef<Ripple>,
     adjacent: &HashSet<Point>,
-    registry: &Registry,
+    watchers: &StarWatchers,
 ) -> Result<Vec<WaveVariantDef<SingularRipple>>, StarErr> {
     let mut rtn = vec![];
-    for port in to_ports(ripple.to.clone(), adjacent, registry).await? {
+    for port in to_ports(ripple.to.clone(), adjacent, watchers).await? {
         let wave = ripple.as_single(port);
         rtn.push(wave)
     }
@@ -1816,39 +2001,30 @@ pub async fn shard_by_location(
 ) -> Result<HashMap<Point, Recipients>, StarErr> {
     match recipients {
         Recipients::Single(single) => {
-            unimplemented!()
-            /*
             let mut map = HashMap::new();
-            let record = registry.locate(&single.point).await?;
-            map.insert(record.location, Recipients::Single(single));
+            if let Some(star) = registry.record(&single.point).await?.location.star {
+                map.insert(star, Recipients::Single(single));
+            }
             Ok(map)
-
-             */
         }
         Recipients::Multi(multi) => {
-            unimplemented!()
-            /*
-            let mut map: HashMap<Point, Vec<Port>> = HashMap::new();
-            for p in multi {
-                let record = registry.locate(&p).await?;
-                if let Some(found) = map.get_mut(&record.location) {
-                    found.push(p);
-                } else {
-                    map.insert(record.location, vec![p]);
+            let mut map: HashMap<Point, Vec<Surface>> = HashMap::new();
+            for surface in multi {
+                if let Some(star) = registry.record(&surface.point).await?.location.star {
+                    map.entry(star).or_default().push(surface);
                 }
             }
-
-
-            let mut map2 = HashMap::new();
-            for (location, points) in map {
-                map2.insert(location, Recipients::Multi(points));
-            }
-            Ok(map2)
-             */
+            Ok(map
+                .into_iter()
+                .map(|(star, surfaces)| (star, Recipients::Multi(surfaces)))
+                .collect())
         }
-        Recipients::Watchers(_) => {
+        Recipients::Watchers(watch) => {
+            // watchers are registered with the star where the watched particle lives
             let mut map = HashMap::new();
-            // todo
+            if let Some(star) = registry.record(&watch.point).await?.location.star {
+                map.insert(star, Recipients::Watchers(watch));
+            }
             Ok(map)
         }
         Recipients::Stars => {
@@ -1864,14 +2040,12 @@ pub async fn shard_by_location(
 pub async fn to_ports(
     recipients: Recipients,
     adjacent: &HashSet<Point>,
-    registry: &Registry,
+    watchers: &StarWatchers,
 ) -> Result<Vec<Surface>, StarErr> {
     match recipients {
         Recipients::Single(single) => Ok(vec![single]),
         Recipients::Multi(multi) => Ok(multi.into_iter().map(|p| p).collect()),
-        Recipients::Watchers(watch) => {
-            unimplemented!();
-        }
+        Recipients::Watchers(watch) => Ok(watchers.watchers(&watch)),
         Recipients::Stars => {
             let stars: Vec<Surface> = adjacent
                 .clone()
@@ -1986,10 +2160,7 @@ impl SmartLocator {
 
             Ok(location.clone())
         } else {
-            self.skel
-                .registry
-                .set_status(&point, &Status::Panic)
-                .await?;
+            self.skel.set_status(&point, &Status::Panic).await?;
 
             match self.skel.registry.record(&point).await {
                 Ok(record) => Err(RegErr::dupe())?,
@@ -2021,6 +2192,8 @@ pub enum StarErr {
     #[error("star needs to send a transport to a non-adjacent star yet does not have any adjacent forwarders"
     )]
     MissingAdjacentForwarder,
+    #[error("search ripple could not find a route to star '{0}'")]
+    NoRouteToStar(Point),
     #[error("attempt to send wave {wave} to layer {layer} that the recipient Kind {kind} does not have in its traversal plan"
     )]
     TraversalPlanNotFound {
@@ -2035,6 +2208,8 @@ pub enum StarErr {
     CouldNotAssignToSelf(Kind),
     #[error("could not find a host to provision '{0}'")]
     CouldNotFindHostToProvision(Kind),
+    #[error("durable journal failure: {0}")]
+    Journal(String),
     #[error("{0}")]
     Anyhow(Arc<anyhow::Error>),
 }
@@ -2068,6 +2243,10 @@ impl StarErr {
         let parent = parent.clone();
         Self::PointNotInStar { point, parent }
     }
+
+    pub fn journal<E: ToString>(err: E) -> Self {
+        Self::Journal(err.to_string())
+    }
 }
 
 #[derive(Debug, Clone, strum_macros::EnumString, strum_macros::Display)]
@@ -0,0 +1,122 @@
+use dashmap::DashMap;
+use starlane_space::point::Point;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// how long a route found by a search ripple is trusted before it must be searched again
+pub static GOLDEN_PATH_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// the next hop a transport should take to reach a star.  Adjacent stars are primed when the
+/// star is created and never expire, routes to any other star are learned from
+/// [`starlane_space::hyper::Discoveries`] and expire after the cache's ttl
+#[derive(Clone)]
+pub struct GoldenPath {
+    routes: Arc<DashMap<Point, GoldenRoute>>,
+    ttl: Duration,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GoldenRoute {
+    pub via: Point,
+    pub hops: u16,
+    expires: Option<Instant>,
+}
+
+impl GoldenRoute {
+    fn is_expired(&self) -> bool {
+        self.expires
+            .map_or(false, |expires| expires <= Instant::now())
+    }
+}
+
+impl GoldenPath {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            routes: Arc::new(DashMap::new()),
+            ttl,
+        }
+    }
+
+    pub fn adjacent(&self, star: Point) {
+        let route = GoldenRoute {
+            via: star.clone(),
+            hops: 0,
+            expires: None,
+        };
+        self.routes.insert(star, route);
+    }
+
+    /// record that `star` was discovered `hops` away through the adjacent star `via`.
+    /// The route with the lowest hop count wins until it expires
+    pub fn discovered(&self, star: Point, via: Point, hops: u16) {
+        let route = GoldenRoute {
+            via,
+            hops,
+            expires: Some(Instant::now() + self.ttl),
+        };
+        let mut entry = self.routes.entry(star).or_insert_with(|| route.clone());
+        if entry.is_expired() || entry.via == route.via || route.hops < entry.hops {
+            *entry = route;
+        }
+    }
+
+    pub fn route(&self, star: &Point) -> Option<GoldenRoute> {
+        let route = self.routes.get(star)?.clone();
+        if route.is_expired() {
+            self.routes.remove_if(star, |_, route| route.is_expired());
+            None
+        } else {
+            Some(route)
+        }
+    }
+
+    pub fn next_hop(&self, star: &Point) -> Option<Point> {
+        self.route(star).map(|route| route.via)
+    }
+}
+
+impl Default for GoldenPath {
+    fn default() -> Self {
+        Self::new(GOLDEN_PATH_TTL.clone())
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use crate::star::golden::GoldenPath;
+    use starlane_space::point::Point;
+    use std::str::FromStr;
+    use std::time::Duration;
+
+    #[test]
+    pub fn test_lowest_hops() {
+        let path = GoldenPath::new(Duration::from_secs(60));
+        let target = Point::from_str("target").unwrap();
+        let nexus_a = Point::from_str("nexus-a").unwrap();
+        let nexus_b = Point::from_str("nexus-b").unwrap();
+
+        path.discovered(target.clone(), nexus_a.clone(), 3);
+        path.discovered(target.clone(), nexus_b.clone(), 1);
+        assert_eq!(path.next_hop(&target), Some(nexus_b.clone()));
+
+        // a longer route through another forwarder does not replace a shorter one
+        path.discovered(target.clone(), nexus_a.clone(), 2);
+        assert_eq!(path.next_hop(&target), Some(nexus_b));
+
+        path.adjacent(nexus_a.clone());
+        assert_eq!(path.route(&nexus_a).unwrap().hops, 0);
+    }
+
+    #[test]
+    pub fn test_expiry() {
+        let path = GoldenPath::new(Duration::from_secs(0));
+        let target = Point::from_str("target").unwrap();
+        let nexus = Point::from_str("nexus").unwrap();
+        path.discovered(target.clone(), nexus.clone(), 1);
+        assert_eq!(path.next_hop(&target), None);
+
+        // adjacents never expire
+        path.adjacent(nexus.clone());
+        assert_eq!(path.next_hop(&nexus), Some(nexus));
+    }
+}
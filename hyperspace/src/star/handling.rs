@@ -0,0 +1,321 @@
+use crate::star::StarErr;
+use starlane_space::wave::{HandlingKind, Priority, SignalCore, Wave, WaveId, WaveVariantDef};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashSet};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering as AtomicOrdering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::Notify;
+
+/// transports waiting to be sent into the hyperway.  Transports are released in
+/// [`Priority`] order and transports of equal priority are released in the order
+/// they were pushed
+#[derive(Clone)]
+pub struct OutboundQueue {
+    heap: Arc<Mutex<BinaryHeap<Outbound>>>,
+    notify: Arc<Notify>,
+    seq: Arc<AtomicU64>,
+}
+
+impl OutboundQueue {
+    pub fn new() -> Self {
+        Self {
+            heap: Arc::new(Mutex::new(BinaryHeap::new())),
+            notify: Arc::new(Notify::new()),
+            seq: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    pub fn push(&self, transport: WaveVariantDef<SignalCore>) {
+        let outbound = Outbound {
+            priority: transport.handling.priority.clone(),
+            seq: self.seq.fetch_add(1, AtomicOrdering::Relaxed),
+            transport,
+        };
+        self.heap.lock().unwrap().push(outbound);
+        self.notify.notify_one();
+    }
+
+    /// wait for the most urgent transport
+    pub async fn pop(&self) -> WaveVariantDef<SignalCore> {
+        loop {
+            if let Some(outbound) = self.heap.lock().unwrap().pop() {
+                return outbound.transport;
+            }
+            self.notify.notified().await;
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.lock().unwrap().len()
+    }
+}
+
+impl Default for OutboundQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+struct Outbound {
+    priority: Priority,
+    seq: u64,
+    transport: WaveVariantDef<SignalCore>,
+}
+
+impl Eq for Outbound {}
+
+impl PartialEq for Outbound {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority && self.seq == other.seq
+    }
+}
+
+impl PartialOrd for Outbound {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Outbound {
+    // BinaryHeap pops the greatest element first, so the most urgent priority
+    // (which sorts lowest) and then the oldest sequence must compare as greatest
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .priority
+            .cmp(&self.priority)
+            .then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+/// on-disk record of [`HandlingKind::Durable`] waves that have not yet been delivered.
+/// A directed wave is journaled when it enters gravity and is cleared when its reflection
+/// returns (or for a Signal when it has been handed off).  Whatever remains in the journal
+/// when a star restarts is replayed back into gravity.  The ids of journaled waves are also
+/// kept in memory so that clearing a wave that was never journaled does not touch the disk
+#[derive(Clone)]
+pub struct DurableJournal {
+    dir: PathBuf,
+    recorded: Arc<Mutex<HashSet<WaveId>>>,
+}
+
+impl DurableJournal {
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        Self {
+            dir: dir.into(),
+            recorded: Arc::new(Mutex::new(HashSet::new())),
+        }
+    }
+
+    pub fn is_durable(wave: &Wave) -> bool {
+        wave.is_directed() && wave.handling().kind == HandlingKind::Durable
+    }
+
+    fn path(&self, id: &WaveId) -> PathBuf {
+        self.dir.join(format!("{}.wave", id.uuid().to_string()))
+    }
+
+    pub async fn record(&self, wave: &Wave) -> Result<(), StarErr> {
+        tokio::fs::create_dir_all(&self.dir)
+            .await
+            .map_err(StarErr::journal)?;
+        let data = bincode::serialize(wave).map_err(StarErr::journal)?;
+        tokio::fs::write(self.path(&wave.id()), data)
+            .await
+            .map_err(StarErr::journal)?;
+        self.recorded.lock().unwrap().insert(wave.id());
+        Ok(())
+    }
+
+    pub async fn clear(&self, id: &WaveId) -> Result<(), StarErr> {
+        if !self.recorded.lock().unwrap().remove(id) {
+            return Ok(());
+        }
+        match tokio::fs::remove_file(self.path(id)).await {
+            Ok(_) => Ok(()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(StarErr::journal(err)),
+        }
+    }
+
+    /// clear the journal entry of the directed wave that `reflected` is a reflection of
+    pub async fn reflected(&self, reflected: &Wave) -> Result<(), StarErr> {
+        if !reflected.is_reflected() {
+            return Ok(());
+        }
+        let reflected = reflected.clone().to_reflected()?;
+        self.clear(reflected.reflection_of()).await
+    }
+
+    /// clear the entry of a durable Signal carried by `transport` once the hyperway has
+    /// accepted it. A Signal is never reflected so nothing else would ever clear it (a
+    /// Signal to a particle on another star would otherwise be replayed on every restart).
+    /// Other directed waves stay journaled until their reflection returns
+    pub async fn handed_off(&self, transport: &WaveVariantDef<SignalCore>) -> Result<(), StarErr> {
+        if let Ok(wave) = transport.clone().unwrap_from_transport() {
+            if let Wave::Signal(signal) = &wave {
+                if Self::is_durable(&wave) {
+                    self.clear(&signal.id).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// every wave still waiting for delivery
+    pub async fn pending(&self) -> Result<Vec<Wave>, StarErr> {
+        let mut waves = vec![];
+        let mut entries = match tokio::fs::read_dir(&self.dir).await {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(waves),
+            Err(err) => return Err(StarErr::journal(err)),
+        };
+        while let Some(entry) = entries.next_entry().await.map_err(StarErr::journal)? {
+            let path = entry.path();
+            if path.extension().map_or(true, |ext| ext != "wave") {
+                continue;
+            }
+            let data = tokio::fs::read(&path).await.map_err(StarErr::journal)?;
+            let wave: Wave = bincode::deserialize(data.as_slice()).map_err(StarErr::journal)?;
+            self.recorded.lock().unwrap().insert(wave.id());
+            waves.push(wave);
+        }
+        Ok(waves)
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use crate::star::handling::{DurableJournal, OutboundQueue};
+    use crate::test_util::temp_dir;
+    use starlane_space::loc::{Layer, ToSurface};
+    use starlane_space::point::Point;
+    use starlane_space::substance::Substance;
+    use starlane_space::wave::core::http2::StatusCode;
+    use starlane_space::wave::core::hyper::HypMethod;
+    use starlane_space::wave::{
+        BounceProto, DirectedProto, Handling, HandlingKind, Priority, Wave,
+    };
+    use std::str::FromStr;
+
+    fn transport(priority: Priority) -> Wave {
+        let mut proto = DirectedProto::signal();
+        proto.from(Point::root().to_surface());
+        proto.to(Point::root().to_surface());
+        proto.method(HypMethod::Transport);
+        proto.handling(Handling {
+            priority,
+            ..Default::default()
+        });
+        proto.build().unwrap().to_wave()
+    }
+
+    #[tokio::test]
+    pub async fn test_outbound_priority() {
+        let queue = OutboundQueue::new();
+        for priority in [Priority::Low, Priority::Med, Priority::Hyper, Priority::Med] {
+            queue.push(transport(priority).to_signal().unwrap());
+        }
+        assert_eq!(queue.len(), 4);
+
+        assert_eq!(queue.pop().await.handling.priority, Priority::Hyper);
+        assert_eq!(queue.pop().await.handling.priority, Priority::Med);
+        assert_eq!(queue.pop().await.handling.priority, Priority::Med);
+        assert_eq!(queue.pop().await.handling.priority, Priority::Low);
+        assert_eq!(queue.len(), 0);
+    }
+
+    #[tokio::test]
+    pub async fn test_durable_journal() {
+        let tmp = temp_dir("journal");
+        let dir = tmp.path().to_path_buf();
+        let journal = DurableJournal::new(dir.clone());
+        assert!(journal.pending().await.unwrap().is_empty());
+
+        let mut ping = DirectedProto::ping();
+        ping.from(Point::root().to_surface());
+        ping.to(Point::root().to_surface());
+        ping.method(HypMethod::Assign);
+        ping.handling(Handling {
+            kind: HandlingKind::Durable,
+            ..Default::default()
+        });
+        let ping = ping.build().unwrap();
+        let wave = ping.clone().to_wave();
+        assert!(DurableJournal::is_durable(&wave));
+
+        journal.record(&wave).await.unwrap();
+        let pending = journal.pending().await.unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending.first().unwrap().id(), wave.id());
+
+        let mut pong = match ping.reflected_proto() {
+            BounceProto::Reflected(pong) => pong,
+            BounceProto::Absorbed => panic!("expected a ping to be reflected"),
+        };
+        pong.from(Point::root().to_surface());
+        pong.status = Some(StatusCode::from_u16(200).unwrap());
+        pong.body(Substance::Empty).unwrap();
+        pong.intended(Point::root().to_surface());
+        let pong = pong.build().unwrap().to_wave();
+
+        // a journal that has not recorded the ping leaves the entry in place
+        DurableJournal::new(dir.clone())
+            .reflected(&pong)
+            .await
+            .unwrap();
+        assert_eq!(journal.pending().await.unwrap().len(), 1);
+
+        journal.reflected(&pong).await.unwrap();
+        assert!(journal.pending().await.unwrap().is_empty());
+    }
+
+    /// a durable Signal to a particle on another star is never reflected, its entry is
+    /// cleared once the transport carrying it is handed to the hyperway
+    #[tokio::test]
+    pub async fn test_cross_star_signal() {
+        let tmp = temp_dir("journal-hop");
+        let dir = tmp.path().to_path_buf();
+        let journal = DurableJournal::new(dir.clone());
+
+        let here = Point::from_str("GLOBAL::star:a").unwrap();
+        let there = Point::from_str("GLOBAL::star:b").unwrap();
+        let durable = |wave: &mut DirectedProto| {
+            wave.from(here.push("particle").unwrap().to_surface());
+            wave.to(there.push("particle").unwrap().to_surface());
+            wave.method(HypMethod::Assign);
+            wave.handling(Handling {
+                kind: HandlingKind::Durable,
+                ..Default::default()
+            });
+        };
+        let transport = |wave: Wave| {
+            let mut transport = wave.wrap_in_transport(
+                here.to_surface().with_layer(Layer::Gravity),
+                there.to_surface().with_layer(Layer::Core),
+            );
+            transport.from(here.to_surface());
+            transport.build().unwrap().to_signal().unwrap()
+        };
+
+        let mut signal = DirectedProto::signal();
+        durable(&mut signal);
+        let signal = signal.build().unwrap().to_wave();
+        let mut ping = DirectedProto::ping();
+        durable(&mut ping);
+        let ping = ping.build().unwrap().to_wave();
+
+        journal.record(&signal).await.unwrap();
+        journal.record(&ping).await.unwrap();
+        assert_eq!(journal.pending().await.unwrap().len(), 2);
+
+        // the ping stays journaled until its reflection returns
+        journal.handed_off(&transport(ping.clone())).await.unwrap();
+        journal.handed_off(&transport(signal)).await.unwrap();
+
+        // a restarted star replays only the ping
+        let pending = DurableJournal::new(dir.clone()).pending().await.unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending.first().unwrap().id(), ping.id());
+    }
+}
@@ -0,0 +1,82 @@
+use dashmap::DashMap;
+use starlane_space::loc::Surface;
+use starlane_space::particle::Watch;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// the watchers of the particles located on a star.  A [`Watch`] ripple that arrives
+/// at the star holding `Watch::point` is expanded into a ripple to every watching [`Surface`]
+#[derive(Clone)]
+pub struct StarWatchers {
+    map: Arc<DashMap<Watch, HashSet<Surface>>>,
+}
+
+impl StarWatchers {
+    pub fn new() -> Self {
+        Self {
+            map: Arc::new(DashMap::new()),
+        }
+    }
+
+    pub fn watch(&self, watch: Watch, watcher: Surface) {
+        self.map.entry(watch).or_default().insert(watcher);
+    }
+
+    pub fn unwatch(&self, watch: &Watch, watcher: &Surface) {
+        let empty = match self.map.get_mut(watch) {
+            Some(mut watchers) => {
+                watchers.remove(watcher);
+                watchers.is_empty()
+            }
+            None => false,
+        };
+        if empty {
+            self.map.remove_if(watch, |_, watchers| watchers.is_empty());
+        }
+    }
+
+    pub fn watchers(&self, watch: &Watch) -> Vec<Surface> {
+        match self.map.get(watch) {
+            Some(watchers) => watchers.iter().cloned().collect(),
+            None => vec![],
+        }
+    }
+}
+
+impl Default for StarWatchers {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use crate::star::watchers::StarWatchers;
+    use starlane_space::loc::ToSurface;
+    use starlane_space::particle::{Aspect, Watch};
+    use starlane_space::point::Point;
+    use std::str::FromStr;
+
+    #[test]
+    pub fn test_watch_unwatch() {
+        let watchers = StarWatchers::new();
+        let app = Point::from_str("localhost:app").unwrap();
+        let ui = Point::from_str("localhost:ui").unwrap().to_surface();
+        let state = Watch {
+            point: app.clone(),
+            aspect: Aspect::State,
+        };
+        let property = Watch {
+            point: app,
+            aspect: Aspect::Property,
+        };
+
+        watchers.watch(state.clone(), ui.clone());
+        watchers.watch(state.clone(), ui.clone());
+        assert_eq!(watchers.watchers(&state), vec![ui.clone()]);
+        assert!(watchers.watchers(&property).is_empty());
+
+        watchers.unwatch(&state, &ui);
+        assert!(watchers.watchers(&state).is_empty());
+    }
+}
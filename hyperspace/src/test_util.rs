@@ -0,0 +1,11 @@
+use tempfile::TempDir;
+
+/// a new empty directory for a test's files, named after `name`.  Every call gets its own
+/// directory so tests running at the same time never share files and the directory is
+/// removed when the returned [TempDir] is dropped, even when the test panics
+pub fn temp_dir(name: &str) -> TempDir {
+    tempfile::Builder::new()
+        .prefix(&format!("starlane-{}-", name))
+        .tempdir()
+        .expect("temp dir")
+}
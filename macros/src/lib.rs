@@ -353,7 +353,7 @@ fn rtn_type(output: &ReturnType) -> TokenStream2 {
                                 quote! {
                                  use #crt::err::CoreReflector;
                                  match result {
-                                     Ok(rtn) => #crt::wave::core::CoreBounce::Reflected(starlane::wave::core::ReflectedCore::ok_body(rtn)),
+                                     Ok(rtn) => #crt::wave::core::CoreBounce::Reflected(#crt::wave::core::ReflectedCore::ok_body(rtn)),
                                      Err(err) => #crt::wave::core::CoreBounce::Reflected(err.as_reflected_core())
                                  }
                                 }
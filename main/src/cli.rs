@@ -1,8 +1,10 @@
 use clap::clap_derive::{Args, Subcommand};
 use clap::Parser;
-use starlane_base::env::STARLANE_HOME;
+use starlane_base::env::{STARLANE_CONTROL_SOCKET, STARLANE_HOME};
 use starlane_hyperspace::driver::control::{ControlCliSession, ControlClient};
 use starlane_hyperspace::hyperlane::tcp::HyperlaneTcpClient;
+#[cfg(unix)]
+use starlane_hyperspace::hyperlane::unix::HyperlaneUnixClient;
 use starlane_hyperspace::hyperlane::HyperwayEndpointFactory;
 use starlane_space::command::{CmdTransfer, RawCommand};
 use starlane_space::err::SpaceErr;
@@ -11,7 +13,7 @@ use starlane_space::parse::util::new_span;
 use starlane_space::parse::util::result;
 use starlane_space::parse::{upload_blocks, SkewerCase};
 use starlane_space::point::Point;
-use starlane_space::substance::Substance;
+use starlane_space::substance::{Substance, SubstanceMap, Token};
 use starlane_space::wave::core::ReflectedCore;
 use std::fs::File;
 use std::io::{Cursor, Read, Seek, Write};
@@ -87,6 +89,18 @@ pub struct TermArgs {
 
     #[arg(long)]
     history_log: Option<String>,
+
+    /// skip verification of the server's certificate
+    #[arg(long)]
+    insecure: bool,
+
+    /// log in as this User of the control's UserBase, the password is prompted for
+    #[arg(long)]
+    user: Option<String>,
+
+    /// log in with an api token of the form `<username>:<api-token>`
+    #[arg(long, conflicts_with = "user")]
+    token: Option<String>,
 }
 
 impl Default for TermArgs {
@@ -95,6 +109,9 @@ impl Default for TermArgs {
             host: None,
             certs: None,
             history_log: None,
+            insecure: false,
+            user: None,
+            token: None,
         }
     }
 }
@@ -115,7 +132,34 @@ pub async fn term(args: TermArgs) -> Result<(), SpaceErr> {
         Some(host) => host.clone(),
     };
 
-    let session = Session::new(host, certs).await?;
+    let auth = match (&args.user, &args.token) {
+        (Some(username), _) => {
+            let password = cliclack::password(format!("password for '{}'", username))
+                .mask('▪')
+                .interact()?;
+            login(username.as_str(), password.as_str())
+        }
+        (None, Some(token)) => Substance::Token(Token::new(token)),
+        (None, None) => Substance::Empty,
+    };
+    let knock = Knock {
+        auth: Box::new(auth),
+        ..Default::default()
+    };
+
+    // a local session without credentials tries the control socket before the tcp port
+    let local = match (&args.host, &*knock.auth) {
+        (None, Substance::Empty) => {
+            Session::new_local(STARLANE_CONTROL_SOCKET.to_string(), knock.clone())
+                .await
+                .ok()
+        }
+        _ => None,
+    };
+    let session = match local {
+        Some(session) => session,
+        None => Session::new(host, certs, knock, !args.insecure).await?,
+    };
 
     let mut rl = rustyline::DefaultEditor::new().unwrap();
     rl.add_history_entry(history_log.as_str());
@@ -137,25 +181,71 @@ pub async fn term(args: TermArgs) -> Result<(), SpaceErr> {
     }
 }
 
+/// the auth of a control [`Knock`] that logs in with a username and password, see
+/// [`starlane_hyperspace::hyperlane::CredentialHyperAuthenticator`]
+pub fn login(username: &str, password: &str) -> Substance {
+    let mut map = SubstanceMap::new();
+    map.insert(
+        "username".to_string(),
+        Substance::Text(username.to_string()),
+    );
+    map.insert(
+        "password".to_string(),
+        Substance::Text(password.to_string()),
+    );
+    Substance::Map(map)
+}
+
 pub struct Session {
     pub client: ControlClient,
     pub cli: ControlCliSession,
 }
 
 impl Session {
-    pub async fn new(host: String, certs: String) -> Result<Self, SpaceErr> {
+    /// connect to the control port of `host` with `knock`, the server's certificate is checked
+    /// against `certs` unless `verify` is false
+    pub async fn new(
+        host: String,
+        certs: String,
+        knock: Knock,
+        verify: bool,
+    ) -> Result<Self, SpaceErr> {
         let logger = logger!(Point::from_str("starlane-cli")?);
         let tcp_client: Box<dyn HyperwayEndpointFactory> = Box::new(HyperlaneTcpClient::new(
             format!("{}:{}", host, 4343),
             certs,
-            Knock::default(),
-            false,
+            knock,
+            verify,
             logger,
         ));
 
-        let client = ControlClient::new(tcp_client)?;
+        Self::connect(tcp_client, Duration::from_secs(30)).await
+    }
+
+    /// connect through the unix domain socket at `path` with `knock`
+    #[cfg(unix)]
+    pub async fn new_local(path: String, knock: Knock) -> Result<Self, SpaceErr> {
+        if !Path::new(&path).exists() {
+            return Err(SpaceErr::str(format!("no control socket at '{}'", path)));
+        }
+        let logger = logger!(Point::from_str("starlane-cli")?);
+        let unix_client: Box<dyn HyperwayEndpointFactory> =
+            Box::new(HyperlaneUnixClient::new(path, knock, logger));
+        Self::connect(unix_client, Duration::from_secs(5)).await
+    }
+
+    #[cfg(not(unix))]
+    pub async fn new_local(path: String, knock: Knock) -> Result<Self, SpaceErr> {
+        Err(SpaceErr::str("control sockets are only available on unix"))
+    }
+
+    async fn connect(
+        factory: Box<dyn HyperwayEndpointFactory>,
+        timeout: Duration,
+    ) -> Result<Self, SpaceErr> {
+        let client = ControlClient::new(factory)?;
 
-        client.wait_for_ready(Duration::from_secs(30)).await?;
+        client.wait_for_ready(timeout).await?;
         client.wait_for_greet().await?;
 
         let cli = client.new_cli_session().await?;
@@ -11,23 +11,35 @@ use starlane_space::loc::{MachineName, StarKey};
 use std::sync::Arc;
 use starlane_hyperspace::driver::base::BaseDriverFactory;
 use starlane_hyperspace::driver::control::ControlDriverFactory;
+use starlane_hyperspace::driver::filestore::{FileDriverFactory, FileStoreDriverFactory};
+use starlane_hyperspace::driver::mechtron::{MechtronDriverFactory, MechtronHostFactory};
 use starlane_hyperspace::driver::root::RootDriverFactory;
 use starlane_hyperspace::driver::space::SpaceDriverFactory;
+use starlane_hyperspace::driver::web::WebDriverFactory;
 use std::fs;
 use std::path::Path;
 use std::str::FromStr;
 use port_check::is_local_ipv4_port_free;
 use anyhow::anyhow;
-use starlane_hyperspace::hyperlane::tcp::{CertGenerator, HyperlaneTcpServer};
-use starlane_hyperspace::shutdown::panic_shutdown;
-use starlane_macros::push_loc;
+use starlane_hyperspace::hyperlane::tcp::{
+    CertGenerator, HyperlaneTcpServer, HyperlaneTcpServerConfig,
+};
+#[cfg(unix)]
+use starlane_hyperspace::hyperlane::unix::{HyperlaneUnixServer, HyperlaneUnixServerConfig};
+use starlane_macros::{logger, push_loc};
 use starlane_space::point::Point;
-use base::env::{config_path, STARLANE_CONTROL_PORT};
+use base::env::{
+    config_path, STARLANE_CACHE_DIR, STARLANE_CONTROL_PORT, STARLANE_CONTROL_SOCKET,
+    STARLANE_FILESTORE,
+};
 use base::foundation::StarlaneConfig;
 use hyperspace::base::BaseSub;
 use hyperspace::registry;
-use hyperspace::registry::Registry;
-use hyperspace::service::STARLANE_DATA_DIR;
+use hyperspace::registry::mem::registry::MemoryRegistry;
+use hyperspace::registry::{Registry, RegistryKind, RegistryWrapper};
+use starlane_platform_for_sqlite_registry::SqliteRegistry;
+use starlane_space::log::Logger;
+use hyperspace::service::{native_service_conf, STARLANE_DATA_DIR};
 use starlane_foundation_for_docker_desktop::DockerDaemonFoundation;
 
 pub mod prelude {
@@ -76,9 +88,12 @@ mod concrete {
         config: PlatformConfig
     }
 
-    #[derive(Clone,Debug,)]
+    #[derive(Clone)]
     pub struct PlatformConfig{
         kind: PlatformKind,
+        /// the [StarlaneConfig] whose `registry` selects the
+        /// [RegistryKind](hyperspace::registry::RegistryKind) built by [crate::starlane::registry]
+        starlane: StarlaneConfig,
     }
 
     impl hyperspace::base::PlatformConfig for PlatformConfig {
@@ -93,7 +108,7 @@ mod concrete {
         }
 
         fn registry(&self) -> &Self::RegistryConfig {
-            todo!()
+            &self.starlane
         }
 
         fn home(&self) -> &String {
@@ -126,6 +141,54 @@ pub mod config {
     pub trait RegistryConfig:  registry::RegistryConfig { }
 }
 
+/// build the [Registry] selected by [registry::RegistryConfig::kind]
+pub async fn registry<C>(config: &C, logger: Logger) -> Result<Registry, HypErr>
+where
+    C: registry::RegistryConfig + ?Sized,
+{
+    let registry: Registry = match config.kind() {
+        RegistryKind::Postgres => Err(anyhow!(
+            "a postgres registry must be provisioned by the Foundation's PostgresDatabase provider"
+        ))?,
+        RegistryKind::Sqlite { path } => {
+            if let Some(dir) = Path::new(path).parent() {
+                fs::create_dir_all(dir)?;
+            }
+            Arc::new(SqliteRegistry::new(path, logger.clone()).await?)
+        }
+        RegistryKind::Memory => Arc::new(MemoryRegistry::new()),
+    };
+
+    Ok(Arc::new(RegistryWrapper::from_config(config, registry, &logger)?))
+}
+
+/// Mechtron guests are compiled with wasmer and their wasm is fetched from the platform's
+/// [`Artifacts`], the same artifacts their `MechtronConfig` is loaded from. Compiled modules
+/// are kept in `STARLANE_CACHE_DIR/wasm`
+#[cfg(feature = "wasm")]
+fn mechtron_hosts(artifacts: &Artifacts) -> Arc<dyn MechtronHostFactory> {
+    use starlane_hyperspace::host::wasm::cache::WasmModuleMemCache;
+    use starlane_hyperspace::host::wasm::{WasmHostConfig, WasmMechtronHostFactory, WasmService};
+
+    let ser = Path::new(STARLANE_CACHE_DIR.as_str()).join("wasm");
+    let cache = match fs::create_dir_all(&ser) {
+        Ok(_) => WasmModuleMemCache::new_with_ser(ser),
+        // without a cache dir every module is compiled again after a restart
+        Err(_) => WasmModuleMemCache::new(),
+    };
+    let service = WasmService::new(Arc::new(artifacts.clone()), Box::new(cache));
+    Arc::new(WasmMechtronHostFactory::new(
+        service,
+        WasmHostConfig::default(),
+    ))
+}
+
+/// this build of Starlane cannot run WebAssembly so every Mechtron assign fails
+#[cfg(not(feature = "wasm"))]
+fn mechtron_hosts(_: &Artifacts) -> Arc<dyn MechtronHostFactory> {
+    Arc::new(starlane_hyperspace::driver::mechtron::NoDiceMechtronHostFactory)
+}
+
 #[derive(Clone)]
 pub struct Starlane {
     config: StarlaneConfig,
@@ -139,7 +202,17 @@ impl  Starlane  {
         config: StarlaneConfig,
         foundation: DockerDaemonFoundation,
     ) -> Result<Starlane, HypErr> {
-        todo!();
+        let artifacts = Artifacts::just_builtins();
+        let logger = logger!(&Point::global_registry());
+        let registry = registry(&config, logger).await?;
+
+        Ok(Self {
+            config,
+            artifacts,
+            registry,
+        })
+
+        // a postgres registry is selected the same way once the foundation can provision it:
         /*
         let artifacts = Artifacts::just_builtins();
 
@@ -168,9 +241,8 @@ impl  Starlane  {
 
         let logger = logger!(&Point::global_registry());
 
-        let registry = Arc::new(RegistryWrapper::new(Arc::new(
-            PostgresRegistry::new(handle, Box::new(lookups), logger).await?,
-        )));
+        let postgres = Arc::new(PostgresRegistry::new(handle, Box::new(lookups), logger.clone()).await?);
+        let registry = Arc::new(RegistryWrapper::from_config(&config, postgres, &logger)?);
 
         Ok(Self {
             config,
@@ -203,6 +275,10 @@ where
         STARLANE_DATA_DIR.clone()
     }
 
+    fn web_port(&self) -> Result<u16, Self::Err> {
+        Ok(self.config.web.port)
+    }
+
     fn star_auth(&self, star: &StarKey) -> Result<Self::StarAuth, Self::Err> {
         Ok(AnonHyperAuthenticator::new())
     }
@@ -215,7 +291,10 @@ where
     }
 
     fn machine_template(&self) -> MachineTemplate {
-        MachineTemplate::default()
+        match STARLANE_FILESTORE.as_str() {
+            "native" => MachineTemplate::new(native_service_conf()),
+            _ => MachineTemplate::default(),
+        }
     }
 
     fn machine_name(&self) -> MachineName {
@@ -241,12 +320,12 @@ where
             }
             StarSub::Nexus => {}
             StarSub::Maelstrom => {
-                /*                builder.add_post(Arc::new(HostDriverFactory::new()));
-                               builder.add_post(Arc::new(MechtronDriverFactory::new()));
-
-                */
+                //                builder.add_post(Arc::new(HostDriverFactory::new()));
+                builder.add_post(Arc::new(MechtronDriverFactory::new(mechtron_hosts(&self.artifacts))));
             }
             StarSub::Scribe => {
+                builder.add_post(Arc::new(FileStoreDriverFactory::new(DriverAvail::External)));
+                builder.add_post(Arc::new(FileDriverFactory::new()));
                 /*builder.add_post(Arc::new(RepoDriverFactory::new()));
                 builder.add_post(Arc::new(BundleSeriesDriverFactory::new()));
                 builder.add_post(Arc::new(BundleDriverFactory::new()));
@@ -255,7 +334,7 @@ where
                  */
             }
             StarSub::Jump => {
-                //builder.add_post(Arc::new(WebDriverFactory::new()));
+                builder.add_post(Arc::new(WebDriverFactory::new(self.config.web.clone())));
                 // builder.add_post(Arc::new(ControlDriverFactory::new()));
             }
             StarSub::Fold => {}
@@ -279,12 +358,12 @@ where
         self.artifacts.clone()
     }
 
-    async fn start_services(&self, gate: &Arc<HyperGateSelector>) {
+    async fn start_services(&self, gate: &Arc<HyperGateSelector>) -> Result<(), Self::Err> {
         let dir = match dirs::home_dir() {
             None => ".starlane/localhost/certs".to_string(),
             Some(path) => format!("{}/.starlane/localhost/certs", path.display()),
         };
-        fs::create_dir_all(dir.as_str());
+        fs::create_dir_all(dir.as_str())?;
 
         let cert = format!("{}/cert.der", dir.as_str());
         let key = format!("{}/key.der", dir.as_str());
@@ -293,26 +372,37 @@ where
 
         if !cert_path.exists() || !key_path.exists() {
             CertGenerator::gen(vec!["localhost".to_string()])
-                .unwrap()
+                .map_err(|err| anyhow!("could not generate control certificates: {}", err))?
                 .write_to_dir(dir.clone())
-                .await
-                .unwrap();
+                .await?;
         };
 
         let logger = push_loc!((self.logger(), Point::from_str("control-blah").unwrap()));
 
         if !is_local_ipv4_port_free(STARLANE_CONTROL_PORT.clone()) {
-            panic_shutdown(format!(
+            Err(anyhow!(
                 "starlane port '{}' is being used by another process",
                 STARLANE_CONTROL_PORT.to_string()
-            ));
+            ))?;
         }
 
-        let server =
-            HyperlaneTcpServer::new(STARLANE_CONTROL_PORT.clone(), dir, gate.clone(), logger)
-                .await
-                .unwrap();
-        server.start().unwrap();
+        let config = HyperlaneTcpServerConfig::new(STARLANE_CONTROL_PORT.clone(), dir)
+            .with_bind(self.config.control_bind.clone());
+        let config = match self.config.control_client_auth.clone() {
+            None => config,
+            Some(client_auth) => config.with_client_auth(client_auth),
+        };
+        let server = HyperlaneTcpServer::new(config, gate.clone(), logger.clone()).await?;
+        server.start()?;
+
+        #[cfg(unix)]
+        {
+            let config = HyperlaneUnixServerConfig::new(STARLANE_CONTROL_SOCKET.as_str());
+            let server = HyperlaneUnixServer::new(config, gate.clone(), logger).await?;
+            server.start()?;
+        }
+
+        Ok(())
     }
 
 
@@ -395,3 +485,26 @@ mod partial {
 mod platform {
     pub struct Platform {}
 }
+
+#[cfg(test)]
+pub mod test {
+    use crate::starlane::registry;
+    use base::foundation::StarlaneConfig;
+    use hyperspace::registry::RegistryKind;
+    use starlane_space::log::Logger;
+
+    #[tokio::test]
+    pub async fn test_default_registry() {
+        let mut config = StarlaneConfig::default();
+        assert!(matches!(config.registry, RegistryKind::Sqlite { .. }));
+
+        // the data dir of the sqlite file is created on demand
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data").join("registry.sqlite");
+        config.registry = RegistryKind::Sqlite {
+            path: path.display().to_string(),
+        };
+        registry(&config, Logger::default()).await.unwrap();
+        assert!(path.exists());
+    }
+}
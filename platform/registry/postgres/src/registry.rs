@@ -36,7 +36,7 @@ use starlane_space::selector::specific::{
     ProductSelector, ProviderSelector, VariantSelector, VendorSelector,
 };
 use starlane_space::selector::{
-    ExactPointSeg, KindBaseSelector, PointHierarchy, PointKindSeg, PointSegSelector, Selector,
+    ExactPointSeg, KindBaseSelector, KindSelector, PointHierarchy, PointKindSeg, PointSegSelector, Selector,
     SubKindSelector,
 };
 use starlane_space::status::Handle;
@@ -379,7 +379,7 @@ impl RegistryApi for PostgresRegistry {
                         false => 0,
                     };
 
-                    let statement = format!("INSERT INTO properties (resource_id,key,value,lock) VALUES ((SELECT id FROM particles WHERE parent='{}' AND point_segment='{}'),'{}' ,'{}','{}') ON CONFLICT(resource_id,key) DO UPDATE SET value='{}' WHERE lock=false", parent, point_segment, key.to_string(), value.to_string(), value.to_string(), lock);
+                    let statement = format!("INSERT INTO properties (resource_id,key,value,lock) VALUES ((SELECT id FROM particles WHERE parent='{}' AND point_segment='{}'),'{}' ,'{}','{}') ON CONFLICT(resource_id,key) DO UPDATE SET value='{}' WHERE properties.lock=false", parent, point_segment, key.to_string(), value.to_string(), lock, value.to_string());
                     trans.execute(statement.as_str()).await?;
                 }
                 PropertyMod::UnSet(key) => {
@@ -520,8 +520,14 @@ impl RegistryApi for PostgresRegistry {
             }
 
             let mut conn = self.handle.acquire().await?;
-            let statement = format!("DELETE FROM particles WHERE point IN [{}]", points);
-            sqlx::query(statement.as_str()).execute(&mut *conn).await?;
+            let mut trans = conn.begin().await?;
+            let statement = format!("DELETE FROM properties WHERE resource_id IN (SELECT id FROM particles WHERE point IN ({}))", points);
+            trans.execute(statement.as_str()).await?;
+            let statement = format!("DELETE FROM access_grants WHERE by_particle IN (SELECT id FROM particles WHERE point IN ({}))", points);
+            trans.execute(statement.as_str()).await?;
+            let statement = format!("DELETE FROM particles WHERE point IN ({})", points);
+            trans.execute(statement.as_str()).await?;
+            trans.commit().await?;
         }
 
         Ok(list)
@@ -532,6 +538,10 @@ impl RegistryApi for PostgresRegistry {
         // build a 'matching so far' query.  Here we will find every child that matches the subselect
         // these matches are used to then query children for additional matches if there are more hops.
         // all of these matches will be filtered to see if they match the ENTIRE select before returning results.
+        if sub_select.hops.is_empty() {
+            return Ok(vec![]);
+        }
+
         let mut params: Vec<String> = vec![];
         let mut where_clause = String::new();
         let mut index = 1;
@@ -539,7 +549,6 @@ impl RegistryApi for PostgresRegistry {
         params.push(sub_select.point.to_string());
 
         if let Option::Some(hop) = sub_select.hops.first() {
-            let x = &hop.kind_selector;
             match &hop.segment_selector {
                 PointSegSelector::Exact(exact) => {
                     index = index + 1;
@@ -556,7 +565,14 @@ impl RegistryApi for PostgresRegistry {
                 _ => {}
             }
 
-            match &hop.kind_selector.base {
+            // the particles a recursive hop passes through may be of any kind, the kind
+            // is filtered later when the ENTIRE select is matched
+            let kind_selector = match hop.segment_selector.is_recursive() {
+                true => KindSelector::any(),
+                false => hop.kind_selector.clone(),
+            };
+
+            match &kind_selector.base {
                 KindBaseSelector::Always => {}
                 KindBaseSelector::Exact(kind) => {
                     index = index + 1;
@@ -566,9 +582,9 @@ impl RegistryApi for PostgresRegistry {
                 KindBaseSelector::Never => {}
             }
 
-            match &hop.kind_selector.base {
+            match &kind_selector.base {
                 KindBaseSelector::Always => {}
-                KindBaseSelector::Exact(kind) => match &hop.kind_selector.sub {
+                KindBaseSelector::Exact(kind) => match &kind_selector.sub {
                     SubKindSelector::Always => {}
                     SubKindSelector::Exact(sub) => {
                         index = index + 1;
@@ -581,7 +597,7 @@ impl RegistryApi for PostgresRegistry {
                 KindBaseSelector::Never => {}
             }
 
-            match &hop.kind_selector.specific {
+            match &kind_selector.specific {
                 ValuePattern::Always => {}
                 ValuePattern::Never => {}
                 ValuePattern::Pattern(specific) => {
@@ -1505,6 +1521,12 @@ pub mod test {
         Ok(())
     }
 
+    #[tokio::test]
+    pub async fn test_conformance() -> Result<(), RegErr> {
+        let registry = registry().await?;
+        starlane_hyperspace::registry::conformance::conformance(registry).await
+    }
+
     #[tokio::test]
     pub async fn test_access() -> Result<(), RegErr> {
         let registry = registry().await?;
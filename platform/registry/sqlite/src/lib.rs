@@ -0,0 +1,7 @@
+/// A [starlane_hyperspace::registry::RegistryApi] backed by an embedded SQLite database.
+///
+/// Unlike `starlane-platform-for-postgres-registry` the SQLite registry has no external
+/// service dependency which makes it suitable for small and edge deployments.
+pub mod registry;
+
+pub use registry::SqliteRegistry;
@@ -0,0 +1,1074 @@
+use async_trait::async_trait;
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions, SqliteRow};
+use sqlx::{Executor, Row, Sqlite, SqlitePool};
+use starlane_hyperspace::registry::err::RegErr;
+use starlane_hyperspace::registry::{Registration, RegistryApi};
+use starlane_macros::push_loc;
+use starlane_space::command::common::{PropertyMod, SetProperties};
+use starlane_space::command::direct::create::Strategy;
+use starlane_space::command::direct::delete::Delete;
+use starlane_space::command::direct::query::{Query, QueryResult};
+use starlane_space::command::direct::select::{Select, SelectIntoSubstance, SelectKind, SubSelect};
+use starlane_space::hyper::{ParticleLocation, ParticleRecord};
+use starlane_space::kind::{BaseKind, Kind, KindParts, Specific};
+use starlane_space::loc::{ToBaseKind, Version};
+use starlane_space::log::Logger;
+use starlane_space::parse::util::parse_errs;
+use starlane_space::parse::{CamelCase, Domain, SkewerCase};
+use starlane_space::particle::{Details, Properties, Property, Status, Stub};
+use starlane_space::point::Point;
+use starlane_space::security::{
+    Access, AccessGrant, AccessGrantKind, EnumeratedAccess, IndexedAccessGrant, Permissions,
+    PermissionsMask, PermissionsMaskKind, Privilege, Privileges,
+};
+use starlane_space::selector::specific::{
+    ProductSelector, ProviderSelector, VariantSelector, VendorSelector,
+};
+use starlane_space::selector::{
+    ExactPointSeg, KindBaseSelector, KindSelector, PointHierarchy, PointKindSeg, PointSegSelector,
+    Selector, SubKindSelector,
+};
+use starlane_space::substance::{Substance, SubstanceList};
+use starlane_space::util::ValuePattern;
+use starlane_space::HYPERUSER;
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+
+/// selects every column of `access_grants` except `by_particle` which is replaced by the
+/// [Point] of the granting particle
+const ACCESS_GRANTS: &str = "SELECT access_grants.id,access_grants.kind,access_grants.data,access_grants.on_point,access_grants.to_point,particles.point as by_particle FROM access_grants,particles";
+
+/// A [RegistryApi] persisted in an embedded SQLite database.
+///
+/// [SqliteRegistry] shares its schema concepts with `PostgresRegistry`: `particles`,
+/// `access_grants`, `labels`, `tags` & `properties` plus the `reset_mode` scorch guard.
+///
+/// No connection is held while [SqliteRegistry] recurses back into itself (i.e. [RegistryApi::access]
+/// and [RegistryApi::sub_select]) so the registry works with a single connection pool which is
+/// required for an in memory database.
+pub struct SqliteRegistry {
+    logger: Logger,
+    pool: SqlitePool,
+}
+
+impl SqliteRegistry {
+    /// open (or create) the SQLite registry database at `path`
+    pub async fn new<P>(path: P, logger: Logger) -> Result<Self, RegErr>
+    where
+        P: AsRef<Path>,
+    {
+        let options = SqliteConnectOptions::new()
+            .filename(path)
+            .create_if_missing(true)
+            .journal_mode(SqliteJournalMode::Wal)
+            .foreign_keys(true);
+
+        let pool = SqlitePoolOptions::new().connect_with(options).await?;
+
+        Self::with_pool(pool, logger).await
+    }
+
+    /// a registry that lives only as long as this [SqliteRegistry] instance
+    pub async fn memory(logger: Logger) -> Result<Self, RegErr> {
+        let options = SqliteConnectOptions::from_str("sqlite::memory:")?.foreign_keys(true);
+
+        // every connection to `sqlite::memory:` opens a different database, therefore the pool
+        // must keep exactly one connection alive for the life of the registry
+        let pool = SqlitePoolOptions::new()
+            .min_connections(1)
+            .max_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect_with(options)
+            .await?;
+
+        Self::with_pool(pool, logger).await
+    }
+
+    async fn with_pool(pool: SqlitePool, logger: Logger) -> Result<Self, RegErr> {
+        let logger = push_loc!((logger, Point::global_registry()));
+
+        let registry = Self {
+            pool,
+            logger: logger.clone(),
+        };
+
+        match registry.setup().await {
+            Ok(_) => {}
+            Err(err) => {
+                let message = err.to_string();
+                logger.error(format!("database setup failed {} ", message));
+                return Err(err);
+            }
+        }
+
+        Ok(registry)
+    }
+
+    async fn setup(&self) -> Result<(), RegErr> {
+        // reset mode of 'None' will not let the db be deleted
+        let mode = r#"CREATE TABLE IF NOT EXISTS reset_mode (
+         mode TEXT DEFAULT 'None' NOT NULL CHECK (mode IN ('None', 'Scorch')),
+         UNIQUE(mode)
+        )"#;
+
+        let default_mode = "INSERT OR IGNORE INTO reset_mode VALUES ('None')";
+
+        let particles = r#"CREATE TABLE IF NOT EXISTS particles (
+         id INTEGER PRIMARY KEY AUTOINCREMENT,
+         point TEXT NOT NULL,
+         point_segment TEXT NOT NULL,
+         parent TEXT NOT NULL,
+         base TEXT NOT NULL,
+         sub TEXT,
+         provider TEXT,
+         vendor TEXT,
+         product TEXT,
+         variant TEXT,
+         version TEXT,
+         version_variant TEXT,
+         star TEXT,
+         host TEXT,
+         status TEXT NOT NULL,
+         sequence INTEGER DEFAULT 0,
+         owner TEXT,
+         UNIQUE(point),
+         UNIQUE(parent,point_segment)
+        )"#;
+
+        let access_grants = r#"CREATE TABLE IF NOT EXISTS access_grants (
+         id INTEGER PRIMARY KEY AUTOINCREMENT,
+         kind TEXT NOT NULL,
+         data TEXT,
+         query_root TEXT NOT NULL,
+         on_point TEXT NOT NULL,
+         to_point TEXT NOT NULL,
+         by_particle INTEGER NOT NULL,
+         FOREIGN KEY (by_particle) REFERENCES particles (id)
+        )"#;
+
+        let labels = r#"CREATE TABLE IF NOT EXISTS labels (
+         id INTEGER PRIMARY KEY AUTOINCREMENT,
+         resource_id INTEGER NOT NULL,
+         key TEXT NOT NULL,
+         value TEXT,
+         UNIQUE(key,value),
+         FOREIGN KEY (resource_id) REFERENCES particles (id)
+        )"#;
+
+        // note that a tag may reference an point NOT in this database
+        // therefore it does not have a FOREIGN KEY constraint
+        let tags = r#"CREATE TABLE IF NOT EXISTS tags (
+         id INTEGER PRIMARY KEY AUTOINCREMENT,
+         parent TEXT NOT NULL,
+         tag TEXT NOT NULL,
+         point TEXT NOT NULL,
+         UNIQUE(tag)
+        )"#;
+
+        let properties = r#"CREATE TABLE IF NOT EXISTS properties (
+         id INTEGER PRIMARY KEY AUTOINCREMENT,
+         resource_id INTEGER NOT NULL,
+         key TEXT NOT NULL,
+         value TEXT NOT NULL,
+         lock BOOLEAN NOT NULL,
+         FOREIGN KEY (resource_id) REFERENCES particles (id),
+         UNIQUE(resource_id,key)
+        )"#;
+
+        let point_index =
+            "CREATE UNIQUE INDEX IF NOT EXISTS resource_point_index ON particles(point)";
+        let point_segment_parent_index = "CREATE UNIQUE INDEX IF NOT EXISTS resource_point_segment_parent_index ON particles(parent,point_segment)";
+        let access_grants_index =
+            "CREATE INDEX IF NOT EXISTS query_root_index ON access_grants(query_root)";
+
+        let mut trans = self.pool.begin().await?;
+        trans.execute(mode).await?;
+        trans.execute(default_mode).await?;
+        trans.execute(particles).await?;
+        trans.execute(access_grants).await?;
+        trans.execute(labels).await?;
+        trans.execute(tags).await?;
+        trans.execute(properties).await?;
+        trans.execute(point_index).await?;
+        trans.execute(point_segment_parent_index).await?;
+        trans.execute(access_grants_index).await?;
+        trans.commit().await?;
+
+        Ok(())
+    }
+
+    async fn access_grants(&self, query_root: &Point) -> Result<Vec<IndexedAccessGrant>, RegErr> {
+        let statement = format!(
+            "{} WHERE access_grants.query_root=? AND particles.id=access_grants.by_particle",
+            ACCESS_GRANTS
+        );
+        let access_grants = sqlx::query_as::<Sqlite, WrappedIndexedAccessGrant>(statement.as_str())
+            .bind(query_root.to_string())
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(access_grants.into_iter().map(|a| a.into()).collect())
+    }
+}
+
+#[async_trait]
+impl RegistryApi for SqliteRegistry {
+    async fn scorch<'a>(&'a self) -> Result<(), RegErr> {
+        self.logger.info("scorching database!");
+
+        let mut trans = self.pool.begin().await?;
+
+        let scorch: i64 = sqlx::query("SELECT count(*) FROM reset_mode WHERE mode='Scorch'")
+            .fetch_one(&mut *trans)
+            .await?
+            .get(0);
+
+        if scorch == 0 {
+            let err = "database has scorch guard enabled.  To change this: 'INSERT INTO reset_mode VALUES ('Scorch')'";
+            self.logger.error(err);
+            Result::Err(RegErr::NoScorch)?;
+        }
+
+        // SQLite has no `DROP TABLE ... CASCADE` so dependents are dropped first
+        trans.execute("DROP TABLE IF EXISTS properties").await?;
+        trans.execute("DROP TABLE IF EXISTS labels").await?;
+        trans.execute("DROP TABLE IF EXISTS tags").await?;
+        trans.execute("DROP TABLE IF EXISTS access_grants").await?;
+        trans.execute("DROP TABLE IF EXISTS particles").await?;
+        trans.commit().await?;
+        self.setup().await?;
+        Ok(())
+    }
+
+    async fn register<'a>(&'a self, registration: &'a Registration) -> Result<(), RegErr> {
+        let params = RegistryParams::from_registration(registration)?;
+        let mut trans = self.pool.begin().await?;
+
+        let count: i64 = sqlx::query("SELECT count(*) FROM particles WHERE point=?")
+            .bind(params.point.clone())
+            .fetch_one(&mut *trans)
+            .await?
+            .get(0);
+
+        if count > 0 {
+            trans.rollback().await?;
+            if registration.strategy == Strategy::Ensure
+                || registration.strategy == Strategy::Override
+            {
+                return Ok(());
+            } else {
+                return Err(RegErr::dupe());
+            }
+        }
+
+        sqlx::query("INSERT INTO particles (point,point_segment,base,sub,provider,vendor,product,variant,version,version_variant,parent,owner,status) VALUES (?,?,?,?,?,?,?,?,?,?,?,?,'Pending')")
+            .bind(params.point.clone())
+            .bind(params.point_segment)
+            .bind(params.base)
+            .bind(params.sub)
+            .bind(params.provider.map(|p| p.to_string()))
+            .bind(params.vendor.map(|v| v.to_string()))
+            .bind(params.product.map(|p| p.to_string()))
+            .bind(params.variant.map(|v| v.to_string()))
+            .bind(params.version)
+            .bind(params.version_variant)
+            .bind(params.parent)
+            .bind(params.owner.to_string())
+            .execute(&mut *trans)
+            .await?;
+
+        for (_, property_mod) in registration.properties.iter() {
+            match property_mod {
+                PropertyMod::Set { key, value, lock } => {
+                    sqlx::query("INSERT INTO properties (resource_id,key,value,lock) VALUES ((SELECT id FROM particles WHERE point=?),?,?,?)")
+                        .bind(params.point.clone())
+                        .bind(key.to_string())
+                        .bind(value.to_string())
+                        .bind(*lock)
+                        .execute(&mut *trans)
+                        .await?;
+                }
+                PropertyMod::UnSet(key) => {
+                    sqlx::query("DELETE FROM properties WHERE resource_id=(SELECT id FROM particles WHERE point=?) AND key=? AND lock=false")
+                        .bind(params.point.clone())
+                        .bind(key.to_string())
+                        .execute(&mut *trans)
+                        .await?;
+                }
+            }
+        }
+        trans.commit().await?;
+        Ok(())
+    }
+
+    async fn assign_star<'a>(&'a self, point: &'a Point, star: &'a Point) -> Result<(), RegErr> {
+        sqlx::query("UPDATE particles SET star=? WHERE point=?")
+            .bind(star.to_string())
+            .bind(point.to_string())
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn assign_host<'a>(&'a self, point: &'a Point, host: &'a Point) -> Result<(), RegErr> {
+        sqlx::query("UPDATE particles SET host=? WHERE point=?")
+            .bind(host.to_string())
+            .bind(point.to_string())
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn set_status<'a>(&'a self, point: &'a Point, status: &'a Status) -> Result<(), RegErr> {
+        sqlx::query("UPDATE particles SET status=? WHERE point=?")
+            .bind(status.to_string())
+            .bind(point.to_string())
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn set_properties<'a>(
+        &'a self,
+        point: &'a Point,
+        properties: &'a SetProperties,
+    ) -> Result<(), RegErr> {
+        let mut trans = self.pool.begin().await?;
+
+        for (_, property_mod) in properties.iter() {
+            match property_mod {
+                PropertyMod::Set { key, value, lock } => {
+                    sqlx::query("INSERT INTO properties (resource_id,key,value,lock) VALUES ((SELECT id FROM particles WHERE point=?),?,?,?) ON CONFLICT(resource_id,key) DO UPDATE SET value=excluded.value, lock=excluded.lock WHERE properties.lock=false")
+                        .bind(point.to_string())
+                        .bind(key.to_string())
+                        .bind(value.to_string())
+                        .bind(*lock)
+                        .execute(&mut *trans)
+                        .await?;
+                }
+                PropertyMod::UnSet(key) => {
+                    sqlx::query("DELETE FROM properties WHERE resource_id=(SELECT id FROM particles WHERE point=?) AND key=? AND lock=false")
+                        .bind(point.to_string())
+                        .bind(key.to_string())
+                        .execute(&mut *trans)
+                        .await?;
+                }
+            }
+        }
+        trans.commit().await?;
+        Ok(())
+    }
+
+    async fn sequence<'a>(&'a self, point: &'a Point) -> Result<u64, RegErr> {
+        let sequence: i64 =
+            sqlx::query("UPDATE particles SET sequence=sequence+1 WHERE point=? RETURNING sequence")
+                .bind(point.to_string())
+                .fetch_one(&self.pool)
+                .await?
+                .get(0);
+
+        Ok(sequence as u64)
+    }
+
+    async fn get_properties<'a>(&'a self, point: &'a Point) -> Result<Properties, RegErr> {
+        let properties = sqlx::query_as::<Sqlite, LocalProperty>("SELECT key,value,lock FROM properties WHERE resource_id=(SELECT id FROM particles WHERE point=?)")
+            .bind(point.to_string())
+            .fetch_all(&self.pool)
+            .await?;
+        let mut map = HashMap::new();
+        for p in properties {
+            map.insert(p.key.clone(), p.into());
+        }
+        Ok(map)
+    }
+
+    async fn record<'a>(&'a self, point: &'a Point) -> Result<ParticleRecord, RegErr> {
+        if point.is_local_root() {
+            return Ok(ParticleRecord::root());
+        }
+
+        let record = sqlx::query_as::<Sqlite, SqliteParticleRecord>(
+            "SELECT * FROM particles WHERE point=?",
+        )
+        .bind(point.to_string())
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or(RegErr::NotFound(point.clone()))?;
+
+        let mut record: ParticleRecord = record.into();
+        record.details.properties = self.get_properties(point).await?;
+
+        Ok(record)
+    }
+
+    async fn query<'a>(
+        &'a self,
+        point: &'a Point,
+        _query: &'a Query,
+    ) -> Result<QueryResult, RegErr> {
+        let mut kind_path = PointHierarchy::new(point.route.clone(), vec![]);
+        let route = point.route.clone();
+
+        let mut segments = vec![];
+        for segment in &point.segments {
+            segments.push(segment.clone());
+            let point = Point {
+                route: route.clone(),
+                segments: segments.clone(),
+            };
+            let record = self.record(&point).await?;
+            let kind_segment = PointKindSeg {
+                segment: record
+                    .details
+                    .stub
+                    .point
+                    .last_segment()
+                    .ok_or("expected at least one segment")?,
+                kind: record.details.stub.kind,
+            };
+            kind_path = kind_path.push(kind_segment);
+        }
+        Ok(QueryResult::PointHierarchy(kind_path))
+    }
+
+    async fn delete<'a>(&'a self, delete: &'a Delete) -> Result<SubstanceList, RegErr> {
+        let mut select = delete.clone().into();
+        let list = self.select(&mut select).await?;
+
+        let mut points = vec![];
+        for point in list.iter() {
+            if let Substance::Point(point) = &**point {
+                points.push(point.to_string());
+            }
+        }
+
+        if !points.is_empty() {
+            let placeholders = vec!["?"; points.len()].join(",");
+            let statements = [
+                format!("DELETE FROM properties WHERE resource_id IN (SELECT id FROM particles WHERE point IN ({}))", placeholders),
+                format!("DELETE FROM labels WHERE resource_id IN (SELECT id FROM particles WHERE point IN ({}))", placeholders),
+                format!("DELETE FROM access_grants WHERE by_particle IN (SELECT id FROM particles WHERE point IN ({}))", placeholders),
+                format!("DELETE FROM particles WHERE point IN ({})", placeholders),
+            ];
+
+            let mut trans = self.pool.begin().await?;
+            for statement in statements.iter() {
+                let mut query = sqlx::query(statement.as_str());
+                for point in points.iter() {
+                    query = query.bind(point.clone());
+                }
+                query.execute(&mut *trans).await?;
+            }
+            trans.commit().await?;
+        }
+
+        Ok(list)
+    }
+
+    async fn sub_select<'a>(&'a self, sub_select: &'a SubSelect) -> Result<Vec<Stub>, RegErr> {
+        // build a 'matching so far' query.  Here we will find every child that matches the subselect
+        // these matches are used to then query children for additional matches if there are more hops.
+        // all of these matches will be filtered to see if they match the ENTIRE select before returning results.
+        let hop = match sub_select.hops.first() {
+            None => return Ok(vec![]),
+            Some(hop) => hop,
+        };
+
+        let mut params: Vec<String> = vec![];
+        let mut where_clause = String::new();
+        where_clause.push_str("parent=?");
+        params.push(sub_select.point.to_string());
+
+        if let PointSegSelector::Exact(exact) = &hop.segment_selector {
+            where_clause.push_str(" AND point_segment=?");
+            match exact {
+                ExactPointSeg::PointSeg(point) => params.push(point.to_string()),
+                ExactPointSeg::Version(version) => params.push(version.to_string()),
+            }
+        }
+
+        // the particles a recursive hop passes through may be of any kind, the kind
+        // is filtered later when the ENTIRE select is matched
+        let kind_selector = match hop.segment_selector.is_recursive() {
+            true => KindSelector::any(),
+            false => hop.kind_selector.clone(),
+        };
+
+        if let KindBaseSelector::Exact(kind) = &kind_selector.base {
+            where_clause.push_str(" AND base=?");
+            params.push(kind.to_string());
+
+            if let SubKindSelector::Exact(sub) = &kind_selector.sub {
+                where_clause.push_str(" AND sub=?");
+                params.push(sub.to_string());
+            }
+        }
+
+        if let ValuePattern::Pattern(specific) = &kind_selector.specific {
+            if let ProviderSelector::Exact(provider) = &specific.provider {
+                where_clause.push_str(" AND provider=?");
+                params.push(provider.to_string());
+            }
+            if let VendorSelector::Exact(vendor) = &specific.vendor {
+                where_clause.push_str(" AND vendor=?");
+                params.push(vendor.to_string());
+            }
+            if let ProductSelector::Exact(product) = &specific.product {
+                where_clause.push_str(" AND product=?");
+                params.push(product.to_string());
+            }
+            if let VariantSelector::Exact(variant) = &specific.variant {
+                where_clause.push_str(" AND variant=?");
+                params.push(variant.to_string());
+            }
+        }
+
+        let matching_so_far_statement =
+            format!("SELECT DISTINCT * FROM particles WHERE {}", where_clause);
+
+        let mut query =
+            sqlx::query_as::<Sqlite, SqliteParticleRecord>(matching_so_far_statement.as_str());
+        for param in params {
+            query = query.bind(param);
+        }
+
+        let matching_so_far = query.fetch_all(&self.pool).await?;
+        let mut matching_so_far: Vec<Stub> = matching_so_far
+            .into_iter()
+            .map(|m| {
+                let record: ParticleRecord = m.into();
+                record.into()
+            })
+            .collect();
+
+        // see if there are matching children
+        let mut hops = sub_select.hops.clone();
+        if !hop.segment_selector.is_recursive() {
+            hops.remove(0);
+        }
+
+        let mut child_stub_matches = vec![];
+        for stub in &matching_so_far {
+            if let Option::Some(last_segment) = stub.point.last_segment() {
+                let point = sub_select.point.push_segment(last_segment.clone())?;
+                let point_tks_path = sub_select.hierarchy.push(PointKindSeg {
+                    segment: last_segment,
+                    kind: stub.kind.clone(),
+                });
+                let sub_select =
+                    sub_select
+                        .clone()
+                        .sub_select(point.clone(), hops.clone(), point_tks_path);
+                let mut more_stubs = self.sub_select(&sub_select).await?;
+                child_stub_matches.append(&mut more_stubs);
+            }
+        }
+
+        // the records matched the present hop (which we needed for deeper searches) however
+        // they may not or may not match the ENTIRE select pattern therefore they must be filtered
+        matching_so_far.retain(|stub| {
+            let point_tks_path = sub_select.hierarchy.push(PointKindSeg {
+                segment: stub
+                    .point
+                    .last_segment()
+                    .expect("expecting at least one segment"),
+                kind: stub.kind.clone(),
+            });
+            sub_select.pattern.matches_found(&point_tks_path)
+        });
+
+        matching_so_far.append(&mut child_stub_matches);
+
+        Ok(matching_so_far)
+    }
+
+    async fn grant<'a>(&'a self, access_grant: &'a AccessGrant) -> Result<(), RegErr> {
+        let (kind, data) = match &access_grant.kind {
+            AccessGrantKind::Super => ("super", None),
+            AccessGrantKind::Privilege(privilege) => ("priv", Some(privilege.to_string())),
+            AccessGrantKind::PermissionsMask(mask) => ("perm", Some(mask.to_string())),
+        };
+
+        sqlx::query("INSERT INTO access_grants (kind,data,query_root,on_point,to_point,by_particle) VALUES (?,?,?,?,?,(SELECT id FROM particles WHERE point=?))")
+            .bind(kind)
+            .bind(data)
+            .bind(access_grant.on_point.query_root().to_string())
+            .bind(access_grant.on_point.to_string())
+            .bind(access_grant.to_point.to_string())
+            .bind(access_grant.by_particle.to_string())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn access<'a>(&'a self, to: &'a Point, on: &'a Point) -> Result<Access, RegErr> {
+        //if 'to' owns 'on' then grant Owner access
+        let has_owner: bool =
+            sqlx::query("SELECT count(*) > 0 as owner FROM particles WHERE point=? AND owner=?")
+                .bind(on.to_string())
+                .bind(to.to_string())
+                .fetch_one(&self.pool)
+                .await?
+                .get(0);
+
+        if *HYPERUSER == *to {
+            if has_owner {
+                return Ok(Access::Super);
+            } else {
+                return Ok(Access::SuperOwner);
+            }
+        }
+
+        if *to == *on && has_owner {
+            return Ok(Access::Owner);
+        }
+
+        let to_kind_path: PointHierarchy =
+            self.query(to, &Query::PointHierarchy).await?.try_into()?;
+        let on_kind_path: PointHierarchy =
+            self.query(on, &Query::PointHierarchy).await?.try_into()?;
+
+        let mut traversal = on.clone();
+        let mut privileges = Privileges::none();
+        let mut permissions = Permissions::none();
+        let mut level_ands: Vec<Vec<PermissionsMask>> = vec![];
+        loop {
+            let mut access_grants: Vec<AccessGrant> = self
+                .access_grants(&traversal)
+                .await?
+                .into_iter()
+                .map(|a| a.into())
+                .collect();
+            access_grants.retain(|access_grant| {
+                access_grant.to_point.matches_found(&to_kind_path)
+                    && access_grant.on_point.matches_found(&on_kind_path)
+            });
+            // check for any superusers
+            for access_grant in &access_grants {
+                let by_access = self.access(&access_grant.by_particle, on).await?;
+                match &access_grant.kind {
+                    AccessGrantKind::Super => {
+                        if by_access.has_super() {
+                            if has_owner {
+                                return Ok(Access::SuperOwner);
+                            } else {
+                                return Ok(Access::Super);
+                            }
+                        }
+                    }
+                    AccessGrantKind::Privilege(privilege) => {
+                        if by_access.has_full() {
+                            privileges = privileges | privilege;
+                        }
+                    }
+                    AccessGrantKind::PermissionsMask(mask) => {
+                        if by_access.has_full() {
+                            if let PermissionsMaskKind::Or = mask.kind {
+                                permissions.or(&mask.permissions);
+                            }
+                        }
+                    }
+                }
+            }
+            let ands: Vec<PermissionsMask> = access_grants
+                .into_iter()
+                .filter_map(|a| match a.kind {
+                    AccessGrantKind::PermissionsMask(mask)
+                        if mask.kind == PermissionsMaskKind::And =>
+                    {
+                        Some(mask)
+                    }
+                    _ => None,
+                })
+                .collect();
+            // save for later when we traverse back down
+            level_ands.push(ands);
+
+            // now reduce the segments of the traversal or break if it's root
+            if traversal.is_root() {
+                break;
+            } else {
+                traversal.segments.pop();
+            }
+        }
+
+        if has_owner {
+            return Ok(Access::Owner);
+        }
+
+        level_ands.reverse();
+        for level in level_ands {
+            for mask in level {
+                permissions.and(&mask.permissions);
+            }
+        }
+
+        let access = EnumeratedAccess {
+            privileges,
+            permissions,
+        };
+
+        Ok(Access::Enumerated(access))
+    }
+
+    async fn chown<'a>(
+        &'a self,
+        on: &'a Selector,
+        owner: &'a Point,
+        by: &'a Point,
+    ) -> Result<(), RegErr> {
+        let mut select = Select {
+            pattern: on.clone(),
+            properties: Default::default(),
+            into_substance: SelectIntoSubstance::Points,
+            kind: SelectKind::Initial,
+        };
+
+        let selection = self.select(&mut select).await?;
+
+        // every particle must be authorized before any owner is changed
+        let mut points = vec![];
+        for on in selection.list {
+            let on: Point = (*on).try_into()?;
+            let access = self.access(by, &on).await?;
+
+            if !access.has_super() {
+                return Err("only a super can change owners".into());
+            }
+            points.push(on);
+        }
+
+        let mut trans = self.pool.begin().await?;
+        for on in points {
+            sqlx::query("UPDATE particles SET owner=? WHERE point=?")
+                .bind(owner.to_string())
+                .bind(on.to_string())
+                .execute(&mut *trans)
+                .await?;
+        }
+        trans.commit().await?;
+        Ok(())
+    }
+
+    async fn list_access<'a>(
+        &'a self,
+        to: &'a Option<&'a Point>,
+        on: &'a Selector,
+    ) -> Result<Vec<IndexedAccessGrant>, RegErr> {
+        let mut select = Select {
+            pattern: on.clone(),
+            properties: Default::default(),
+            into_substance: SelectIntoSubstance::Points,
+            kind: SelectKind::Initial,
+        };
+
+        let to: Option<PointHierarchy> = match to {
+            None => None,
+            Some(to) => Some(self.query(to, &Query::PointHierarchy).await?.try_into()?),
+        };
+
+        let selection = self.select(&mut select).await?;
+        let mut all_access_grants = HashMap::new();
+        for on in selection.list {
+            let on: Point = (*on).try_into()?;
+            let mut access_grants = self.access_grants(&on).await?;
+
+            access_grants.retain(|a| match to.as_ref() {
+                None => true,
+                Some(to) => a.to_point.matches_found(to),
+            });
+            for access_grant in access_grants {
+                all_access_grants.insert(access_grant.id, access_grant);
+            }
+        }
+        let mut all_access_grants: Vec<IndexedAccessGrant> =
+            all_access_grants.into_values().collect();
+
+        all_access_grants.sort();
+
+        Ok(all_access_grants)
+    }
+
+    async fn remove_access<'a>(&'a self, id: i32, to: &'a Point) -> Result<(), RegErr> {
+        let statement = format!(
+            "{} WHERE access_grants.id=? AND particles.id=access_grants.by_particle",
+            ACCESS_GRANTS
+        );
+        let access_grant: IndexedAccessGrant =
+            sqlx::query_as::<Sqlite, WrappedIndexedAccessGrant>(statement.as_str())
+                .bind(id)
+                .fetch_one(&self.pool)
+                .await?
+                .into();
+        let access = self.access(to, &access_grant.by_particle).await?;
+        if access.has_full() {
+            sqlx::query("DELETE FROM access_grants WHERE id=?")
+                .bind(id)
+                .execute(&self.pool)
+                .await?;
+            Ok(())
+        } else {
+            Err(RegErr::Msg(format!("'{}' could not revoked grant {} because it does not have full access (super or owner) on {}", to, id, access_grant.by_particle)))
+        }
+    }
+}
+
+struct LocalProperty {
+    pub key: String,
+    pub value: String,
+    pub locked: bool,
+}
+
+impl From<LocalProperty> for Property {
+    fn from(property: LocalProperty) -> Self {
+        Property {
+            key: property.key,
+            value: property.value,
+            locked: property.locked,
+        }
+    }
+}
+
+impl sqlx::FromRow<'_, SqliteRow> for LocalProperty {
+    fn from_row(row: &SqliteRow) -> Result<Self, sqlx::Error> {
+        let key = row.try_get("key")?;
+        let value = row.try_get("value")?;
+        let locked = row.try_get("lock")?;
+        Ok(LocalProperty { key, value, locked })
+    }
+}
+
+struct WrappedIndexedAccessGrant {
+    grant: IndexedAccessGrant,
+}
+
+impl From<WrappedIndexedAccessGrant> for IndexedAccessGrant {
+    fn from(wrapped: WrappedIndexedAccessGrant) -> Self {
+        wrapped.grant
+    }
+}
+
+impl sqlx::FromRow<'_, SqliteRow> for WrappedIndexedAccessGrant {
+    fn from_row(row: &SqliteRow) -> Result<Self, sqlx::Error> {
+        fn wrap(row: &SqliteRow) -> Result<IndexedAccessGrant, RegErr> {
+            let id: i32 = row.try_get("id")?;
+            let kind: String = row.try_get("kind")?;
+            let kind = match kind.as_str() {
+                "super" => AccessGrantKind::Super,
+                "priv" => {
+                    let privilege: String = row.try_get("data")?;
+                    AccessGrantKind::Privilege(Privilege::from_str(privilege.as_str())?)
+                }
+                "perm" => {
+                    let mask: String = row.try_get("data")?;
+                    AccessGrantKind::PermissionsMask(PermissionsMask::from_str(mask.as_str())?)
+                }
+                what => {
+                    return Err(RegErr::Msg(format!(
+                        "don't know how to handle access grant kind {}",
+                        what
+                    )))
+                }
+            };
+
+            let on_point: String = row.try_get("on_point")?;
+            let to_point: String = row.try_get("to_point")?;
+            let by_particle: String = row.try_get("by_particle")?;
+
+            let access_grant = AccessGrant {
+                kind,
+                on_point: Selector::from_str(on_point.as_str())?,
+                to_point: Selector::from_str(to_point.as_str())?,
+                by_particle: Point::from_str(by_particle.as_str())?,
+            };
+            Ok(IndexedAccessGrant { id, access_grant })
+        }
+
+        match wrap(row) {
+            Ok(grant) => Ok(WrappedIndexedAccessGrant { grant }),
+            Err(err) => Err(sqlx::Error::Decode(Box::new(err))),
+        }
+    }
+}
+
+struct SqliteParticleRecord {
+    pub details: Details,
+    pub location: ParticleLocation,
+}
+
+impl From<SqliteParticleRecord> for ParticleRecord {
+    fn from(record: SqliteParticleRecord) -> Self {
+        ParticleRecord {
+            details: record.details,
+            location: record.location,
+        }
+    }
+}
+
+impl sqlx::FromRow<'_, SqliteRow> for SqliteParticleRecord {
+    fn from_row(row: &SqliteRow) -> Result<Self, sqlx::Error> {
+        fn wrap(row: &SqliteRow) -> Result<SqliteParticleRecord, RegErr> {
+            let point: String = row.try_get("point")?;
+            let base: String = row.try_get("base")?;
+            let sub: Option<String> = row.try_get("sub")?;
+            let provider: Option<String> = row.try_get("provider")?;
+            let vendor: Option<String> = row.try_get("vendor")?;
+            let product: Option<String> = row.try_get("product")?;
+            let variant: Option<String> = row.try_get("variant")?;
+            let version: Option<String> = row.try_get("version")?;
+            let version_variant: Option<String> = row.try_get("version_variant")?;
+            let star: Option<String> = row.try_get("star")?;
+            let host: Option<String> = row.try_get("host")?;
+            let status: String = row.try_get("status")?;
+
+            let point = Point::from_str(point.as_str())?;
+            let base = parse_errs(BaseKind::from_str(base.as_str()))?;
+            let sub = match sub {
+                None => None,
+                Some(sub) => Some(CamelCase::from_str(sub.as_str())?),
+            };
+
+            let specific = match (provider, vendor, product, variant, version) {
+                (Some(provider), Some(vendor), Some(product), Some(variant), Some(version)) => {
+                    let version = match version_variant {
+                        None => Version::from_str(version.as_str())?,
+                        Some(version_variant) => {
+                            Version::from_str(format!("{}-{}", version, version_variant).as_str())?
+                        }
+                    };
+
+                    Some(Specific {
+                        provider: Domain::from_str(provider.as_str())?,
+                        vendor: Domain::from_str(vendor.as_str())?,
+                        product: SkewerCase::from_str(product.as_str())?,
+                        variant: SkewerCase::from_str(variant.as_str())?,
+                        version,
+                    })
+                }
+                _ => None,
+            };
+
+            let kind: Kind = KindParts::new(base, sub, specific).try_into()?;
+
+            let star = match star {
+                None => None,
+                Some(p) => Some(Point::from_str(p.as_str())?),
+            };
+
+            let host = match host {
+                None => None,
+                Some(p) => Some(Point::from_str(p.as_str())?),
+            };
+
+            let location = ParticleLocation { star, host };
+
+            let status = parse_errs(Status::from_str(status.as_str()))?;
+
+            let stub = Stub {
+                point,
+                kind,
+                status,
+            };
+
+            let details = Details {
+                stub,
+                properties: Default::default(),
+            };
+
+            Ok(SqliteParticleRecord { details, location })
+        }
+
+        wrap(row).map_err(|err| sqlx::Error::Decode(Box::new(err)))
+    }
+}
+
+struct RegistryParams {
+    pub point: String,
+    pub point_segment: String,
+    pub base: String,
+    pub sub: Option<String>,
+    pub provider: Option<Domain>,
+    pub vendor: Option<Domain>,
+    pub product: Option<SkewerCase>,
+    pub variant: Option<SkewerCase>,
+    pub version: Option<String>,
+    pub version_variant: Option<String>,
+    pub parent: String,
+    pub owner: Point,
+}
+
+impl RegistryParams {
+    pub fn from_registration(registration: &Registration) -> Result<Self, RegErr> {
+        let point_segment = match registration.point.segments.last() {
+            None => "".to_string(),
+            Some(segment) => segment.to_string(),
+        };
+        let parent = match registration.point.parent() {
+            None => "".to_string(),
+            Some(parent) => parent.to_string(),
+        };
+
+        let base = registration.kind.to_base().to_string();
+        let sub = registration.kind.sub();
+        let specific = registration.kind.specific();
+
+        let version = specific.as_ref().map(|specific| {
+            let version = &specific.version;
+            format!("{}.{}.{}", version.major, version.minor, version.patch)
+        });
+
+        let version_variant = specific.as_ref().and_then(|specific| {
+            match specific.version.pre.is_empty() {
+                true => None,
+                false => Some(specific.version.pre.to_string()),
+            }
+        });
+
+        Ok(RegistryParams {
+            point: registration.point.to_string(),
+            point_segment,
+            parent,
+            base,
+            sub: sub.into(),
+            provider: specific.as_ref().map(|s| s.provider.clone()),
+            vendor: specific.as_ref().map(|s| s.vendor.clone()),
+            product: specific.as_ref().map(|s| s.product.clone()),
+            variant: specific.as_ref().map(|s| s.variant.clone()),
+            version,
+            version_variant,
+            owner: registration.owner.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use crate::SqliteRegistry;
+    use starlane_hyperspace::registry::err::RegErr;
+    use starlane_hyperspace::registry::{conformance, Registry, RegistryApi};
+    use starlane_space::log::Logger;
+    use std::sync::Arc;
+
+    pub async fn registry() -> Result<SqliteRegistry, RegErr> {
+        let registry = SqliteRegistry::memory(Logger::default()).await?;
+        sqlx::query("INSERT INTO reset_mode VALUES ('Scorch')")
+            .execute(&registry.pool)
+            .await?;
+        Ok(registry)
+    }
+
+    #[tokio::test]
+    pub async fn test_scorch_guard() -> Result<(), RegErr> {
+        let registry = SqliteRegistry::memory(Logger::default()).await?;
+        assert!(matches!(registry.scorch().await, Err(RegErr::NoScorch)));
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn test_conformance() -> Result<(), RegErr> {
+        let registry: Registry = Arc::new(registry().await?);
+        conformance::conformance(registry).await
+    }
+}
@@ -23,11 +23,13 @@ use std::collections::HashMap;
 use std::error::Error;
 use std::ops::{Deref, DerefMut};
 use std::string::FromUtf8Error;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, SystemTime};
 use thiserror::Error;
 use tokio::sync::{broadcast, mpsc, watch};
 use tokio::time::error::Elapsed;
+use tokio::time::Instant;
 
 #[derive(Clone, Error, Debug)]
 pub enum ArtErr {
@@ -196,12 +198,15 @@ where
     A: FromStr<Err = ParseErrs>,
 {
     watch: watch::Receiver<ArtStatus<A>>,
+    /// the raw data once fetched.  Kept apart from `watch` which may skip [ArtStatus::Raw]
+    raw: Arc<Mutex<Option<Arc<Bin>>>>,
 }
 
 #[derive(Clone)]
 pub struct ArtifactsSkel {
     pub timeouts: Timeouts,
     pub wait_time: WaitTime,
+    pub eviction: Eviction,
 }
 
 impl Default for ArtifactsSkel {
@@ -209,19 +214,64 @@ impl Default for ArtifactsSkel {
         Self {
             timeouts: Default::default(),
             wait_time: WaitTime::default(),
+            eviction: Default::default(),
         }
     }
 }
 
+/// limits on what an [ArtifactCache] holds onto.  An artifact that has not been used (cloned or
+/// dereferenced through its [ArtRef]) for `idle` is evicted, and when the cache holds more than
+/// `max_artifacts` artifacts or more than `max_bin_bytes` of raw artifact data the least recently
+/// used artifacts are evicted until it fits again
+#[derive(Clone)]
+pub struct Eviction {
+    pub idle: Duration,
+    pub max_artifacts: usize,
+    pub max_bin_bytes: usize,
+}
+
+impl Default for Eviction {
+    fn default() -> Self {
+        Self {
+            idle: Duration::from_secs(10 * 60), // 10 minutes
+            max_artifacts: 1024,
+            max_bin_bytes: 64 * 1024 * 1024, // 64 MiB
+        }
+    }
+}
+
+/// the last time an artifact was used.  Touched whenever its [ArtRef] signals a usage
+#[derive(Clone)]
+struct Usage {
+    last: Arc<Mutex<Instant>>,
+}
+
+impl Usage {
+    fn new() -> Self {
+        Self {
+            last: Arc::new(Mutex::new(Instant::now())),
+        }
+    }
+
+    fn touch(&self) {
+        *self.last.lock().unwrap() = Instant::now();
+    }
+
+    fn last(&self) -> Instant {
+        *self.last.lock().unwrap()
+    }
+}
+
 impl<A> ArtifactPipeline<A>
 where
     A: FromStr<Err = ParseErrs> + 'static,
 {
-    pub fn new(point: &Point, fetcher: Arc<dyn ArtifactFetcher>) -> ArtifactPipeline<A> {
-        let runner = ArtifactPipelineRunner::new(point.clone(), fetcher);
+    fn new(point: &Point, fetcher: Arc<dyn ArtifactFetcher>, usage: Usage) -> ArtifactPipeline<A> {
+        let runner = ArtifactPipelineRunner::new(point.clone(), fetcher, usage);
         let watch = runner.watch();
+        let raw = runner.raw.clone();
         runner.start();
-        Self { watch }
+        Self { watch, raw }
     }
 
     pub fn status(&mut self) -> ArtStatus<A> {
@@ -233,11 +283,17 @@ where
     pub fn watch(&self) -> watch::Receiver<ArtStatus<A>> {
         self.watch.clone()
     }
+
+    pub fn raw(&self) -> Option<Arc<Bin>> {
+        self.raw.lock().unwrap().clone()
+    }
 }
 
 struct ArtifactPipelineRunner<A> {
     point: Point,
     fetcher: Arc<dyn ArtifactFetcher>,
+    usage: Usage,
+    raw: Arc<Mutex<Option<Arc<Bin>>>>,
     broadcast_tx: broadcast::Sender<ArtStatus<A>>,
     watch_rx: watch::Receiver<ArtStatus<A>>,
 }
@@ -246,7 +302,7 @@ impl<A> ArtifactPipelineRunner<A>
 where
     A: FromStr<Err = ParseErrs> + 'static,
 {
-    pub fn new(point: Point, fetcher: Arc<dyn ArtifactFetcher>) -> Self {
+    fn new(point: Point, fetcher: Arc<dyn ArtifactFetcher>, usage: Usage) -> Self {
         let (watch_tx, watch_rx) = watch::channel(ArtStatus::Unknown);
         let (broadcast_tx, mut broadcast_rx) = broadcast::channel(10);
 
@@ -259,6 +315,8 @@ where
         let runner = Self {
             point,
             fetcher,
+            usage,
+            raw: Arc::new(Mutex::new(None)),
             broadcast_tx: broadcast_tx,
             watch_rx,
         };
@@ -287,12 +345,19 @@ where
     async fn run(&mut self) -> Result<(), ArtErr> {
         self.broadcast_tx.send(ArtStatus::Fetching)?;
         let bin = self.fetcher.fetch(&self.point).await?;
+        self.raw.lock().unwrap().replace(bin.clone());
         self.broadcast_tx.send(ArtStatus::Raw(bin.clone()))?;
         let string = String::from_utf8((*bin).clone())?;
 
         self.broadcast_tx.send(ArtStatus::Parsing)?;
         let artifact = A::from_str(string.as_str())?;
-        let (tx, rx) = mpsc::channel(10);
+        let (tx, mut rx) = mpsc::channel(10);
+        let usage = self.usage.clone();
+        tokio::spawn(async move {
+            while let Some(()) = rx.recv().await {
+                usage.touch();
+            }
+        });
         let art = ArtRef::new(artifact, self.point.clone(), tx);
         self.broadcast_tx.send(ArtStatus::Cached(art))?;
         Ok(())
@@ -306,7 +371,10 @@ where
     skel: ArtifactsSkel,
     artifacts: DashMap<Point, ArtRef<A>>,
     bins: DashMap<Point, Arc<Bin>>,
+    bin_bytes: AtomicUsize,
     pipelines: DashMap<Point, ArtifactPipeline<A>>,
+    usages: DashMap<Point, Usage>,
+    last_sweep: Mutex<Instant>,
     fetcher: Arc<dyn ArtifactFetcher>,
 }
 
@@ -319,7 +387,10 @@ where
             skel,
             artifacts: DashMap::new(),
             bins: DashMap::new(),
+            bin_bytes: AtomicUsize::new(0),
             pipelines: DashMap::new(),
+            usages: DashMap::new(),
+            last_sweep: Mutex::new(Instant::now()),
             fetcher,
         }
     }
@@ -333,6 +404,8 @@ where
     }
 
     pub async fn get_with_wait(&self, point: &Point, wait: &WaitTime) -> Result<ArtRef<A>, ArtErr> {
+        self.sweep();
+
         if let Some(art) = self.artifacts.get(point) {
             let art2 = &*art;
             //return Ok((*art).clone());
@@ -341,22 +414,32 @@ where
 
         let timeout = Duration::from_secs(self.skel.timeouts.from_wait(wait));
         let fetcher = self.fetcher.clone();
-        let pipeline = self
-            .pipelines
+        let usage = self
+            .usages
             .entry(point.clone())
-            .or_insert_with(move || ArtifactPipeline::new(point, fetcher));
-        let mut watch = pipeline.watch();
-        let bins = &self.bins;
+            .or_insert_with(Usage::new)
+            .clone();
+        // the pipeline entry must not be held across the wait below or it would lock
+        // its shard of `pipelines` until the artifact arrives
+        let (mut watch, raw) = {
+            let pipeline = self
+                .pipelines
+                .entry(point.clone())
+                .or_insert_with(move || ArtifactPipeline::new(point, fetcher, usage));
+            (pipeline.watch(), pipeline.raw.clone())
+        };
         let artifacts = &self.artifacts;
         let status = tokio::time::timeout(
             timeout,
             watch.wait_for(move |status| match status {
-                ArtStatus::Raw(bin) => {
-                    bins.insert(point.clone(), bin.clone());
-                    false
-                }
+                // a point invalidated or evicted while it was being fetched is not cached
                 ArtStatus::Cached(art) => {
-                    artifacts.insert(point.clone(), art.clone());
+                    if self.usages.contains_key(point) {
+                        if let Some(bin) = raw.lock().unwrap().clone() {
+                            self.insert_bin(point, bin);
+                        }
+                        artifacts.insert(point.clone(), art.clone());
+                    }
                     true
                 }
                 ArtStatus::Fail(_) => true,
@@ -365,7 +448,10 @@ where
         )
         .await??;
 
-        match &*status {
+        let status = (*status).clone();
+        self.evict();
+
+        match &status {
             ArtStatus::Unknown => Err(ArtErr::UnknownStatus),
             ArtStatus::Fetching => Err(ArtErr::FetchingStatus),
             ArtStatus::Raw(_) => Err(ArtErr::BinStatus),
@@ -374,10 +460,82 @@ where
             ArtStatus::Fail(err) => Err(err.err.clone()),
         }
     }
+
+    /// drop `point` and every artifact within it (for instance every artifact of a re-published
+    /// bundle) so the next [ArtifactCache::get] fetches it again
+    pub fn invalidate(&self, point: &Point) {
+        let points: Vec<Point> = self
+            .usages
+            .iter()
+            .map(|usage| usage.key().clone())
+            .filter(|cached| point.is_parent_of(cached))
+            .collect();
+        for point in points {
+            self.remove(&point);
+        }
+    }
+
+    /// the number of bytes of raw artifact data held by this cache
+    pub fn bin_bytes(&self) -> usize {
+        self.bin_bytes.load(Ordering::Relaxed)
+    }
+
+    fn insert_bin(&self, point: &Point, bin: Arc<Bin>) {
+        self.bin_bytes.fetch_add(bin.len(), Ordering::Relaxed);
+        if let Some(old) = self.bins.insert(point.clone(), bin) {
+            self.bin_bytes.fetch_sub(old.len(), Ordering::Relaxed);
+        }
+    }
+
+    fn remove(&self, point: &Point) {
+        self.usages.remove(point);
+        self.pipelines.remove(point);
+        self.artifacts.remove(point);
+        if let Some((_, bin)) = self.bins.remove(point) {
+            self.bin_bytes.fetch_sub(bin.len(), Ordering::Relaxed);
+        }
+    }
+
+    /// [ArtifactCache::evict] at most once every quarter of [Eviction::idle]
+    fn sweep(&self) {
+        {
+            let mut last_sweep = self.last_sweep.lock().unwrap();
+            if last_sweep.elapsed() < self.skel.eviction.idle / 4 {
+                return;
+            }
+            *last_sweep = Instant::now();
+        }
+        self.evict();
+    }
+
+    /// evict idle artifacts and then the least recently used artifacts until the cache is
+    /// within the limits of its [Eviction]
+    fn evict(&self) {
+        let eviction = &self.skel.eviction;
+        let mut usages: Vec<(Point, Instant)> = self
+            .usages
+            .iter()
+            .map(|usage| (usage.key().clone(), usage.value().last()))
+            .collect();
+        // most recently used first
+        usages.sort_by(|(_, a), (_, b)| b.cmp(a));
+        while let Some((point, last)) = usages.last() {
+            if last.elapsed() >= eviction.idle
+                || usages.len() > eviction.max_artifacts
+                || self.bin_bytes() > eviction.max_bin_bytes
+            {
+                self.remove(point);
+                usages.pop();
+            } else {
+                break;
+            }
+        }
+    }
 }
 
 pub struct ArtifactHub {
     skel: ArtifactsSkel,
+    fetcher: Arc<dyn ArtifactFetcher>,
     pub bind: ArtifactCache<BindConfig>,
     pub mechtron: ArtifactCache<MechtronConfig>,
     pub selector: PointSelector,
@@ -388,6 +546,7 @@ impl ArtifactHub {
         ArtifactHub {
             bind: ArtifactCache::new(fetcher.clone(), skel.clone()),
             mechtron: ArtifactCache::new(fetcher.clone(), skel.clone()),
+            fetcher,
             skel,
             selector: PointSelector::always(),
         }
@@ -400,6 +559,11 @@ impl ArtifactHub {
     pub async fn mechtron_conf(&self, point: &Point) -> Result<ArtRef<MechtronConfig>, ArtErr> {
         self.mechtron.get(point).await
     }
+
+    pub fn invalidate(&self, point: &Point) {
+        self.bind.invalidate(point);
+        self.mechtron.invalidate(point);
+    }
 }
 
 pub struct ArtifactsBuilder {
@@ -467,6 +631,43 @@ impl Artifacts {
         }
         None
     }
+
+    /// drop `point` and every artifact within it from every hub so they are fetched again.
+    /// Called when a bundle is (re-)published.  Clones of [Artifacts] share their hubs so this
+    /// reaches every star of the machine, but nothing tells other machines: their caches keep
+    /// serving the old artifacts until they are evicted (see [Eviction::idle])
+    pub fn invalidate(&self, point: &Point) {
+        for hub in &self.hubs {
+            hub.invalidate(point);
+        }
+    }
+}
+
+/// fetches raw artifacts (for example a Mechtron's wasm) from the same hub that serves
+/// the configs which reference them
+#[async_trait]
+impl ArtifactFetcher for Artifacts {
+    async fn stub(&self, point: &Point) -> Result<Stub, ArtErr> {
+        for hub in &self.hubs {
+            if hub.selector.is_match(point).is_ok() {
+                return hub.fetcher.stub(point).await;
+            }
+        }
+        Err(ArtErr::not_found(point))
+    }
+
+    async fn fetch(&self, point: &Point) -> Result<Arc<Bin>, ArtErr> {
+        for hub in &self.hubs {
+            if hub.selector.is_match(point).is_ok() {
+                return hub.fetcher.fetch(point).await;
+            }
+        }
+        Err(ArtErr::not_found(point))
+    }
+
+    fn selector(&self) -> ValuePattern<Selector> {
+        ValuePattern::Always
+    }
 }
 
 pub struct FetchChamber {
@@ -587,3 +788,133 @@ fn test() {
         arc: Arc<A>,
     }
 }
+
+#[cfg(test)]
+pub mod cache_test {
+    use crate::artifact::asynch::{
+        ArtErr, ArtifactCache, ArtifactFetcher, ArtifactHub, Artifacts, ArtifactsSkel, Eviction,
+        MapFetcher,
+    };
+    use crate::point::Point;
+    use std::str::FromStr;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    fn cache(eviction: Eviction) -> ArtifactCache<Point> {
+        let mut fetcher = MapFetcher::new();
+        for name in ["a", "b", "c"] {
+            let point = Point::from_str(format!("repo:bundle:1.0.0:/{}", name).as_str()).unwrap();
+            fetcher.str(&point, point.to_string());
+        }
+        let skel = ArtifactsSkel {
+            eviction,
+            ..Default::default()
+        };
+        ArtifactCache::new(Arc::new(fetcher), skel)
+    }
+
+    fn point(name: &str) -> Point {
+        Point::from_str(format!("repo:bundle:1.0.0:/{}", name).as_str()).unwrap()
+    }
+
+    /// let the usages of the artifacts just gotten be recorded and then move the paused clock
+    async fn advance(duration: Duration) {
+        tokio::task::yield_now().await;
+        tokio::time::advance(duration).await;
+    }
+
+    #[tokio::test]
+    pub async fn test_evict_lru() {
+        tokio::time::pause();
+        let cache = cache(Eviction {
+            max_artifacts: 2,
+            ..Default::default()
+        });
+        cache.get(&point("a")).await.unwrap();
+        advance(Duration::from_millis(10)).await;
+        cache.get(&point("b")).await.unwrap();
+        advance(Duration::from_millis(10)).await;
+        // using 'a' again leaves 'b' as the least recently used
+        cache.get(&point("a")).await.unwrap();
+        advance(Duration::from_millis(10)).await;
+        cache.get(&point("c")).await.unwrap();
+
+        assert!(cache.artifacts.contains_key(&point("a")));
+        assert!(!cache.artifacts.contains_key(&point("b")));
+        assert!(cache.artifacts.contains_key(&point("c")));
+    }
+
+    #[tokio::test]
+    pub async fn test_evict_bin_bytes() {
+        let cache = cache(Eviction {
+            max_bin_bytes: 0,
+            ..Default::default()
+        });
+        cache.get(&point("a")).await.unwrap();
+        assert_eq!(cache.bin_bytes(), 0);
+        assert!(cache.artifacts.is_empty());
+    }
+
+    #[tokio::test]
+    pub async fn test_bin_bytes() {
+        let cache = cache(Default::default());
+        cache.get(&point("a")).await.unwrap();
+        cache.get(&point("b")).await.unwrap();
+        let expected = point("a").to_string().len() + point("b").to_string().len();
+        assert_eq!(cache.bin_bytes(), expected);
+    }
+
+    #[tokio::test]
+    pub async fn test_evict_idle() {
+        tokio::time::pause();
+        let cache = cache(Eviction {
+            idle: Duration::from_millis(50),
+            ..Default::default()
+        });
+        cache.get(&point("a")).await.unwrap();
+        advance(Duration::from_millis(49)).await;
+        cache.get(&point("b")).await.unwrap();
+        assert!(cache.artifacts.contains_key(&point("a")));
+
+        // idle artifacts are swept at most every quarter of the idle time
+        advance(Duration::from_millis(13)).await;
+        cache.get(&point("b")).await.unwrap();
+
+        assert!(!cache.artifacts.contains_key(&point("a")));
+        assert!(cache.artifacts.contains_key(&point("b")));
+    }
+
+    #[tokio::test]
+    pub async fn test_invalidate() {
+        let cache = cache(Default::default());
+        cache.get(&point("a")).await.unwrap();
+        cache.get(&point("b")).await.unwrap();
+        cache.invalidate(&Point::from_str("repo:bundle:1.0.0").unwrap());
+
+        assert!(cache.artifacts.is_empty());
+        assert!(cache.pipelines.is_empty());
+        assert_eq!(cache.bin_bytes(), 0);
+        cache.get(&point("a")).await.unwrap();
+        assert!(cache.artifacts.contains_key(&point("a")));
+    }
+
+    /// raw artifacts are fetched through the hub that serves their configs
+    #[tokio::test]
+    pub async fn test_artifacts_fetch() {
+        let mut fetcher = MapFetcher::new();
+        fetcher.str(&point("a"), "wasm");
+        let hub = ArtifactHub::new(Arc::new(fetcher), ArtifactsSkel::default());
+        let artifacts = Artifacts {
+            hubs: vec![Arc::new(hub)],
+        };
+
+        assert_eq!(
+            artifacts.fetch(&point("a")).await.unwrap().as_slice(),
+            b"wasm"
+        );
+        assert!(matches!(
+            artifacts.fetch(&point("b")).await,
+            Err(ArtErr::NotFound(_))
+        ));
+    }
+}
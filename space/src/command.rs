@@ -17,6 +17,7 @@ use direct::get::{Get, GetCtx, GetVar};
 use direct::read::{Read, ReadCtx, ReadVar};
 use direct::select::{SelectCtx, SelectVar};
 use direct::set::{Set, SetCtx, SetVar};
+use direct::watch::{Unwatch, UnwatchCtx, UnwatchVar, Watch, WatchCtx, WatchVar};
 use direct::write::{Write, WriteCtx, WriteVar};
 use starlane_macros::Autobox;
 
@@ -971,6 +972,92 @@ pub mod direct {
         }
     }
 
+    pub mod watch {
+        use serde::{Deserialize, Serialize};
+
+        use crate::err::ParseErrs;
+        use crate::parse::Env;
+        use crate::particle::{Aspect, Watch as WatchRecipient};
+        use crate::point::{Point, PointCtx, PointVar};
+        use crate::util::ToResolved;
+
+        pub type Watch = WatchDef<Point>;
+        pub type WatchCtx = WatchDef<PointCtx>;
+        pub type WatchVar = WatchDef<PointVar>;
+
+        /// subscribe the issuer of the command to `aspect` events of `point`
+        #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+        pub struct WatchDef<Pnt> {
+            pub point: Pnt,
+            pub aspect: Aspect,
+        }
+
+        impl Watch {
+            pub fn to_recipient(&self) -> WatchRecipient {
+                WatchRecipient {
+                    point: self.point.clone(),
+                    aspect: self.aspect.clone(),
+                }
+            }
+        }
+
+        impl ToResolved<WatchCtx> for WatchVar {
+            fn to_resolved(self, env: &Env) -> Result<WatchCtx, ParseErrs> {
+                Ok(WatchCtx {
+                    point: self.point.to_resolved(env)?,
+                    aspect: self.aspect,
+                })
+            }
+        }
+
+        impl ToResolved<Watch> for WatchCtx {
+            fn to_resolved(self, env: &Env) -> Result<Watch, ParseErrs> {
+                Ok(Watch {
+                    point: self.point.to_resolved(env)?,
+                    aspect: self.aspect,
+                })
+            }
+        }
+
+        pub type Unwatch = UnwatchDef<Point>;
+        pub type UnwatchCtx = UnwatchDef<PointCtx>;
+        pub type UnwatchVar = UnwatchDef<PointVar>;
+
+        /// cancel a subscription previously made with [`Watch`]
+        #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+        pub struct UnwatchDef<Pnt> {
+            pub point: Pnt,
+            pub aspect: Aspect,
+        }
+
+        impl Unwatch {
+            pub fn to_recipient(&self) -> WatchRecipient {
+                WatchRecipient {
+                    point: self.point.clone(),
+                    aspect: self.aspect.clone(),
+                }
+            }
+        }
+
+        impl ToResolved<UnwatchCtx> for UnwatchVar {
+            fn to_resolved(self, env: &Env) -> Result<UnwatchCtx, ParseErrs> {
+                Ok(UnwatchCtx {
+                    point: self.point.to_resolved(env)?,
+                    aspect: self.aspect,
+                })
+            }
+        }
+
+        impl ToResolved<Unwatch> for UnwatchCtx {
+            fn to_resolved(self, env: &Env) -> Result<Unwatch, ParseErrs> {
+                Ok(Unwatch {
+                    point: self.point.to_resolved(env)?,
+                    aspect: self.aspect,
+                })
+            }
+        }
+    }
+
     pub mod query {
         use std::convert::TryInto;
 
@@ -1018,6 +1105,8 @@ pub enum Command {
     Get(Get),
     Write(Write),
     Read(Read),
+    Watch(Watch),
+    Unwatch(Unwatch),
 }
 
 impl ChildSubstance for Command {}
@@ -1044,6 +1133,8 @@ pub enum CommandCtx {
     Get(GetCtx),
     Update(WriteCtx),
     Read(ReadCtx),
+    Watch(WatchCtx),
+    Unwatch(UnwatchCtx),
 }
 
 pub enum CommandVar {
@@ -1054,6 +1145,8 @@ pub enum CommandVar {
     Get(GetVar),
     Update(WriteVar),
     Read(ReadVar),
+    Watch(WatchVar),
+    Unwatch(UnwatchVar),
 }
 
 impl FromStr for CommandVar {
@@ -1082,6 +1175,8 @@ impl ToResolved<CommandCtx> for CommandVar {
             CommandVar::Delete(i) => CommandCtx::Delete(i.to_resolved(env)?),
             CommandVar::Update(update) => CommandCtx::Update(update.to_resolved(env)?),
             CommandVar::Read(read) => CommandCtx::Read(read.to_resolved(env)?),
+            CommandVar::Watch(watch) => CommandCtx::Watch(watch.to_resolved(env)?),
+            CommandVar::Unwatch(unwatch) => CommandCtx::Unwatch(unwatch.to_resolved(env)?),
         })
     }
 }
@@ -1096,6 +1191,8 @@ impl ToResolved<Command> for CommandCtx {
             CommandCtx::Delete(i) => Command::Delete(i.to_resolved(env)?),
             CommandCtx::Update(update) => Command::Write(update.to_resolved(env)?),
             CommandCtx::Read(read) => Command::Read(read.to_resolved(env)?),
+            CommandCtx::Watch(watch) => Command::Watch(watch.to_resolved(env)?),
+            CommandCtx::Unwatch(unwatch) => Command::Unwatch(unwatch.to_resolved(env)?),
         })
     }
 }
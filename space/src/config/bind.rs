@@ -1,5 +1,6 @@
 use core::str::FromStr;
 use std::convert::TryInto;
+use std::sync::Arc;
 
 use regex::Regex;
 use serde::{Deserialize, Serialize};
@@ -7,7 +8,7 @@ use serde::{Deserialize, Serialize};
 use crate::err::{ParseErrs, SpaceErr};
 use crate::loc::Topic;
 use crate::parse::model::{BindScope, MethodScope, PipelineSegmentDef, RouteScope, ScopeFilters};
-use crate::parse::{bind_config, Env};
+use crate::parse::{bind_config, Env, MultiVarResolver, RegexCapturesResolver};
 use crate::point::{Point, PointCtx, PointVar};
 use crate::selector::PayloadBlockDef;
 use crate::substance::{CallDef, SubstancePattern};
@@ -41,14 +42,68 @@ impl BindConfig {
         scopes
     }
 
+    /// require the path regex of every scope to match the whole uri path so that
+    /// `/users/(?P<id>\w+)` does not also select `/users/scott/posts`.  Applied to
+    /// `Bind(version=1.1.0)` documents, a `1.0.0` bind keeps selecting any uri path that
+    /// one of its paths matches part of.  To migrate a `1.0.0` bind append `.*` to each
+    /// path that is meant to select everything below it
+    pub fn anchor_paths(mut self) -> Result<Self, ParseErrs> {
+        fn anchor(path: &Regex) -> Result<Regex, ParseErrs> {
+            Ok(Regex::new(format!("^(?:{})$", path.as_str()).as_str())?)
+        }
+        for scope in self.scopes.iter_mut() {
+            match scope {
+                BindScope::RequestScope(route) => {
+                    route.selector.selector.selector.path =
+                        anchor(&route.selector.selector.selector.path)?;
+                    for message in route.block.iter_mut() {
+                        message.selector.selector.path = anchor(&message.selector.selector.path)?;
+                        for method in message.block.iter_mut() {
+                            method.selector.selector.path = anchor(&method.selector.selector.path)?;
+                        }
+                    }
+                }
+            }
+        }
+        Ok(self)
+    }
+
     pub fn select(&self, directed: &DirectedWave) -> Result<&MethodScope, SpaceErr> {
+        Ok(self.selection(directed)?.0)
+    }
+
+    /// the [`MethodScope`] selected for `directed` and a resolver for the named captures of the
+    /// path regexes of the scopes that selected it.  A capture of an inner scope shadows a
+    /// capture of the same name in an outer scope
+    pub fn select_with_captures(
+        &self,
+        directed: &DirectedWave,
+    ) -> Result<(&MethodScope, MultiVarResolver), SpaceErr> {
+        let (method_scope, paths) = self.selection(directed)?;
+        let path = directed.core().uri.path().to_string();
+        let mut captures = MultiVarResolver::new();
+        for regex in paths.into_iter().rev() {
+            captures.push(Arc::new(RegexCapturesResolver::new(
+                regex.clone(),
+                path.clone(),
+            )?));
+        }
+        Ok((method_scope, captures))
+    }
+
+    fn selection(&self, directed: &DirectedWave) -> Result<(&MethodScope, Vec<&Regex>), SpaceErr> {
         for route_scope in self.route_scopes() {
             if route_scope.selector.is_match(directed).is_ok() {
                 for message_scope in &route_scope.block {
                     if message_scope.selector.is_match(directed).is_ok() {
                         for method_scope in &message_scope.block {
                             if method_scope.selector.is_match(directed).is_ok() {
-                                return Ok(method_scope);
+                                let paths = vec![
+                                    &route_scope.selector.path,
+                                    &message_scope.selector.path,
+                                    &method_scope.selector.path,
+                                ];
+                                return Ok((method_scope, paths));
                             }
                         }
                     }
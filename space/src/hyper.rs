@@ -1,21 +1,21 @@
 use std::collections::HashSet;
 use std::ops::{Deref, DerefMut};
 
-use crate::command::common::StateSrc;
+use crate::command::common::{SetProperties, StateSrc};
 use crate::config::mechtron::MechtronConfig;
 use crate::err::ParseErrs;
 use crate::err::SpaceErr;
 use crate::kind::{Kind, KindParts, StarSub};
 use crate::loc::{StarKey, Surface, ToSurface};
 use crate::log::Log;
-use crate::particle::{Details, Status, Stub};
+use crate::particle::{Aspect, Details, Status, Stub, Watch};
 use crate::point::Point;
 use crate::selector::KindSelector;
 use crate::substance::{Substance, SubstanceKind};
 use crate::wave::core::hyper::HypMethod;
 use crate::wave::core::{DirectedCore, ReflectedCore};
 use crate::wave::{
-    Agent, PingCore, ReflectedKind, ReflectedProto, Wave, WaveId, WaveKind, WaveVariantDef,
+    Agent, PingCore, ReflectedKind, ReflectedProto, Scope, Wave, WaveId, WaveKind, WaveVariantDef,
 };
 use serde::{Deserialize, Serialize};
 use starlane_macros::Autobox;
@@ -251,6 +251,7 @@ pub enum HyperSubstance {
     Log(Log),
     Search(Search),
     Discoveries(Discoveries),
+    Watch(Watcher),
 }
 
 impl HyperSubstance {
@@ -264,6 +265,7 @@ impl HyperSubstance {
             HyperSubstance::Log(_) => HyperSubstanceKind::Log,
             HyperSubstance::Search(_) => HyperSubstanceKind::Search,
             HyperSubstance::Discoveries(_) => HyperSubstanceKind::Discoveries,
+            HyperSubstance::Watch(_) => HyperSubstanceKind::Watch,
         }
     }
 }
@@ -288,6 +290,7 @@ pub enum HyperSubstanceKind {
     Log,
     Search,
     Discoveries,
+    Watch,
 }
 
 impl Default for HyperSubstanceKind {
@@ -378,6 +381,35 @@ impl Into<DirectedCore> for Assign {
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, strum_macros::Display, Autobox)]
 pub enum HyperEvent {
     Created(Created),
+    StatusChanged(StatusChanged),
+    PropertiesChanged(PropertiesChanged),
+}
+
+impl HyperEvent {
+    /// the [`Watch`] whose watchers are notified of this event.  A `Created` event
+    /// is a [`Aspect::Child`] change of the new particle's parent
+    pub fn watch(&self) -> Watch {
+        match self {
+            HyperEvent::Created(created) => Watch {
+                point: created.point.parent().unwrap_or(created.point.clone()),
+                aspect: Aspect::Child,
+            },
+            HyperEvent::StatusChanged(changed) => Watch {
+                point: changed.point.clone(),
+                aspect: Aspect::State,
+            },
+            HyperEvent::PropertiesChanged(changed) => Watch {
+                point: changed.point.clone(),
+                aspect: Aspect::Property,
+            },
+        }
+    }
+}
+
+impl Into<Substance> for HyperEvent {
+    fn into(self) -> Substance {
+        Substance::Hyper(HyperSubstance::Event(self))
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
@@ -386,6 +418,31 @@ pub struct Created {
     pub kind: KindParts,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct StatusChanged {
+    pub point: Point,
+    pub status: Status,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct PropertiesChanged {
+    pub point: Point,
+    pub properties: SetProperties,
+}
+
+/// a subscription of `surface` to the events of a [`Watch`]
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct Watcher {
+    pub watch: Watch,
+    pub surface: Surface,
+}
+
+impl Into<Substance> for Watcher {
+    fn into(self) -> Substance {
+        Substance::Hyper(HyperSubstance::Watch(self))
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, strum_macros::Display, Hash)]
 pub enum InterchangeKind {
     Singleton,
@@ -446,6 +503,10 @@ pub struct Greet {
     pub agent: Agent,
     pub hop: Surface,
     pub transport: Surface,
+    /// narrows what `agent` may do on this hyperway, see [crate::security::Access::mask].
+    /// A Greet from a gate that predates scopes grants [Scope::Full] as it did before
+    #[serde(default = "Greet::unscoped")]
+    pub scope: Scope,
 }
 
 impl Greet {
@@ -455,8 +516,13 @@ impl Greet {
             surface,
             hop,
             transport,
+            scope: Self::unscoped(),
         }
     }
+
+    fn unscoped() -> Scope {
+        Scope::Full
+    }
 }
 
 impl Into<Wave> for Greet {
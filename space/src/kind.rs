@@ -385,6 +385,18 @@ impl Kind {
             Kind::Artifact(_) => true,
             Kind::Mechtron => true,
             Kind::Host => true,
+            Kind::FileStore => true,
+            Kind::File(_) => true,
+            _ => false,
+        }
+    }
+
+    /// particles of this kind keep state outside of the registry which is removed by sending
+    /// them a `Cmd<Delete>` before they are deleted
+    pub fn has_external_state(&self) -> bool {
+        match self {
+            Kind::FileStore => true,
+            Kind::File(_) => true,
             _ => false,
         }
     }
@@ -444,6 +456,12 @@ impl Kind {
     }
 }
 
+impl From<Kind> for KindParts {
+    fn from(kind: Kind) -> Self {
+        KindParts::new(kind.to_base(), kind.sub().to_camel_case(), kind.specific())
+    }
+}
+
 impl TryFrom<KindParts> for Kind {
     type Error = ParseErrs;
 
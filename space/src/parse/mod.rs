@@ -10,9 +10,13 @@ use crate::command::direct::create::{
     CreateVar, KindTemplate, PointSegTemplate, PointTemplateSeg, PointTemplateVar, Strategy,
     TemplateVar,
 };
+use crate::command::direct::delete::DeleteVar;
 use crate::command::direct::get::{GetOp, GetVar};
+use crate::command::direct::read::ReadVar;
 use crate::command::direct::select::{SelectIntoSubstance, SelectKind, SelectVar};
 use crate::command::direct::set::SetVar;
+use crate::command::direct::watch::{UnwatchVar, WatchVar};
+use crate::command::direct::write::WriteVar;
 use crate::command::direct::CmdKind;
 use crate::command::CommandVar;
 use crate::config::bind::{
@@ -30,7 +34,7 @@ use crate::loc::StarKey;
 use crate::loc::{Layer, PointSegment, Surface, Topic, Uuid, VarVal, Version};
 use crate::parse::util::unstack;
 use crate::parse::util::{log_parse_err, preceded, recognize, result};
-use crate::particle::PointKindVar;
+use crate::particle::{Aspect, PointKindVar};
 use crate::point::{
     Point, PointCtx, PointSeg, PointSegCtx, PointSegDelim, PointSegVar, PointVar, RouteSeg,
     RouteSegVar,
@@ -69,11 +73,12 @@ use model::{
     ScopeFiltersDef, Spanned, Subst, TerminatedBlockKind, TextType, VarParser,
 };
 use nom::branch::alt;
-use nom::bytes::complete::{is_a, is_not};
+use nom::bytes::complete::{escaped, is_a, is_not};
 use nom::bytes::complete::{tag, take_until};
 use nom::character::complete::{alpha1, digit1};
 use nom::character::complete::{
-    alphanumeric0, alphanumeric1, anychar, char, multispace0, multispace1, satisfy, space1,
+    alphanumeric0, alphanumeric1, anychar, char, multispace0, multispace1, one_of, satisfy,
+    space1,
 };
 use nom::combinator::{all_consuming, into, opt};
 use nom::combinator::{cut, eof, fail, not, peek, value, verify};
@@ -2264,6 +2269,75 @@ pub fn select<I: Span>(input: I) -> Res<I, SelectVar> {
     })
 }
 
+pub fn delete<I: Span>(input: I) -> Res<I, DeleteVar> {
+    point_selector(input).map(|(next, selector)| (next, DeleteVar { selector }))
+}
+
+/// the literal content between `^[` and `]` which becomes the [Substance::Text] payload of a
+/// write.  A `]` or `\` within the content is escaped with a `\`
+pub fn write_payload<I: Span>(input: I) -> Res<I, Substance> {
+    delimited(
+        tag("^["),
+        opt(escaped(is_not("\\]"), '\\', one_of("\\]"))),
+        tag("]"),
+    )(input)
+    .map(|(next, content)| {
+        let content = content.map(|c| unescape(c.to_string())).unwrap_or_default();
+        (next, Substance::Text(content.trim().to_string()))
+    })
+}
+
+/// removes the `\` from each escaped char of a [write_payload]
+fn unescape(content: String) -> String {
+    let mut rtn = String::with_capacity(content.len());
+    let mut chars = content.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => rtn.extend(chars.next()),
+            c => rtn.push(c),
+        }
+    }
+    rtn
+}
+
+pub fn write<I: Span>(input: I) -> Res<I, WriteVar> {
+    tuple((point_var, space1, write_payload))(input).map(|(next, (point, _, payload))| {
+        let write = WriteVar { point, payload };
+        (next, write)
+    })
+}
+
+pub fn read<I: Span>(input: I) -> Res<I, ReadVar> {
+    point_var(input).map(|(next, point)| {
+        let read = ReadVar {
+            point,
+            payload: Substance::Empty,
+        };
+        (next, read)
+    })
+}
+
+pub fn aspect<I: Span>(input: I) -> Res<I, Aspect> {
+    let (next, aspect) = recognize(camel_case)(input.clone())?;
+    match Aspect::from_str(aspect.to_string().as_str()) {
+        Ok(aspect) => Ok((next, aspect)),
+        Err(err) => Err(nom::Err::Error(NomErr::from_error_kind(
+            input,
+            ErrorKind::Alpha,
+        ))),
+    }
+}
+
+pub fn watch<I: Span>(input: I) -> Res<I, WatchVar> {
+    tuple((point_var, space1, aspect))(input)
+        .map(|(next, (point, _, aspect))| (next, WatchVar { point, aspect }))
+}
+
+pub fn unwatch<I: Span>(input: I) -> Res<I, UnwatchVar> {
+    tuple((point_var, space1, aspect))(input)
+        .map(|(next, (point, _, aspect))| (next, UnwatchVar { point, aspect }))
+}
+
 pub fn publish<I: Span>(input: I) -> Res<I, CreateVar> {
     let (next, (upload, _, point)) = tuple((upload_block, space1, point_template))(input.clone())?;
 
@@ -4090,10 +4164,7 @@ pub mod model {
 
     impl RouteScopeSelector {
         pub fn new<I: ToString>(path: Option<I>) -> Result<Self, ParseErrs> {
-            let path = match path {
-                None => Regex::new(".*")?,
-                Some(path) => Regex::new(path.to_string().as_str())?,
-            };
+            let path = default_path(path)?;
             Ok(Self {
                 selector: ScopeSelectorDef {
                     path,
@@ -5271,6 +5342,31 @@ fn get_command<I: Span>(input: I) -> Res<I, CommandVar> {
     tuple((tag("get"), space1, get))(input).map(|(next, (_, _, get))| (next, CommandVar::Get(get)))
 }
 
+fn delete_command<I: Span>(input: I) -> Res<I, CommandVar> {
+    tuple((tag("delete"), space1, delete))(input)
+        .map(|(next, (_, _, delete))| (next, CommandVar::Delete(delete)))
+}
+
+fn write_command<I: Span>(input: I) -> Res<I, CommandVar> {
+    tuple((tag("write"), space1, write))(input)
+        .map(|(next, (_, _, write))| (next, CommandVar::Update(write)))
+}
+
+fn read_command<I: Span>(input: I) -> Res<I, CommandVar> {
+    tuple((tag("read"), space1, read))(input)
+        .map(|(next, (_, _, read))| (next, CommandVar::Read(read)))
+}
+
+fn watch_command<I: Span>(input: I) -> Res<I, CommandVar> {
+    tuple((tag("watch"), space1, watch))(input)
+        .map(|(next, (_, _, watch))| (next, CommandVar::Watch(watch)))
+}
+
+fn unwatch_command<I: Span>(input: I) -> Res<I, CommandVar> {
+    tuple((tag("unwatch"), space1, unwatch))(input)
+        .map(|(next, (_, _, unwatch))| (next, CommandVar::Unwatch(unwatch)))
+}
+
 pub fn command_strategy<I: Span>(input: I) -> Res<I, Strategy> {
     opt(tuple((tag("?"), multispace0)))(input).map(|(next, hint)| match hint {
         None => (next, Strategy::Commit),
@@ -5287,6 +5383,11 @@ pub fn command<I: Span>(input: I) -> Res<I, CommandVar> {
             select_command,
             set_command,
             get_command,
+            delete_command,
+            write_command,
+            read_command,
+            watch_command,
+            unwatch_command,
             fail,
         )),
     )(input)
@@ -5449,9 +5550,11 @@ pub mod cmd_test {
     use crate::command::{Command, CommandVar};
     use crate::err::ParseErrs;
     use crate::kind::{BaseKind, Kind};
+    use crate::particle::Aspect;
     use crate::parse::util::{new_span, result};
     use crate::point::{PointSeg, RouteSeg};
-    use crate::selector::{PointHierarchy, PointKindSeg};
+    use crate::selector::{PointHierarchy, PointKindSeg, Selector};
+    use crate::substance::Substance;
     use crate::util::ToResolved;
 
     use crate::parse::{
@@ -5522,6 +5625,70 @@ pub mod cmd_test {
         Ok(())
     }
 
+    #[test]
+    pub fn test_delete_write_read() -> Result<(), ParseErrs> {
+        let parsed = result(command(new_span("delete localhost:app:**")))?.collapse()?;
+        match parsed {
+            Command::Delete(delete) => {
+                assert_eq!(delete.selector, point_selector_from("localhost:app:**")?)
+            }
+            _ => panic!("expected delete command"),
+        }
+
+        let parsed = result(command(new_span("write localhost:config ^[ hello world ]")))?;
+        match parsed.collapse()? {
+            Command::Write(write) => {
+                assert_eq!(write.point.to_string(), "localhost:config".to_string());
+                assert_eq!(write.payload, Substance::Text("hello world".to_string()));
+            }
+            _ => panic!("expected write command"),
+        }
+
+        let parsed = result(command(new_span("read localhost:config")))?.collapse()?;
+        match parsed {
+            Command::Read(read) => {
+                assert_eq!(read.point.to_string(), "localhost:config".to_string());
+            }
+            _ => panic!("expected read command"),
+        }
+
+        let input = r#" write localhost:config ^[ goodbye ];
+ read localhost:config ;
+ delete localhost:config;
+        "#;
+        assert_eq!(result(script(new_span(input)))?.len(), 3);
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_watch_unwatch() -> Result<(), ParseErrs> {
+        let parsed = result(command(new_span("watch localhost:app Property")))?.collapse()?;
+        match parsed {
+            Command::Watch(watch) => {
+                assert_eq!(watch.point.to_string(), "localhost:app".to_string());
+                assert_eq!(watch.aspect, Aspect::Property);
+            }
+            _ => panic!("expected watch command"),
+        }
+
+        let parsed = result(command(new_span("unwatch localhost:app State")))?.collapse()?;
+        match parsed {
+            Command::Unwatch(unwatch) => {
+                assert_eq!(unwatch.point.to_string(), "localhost:app".to_string());
+                assert_eq!(unwatch.aspect, Aspect::State);
+            }
+            _ => panic!("expected unwatch command"),
+        }
+
+        assert!(result(command(new_span("watch localhost:app Bogus"))).is_err());
+        Ok(())
+    }
+
+    fn point_selector_from(selector: &str) -> Result<Selector, ParseErrs> {
+        result(point_selector(new_span(selector)))
+    }
+
     #[test]
     pub fn test_publish() -> Result<(), ParseErrs> {
         let input = r#"publish ^[ bundle.zip ]-> localhost:repo:tutorial:1.0.0"#;
@@ -7240,8 +7407,13 @@ pub fn doc(src: &str) -> Result<Document, ParseErrs> {
         }
     } else if root_scope_selector.name.as_str() == "Bind" {
         if root_scope_selector.version == Version::from_str("1.0.0")? {
+            // a 1.0.0 scope path selects any uri path that it matches part of
             let bind = parse_bind_config(lex_root_scope.block.content.clone())?;
 
+            return Ok(Document::BindConfig(bind));
+        } else if root_scope_selector.version == Version::from_str("1.1.0")? {
+            let bind = parse_bind_config(lex_root_scope.block.content.clone())?.anchor_paths()?;
+
             return Ok(Document::BindConfig(bind));
         } else {
             let message = format!(
@@ -7535,6 +7707,29 @@ pub fn call_pipeline_stop<I: Span>(input: I) -> Res<I, PipelineStopVar> {
     context("Call", call)(input).map(|(next, call)| (next, PipelineStopVar::Call(call)))
 }
 
+/// a canned error reflection such as `Err<404>("no such user")`
+pub fn err_pipeline_stop<I: Span>(input: I) -> Res<I, PipelineStopVar> {
+    let (next, (_, status, msg)) = context(
+        "pipeline:stop:err",
+        tuple((
+            tag("Err"),
+            delimited(tag("<"), digit1, tag(">")),
+            delimited(tag("(\""), opt(in_double_quotes), tag("\")")),
+        )),
+    )(input.clone())?;
+    let status = match status.to_string().parse::<u16>() {
+        Ok(status) => status,
+        Err(_) => {
+            return Err(nom::Err::Failure(NomErr::from_error_kind(
+                input,
+                ErrorKind::Digit,
+            )))
+        }
+    };
+    let msg = msg.map(|msg| msg.to_string()).unwrap_or_default();
+    Ok((next, PipelineStopVar::Err { status, msg }))
+}
+
 pub fn point_pipeline_stop<I: Span>(input: I) -> Res<I, PipelineStopVar> {
     context("pipeline:stop:point", point_var)(input)
         .map(|(next, point)| (next, PipelineStopVar::Point(point)))
@@ -7551,6 +7746,7 @@ pub fn pipeline_stop_var<I: Span>(input: I) -> Res<I, PipelineStopVar> {
             alt((
                 core_pipeline_stop,
                 return_pipeline_stop,
+                err_pipeline_stop,
                 call_pipeline_stop,
                 point_pipeline_stop,
             )),
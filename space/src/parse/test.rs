@@ -1,7 +1,8 @@
 use crate::command::direct::create::{PointSegTemplate, PointTemplate, Template};
 use crate::command::Command;
+use crate::config::bind::PipelineStopVar;
 use crate::config::Document;
-use crate::err::PrintErr;
+use crate::err::{PrintErr, SpaceErr};
 use crate::parse::context;
 use crate::parse::model::{BlockKind, DelimitedBlockKind, NestedBlockKind, TerminatedBlockKind};
 use crate::parse::util::{new_span, result, span_with_extra};
@@ -13,12 +14,14 @@ use crate::parse::{
     point_var, point_var_seg, pop, rec_version, root_ctx_seg, root_scope, root_scope_selector,
     route_attribute, scope_filter, scope_filters, skewer_case_chars, skewer_dot, space_chars,
     space_no_dupe_dots, space_point_kind_segment, space_point_segment, strip_comments, template,
-    var_case, version, Env,
+    var_case, version, write_payload, Env,
 };
 use crate::point::{Point, PointCtx, PointSegVar, RouteSegVar};
-use crate::substance::Substance;
+use crate::substance::{Call, Substance};
 use crate::util;
 use crate::util::{log, ToResolved};
+use crate::wave::core::http2::HttpMethod;
+use crate::wave::core::Method;
 use anyhow::Context;
 use nom::bytes::complete::escaped;
 use nom::character::complete::{alpha1, anychar, multispace0};
@@ -101,6 +104,20 @@ pub fn test_create_command() {
     let command: Command = util::log(command.to_resolved(&env)).unwrap();
 }
 
+#[test]
+pub fn test_write_payload() {
+    let payload = util::log(result(write_payload(new_span("^[ hello ]")))).unwrap();
+    assert_eq!(payload, Substance::Text("hello".to_string()));
+
+    let payload = util::log(result(write_payload(new_span(r"^[ a[0\] \\ b ]")))).unwrap();
+    assert_eq!(payload, Substance::Text(r"a[0] \ b".to_string()));
+
+    let payload = util::log(result(write_payload(new_span("^[]")))).unwrap();
+    assert_eq!(payload, Substance::Text("".to_string()));
+
+    assert!(result(all_consuming(write_payload)(new_span("^[ a]b ]"))).is_err());
+}
+
 //    #[test]
 pub fn test_command_line_err() {
     let command = util::log(result(command_line(new_span("create localhost<bad>")))).unwrap();
@@ -507,6 +524,42 @@ pub fn test_pipeline_stop() {
     util::log(result(pipeline_stop_var(new_span("localhost:app:hello")))).unwrap();
 }
 
+#[test]
+pub fn test_call_and_err_pipeline_stops() {
+    let stop = util::log(result(pipeline_stop_var(new_span(
+        "localhost:users^Http<Get>/users",
+    ))))
+    .unwrap();
+    if let PipelineStopVar::Call(call) = stop {
+        let call: Call = call.to_resolved(&Env::no_point()).unwrap();
+        assert_eq!(call.point, Point::from_str("localhost:users").unwrap());
+        assert_eq!(call.kind.method(), Method::Http(HttpMethod::Get));
+        let path: String = call
+            .kind
+            .path()
+            .clone()
+            .to_resolved(&Env::no_point())
+            .unwrap();
+        assert_eq!(path.as_str(), "/users");
+    } else {
+        assert!(false);
+    }
+
+    let stop = util::log(result(pipeline_stop_var(new_span(
+        "Err<404>(\"no such user\")",
+    ))))
+    .unwrap();
+    if let PipelineStopVar::Err { status, msg } = stop {
+        assert_eq!(status, 404);
+        assert_eq!(msg.as_str(), "no such user");
+    } else {
+        assert!(false);
+    }
+
+    util::log(result(pipeline(new_span("-> Err<403>(\"\")")))).unwrap();
+    assert!(util::log(result(pipeline_stop_var(new_span("Err<x>(\"bad\")")))).is_err());
+}
+
 #[test]
 pub fn test_pipeline() {
     util::log(result(pipeline(new_span("-> localhost => &")))).unwrap();
@@ -1147,3 +1200,63 @@ pub fn test_subst() {
 pub fn space_point() {
     assert!(log(result(space_point_segment(new_span("lah.com")))).is_ok());
 }
+
+#[test]
+pub fn test_bind_path_captures() {
+    use crate::config::bind::BindConfig;
+    use crate::loc::ToSurface;
+    use crate::parse::Env;
+    use crate::wave::DirectedProto;
+
+    let bind = |version: &str| -> BindConfig {
+        let bind_config_str = r#"Bind(version=VERSION) {
+              Route<Http> -> {
+                 <Get>/users/(?P<id>\w+) -> users:${id} => &;
+                 <Get>/users/(?P<id>\w+)/posts/(?P<post>[a-z][a-z0-9-]*) -> users:${id}:posts:${post} => &;
+              }
+           }"#
+        .replace("VERSION", version);
+        match util::log(doc(bind_config_str.as_str())).unwrap() {
+            Document::BindConfig(bind) => bind,
+            _ => panic!("expected BindConfig"),
+        }
+    };
+
+    let resolve = |bind: &BindConfig, path: &str| -> Result<Point, SpaceErr> {
+        let mut proto = DirectedProto::http(Point::root().to_surface(), HttpMethod::Get);
+        proto.uri(url::Url::parse(format!("http://localhost{}", path).as_str()).unwrap());
+        proto.from(Point::root().to_surface());
+        let directed = proto.build()?;
+        let (route, captures) = bind.select_with_captures(&directed)?;
+        let mut env = Env::new(Point::root());
+        env.add_var_resolver(Arc::new(captures));
+        match &route.block.first().unwrap().stop {
+            PipelineStopVar::Point(point) => Ok(point.clone().to_resolved(&env)?),
+            _ => panic!("expected a Point stop"),
+        }
+    };
+
+    // from 1.1.0 a scope path must match the whole uri path
+    let anchored = bind("1.1.0");
+    assert_eq!(
+        resolve(&anchored, "/users/scott").unwrap(),
+        Point::from_str("users:scott").unwrap()
+    );
+    assert_eq!(
+        resolve(&anchored, "/users/scott/posts/hello-world").unwrap(),
+        Point::from_str("users:scott:posts:hello-world").unwrap()
+    );
+    assert!(resolve(&anchored, "/users/scott/comments").is_err());
+    assert!(resolve(&anchored, "/admin/users/scott").is_err());
+
+    // a 1.0.0 scope path still selects any uri path it matches part of
+    let unanchored = bind("1.0.0");
+    assert_eq!(
+        resolve(&unanchored, "/users/scott/posts/hello-world").unwrap(),
+        Point::from_str("users:scott").unwrap()
+    );
+    assert_eq!(
+        resolve(&unanchored, "/users/scott/comments").unwrap(),
+        Point::from_str("users:scott").unwrap()
+    );
+}
@@ -785,7 +785,7 @@ impl ToResolved<PointCtx> for PointVar {
             if let PointSegVar::Var(ref var) = segment {
                 match env.val(var.name.clone().as_str()) {
                     Ok(val) => {
-                        if index > 1 {
+                        if index > 0 {
                             if after_fs {
                                 //                                    rtn.push_str("/");
                             } else {
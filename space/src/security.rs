@@ -14,7 +14,7 @@ use crate::parse::util::result;
 use crate::parse::{particle_perms, permissions, permissions_mask, privilege};
 use crate::point::Point;
 use crate::selector::{PointHierarchy, Selector};
-use crate::wave::{Agent, ScopeGrant};
+use crate::wave::{Agent, Scope, ScopeGrant, ScopeGrantAspect, ScopeGrantKind};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Access {
@@ -56,6 +56,44 @@ impl Access {
         Self::Enumerated(EnumeratedAccess::none())
     }
 
+    /// narrow this access on the particle described by `hierarchy` to what `scope` allows.
+    ///
+    /// The [ScopeGrant]s whose selector matches `hierarchy` form a ceiling: every
+    /// [ScopeGrantKind::Or] grant is added to an empty access and then every
+    /// [ScopeGrantKind::And] grant is applied to the result.  The ceiling is intersected
+    /// with this access, so a scope can take access away but never add to it.  `Super` and
+    /// `Owner` are treated as full access before they are narrowed.
+    ///
+    /// [Scope::Full] & [Scope::None] leave access unchanged: [Scope::None] is what a wave
+    /// carries when nothing scoped it.  A scope that should allow nothing is an empty
+    /// [Scope::Grants]
+    pub fn mask(&self, scope: &Scope, hierarchy: &PointHierarchy) -> Access {
+        let grants = match scope {
+            Scope::Full | Scope::None => return self.clone(),
+            Scope::Grants(grants) => grants,
+        };
+
+        let grants: Vec<&ScopeGrant> = grants
+            .iter()
+            .filter(|grant| grant.on.matches_found(hierarchy))
+            .collect();
+
+        let mut ceiling = EnumeratedAccess::none();
+        for grant in grants.iter().filter(|g| g.kind == ScopeGrantKind::Or) {
+            ceiling.mask(grant);
+        }
+        for grant in grants.iter().filter(|g| g.kind == ScopeGrantKind::And) {
+            ceiling.mask(grant);
+        }
+
+        let mut access = match self {
+            Access::Enumerated(enumerated) => enumerated.clone(),
+            _ => EnumeratedAccess::full(),
+        };
+        access.and(&ceiling);
+        Access::Enumerated(access)
+    }
+
     pub fn permissions(&self) -> Permissions {
         match self {
             Access::Super => Permissions::full(),
@@ -232,7 +270,25 @@ pub struct EnumeratedAccess {
 }
 
 impl EnumeratedAccess {
-    pub fn mask(&mut self, scope_grant: &ScopeGrant) {}
+    /// apply a single [ScopeGrant] to this access.  An [ScopeGrantKind::Or] grant adds its
+    /// permissions or privilege while an [ScopeGrantKind::And] grant keeps only what it names
+    pub fn mask(&mut self, scope_grant: &ScopeGrant) {
+        match (&scope_grant.kind, &scope_grant.aspect) {
+            (ScopeGrantKind::Or, ScopeGrantAspect::Perm(permissions)) => {
+                self.permissions.or(permissions)
+            }
+            (ScopeGrantKind::And, ScopeGrantAspect::Perm(permissions)) => {
+                self.permissions.and(permissions)
+            }
+            (ScopeGrantKind::Or, ScopeGrantAspect::Priv(privilege)) => {
+                self.privileges = self.privileges.clone() | privilege;
+            }
+            (ScopeGrantKind::And, ScopeGrantAspect::Priv(privilege)) => {
+                let privileges = Privileges::none() | privilege;
+                self.privileges = self.privileges.clone().and(&privileges);
+            }
+        }
+    }
 
     pub fn full() -> Self {
         Self {
@@ -593,3 +649,69 @@ impl AccessProvider for AllAccessProvider {
         Ok(Access::SuperOwner)
     }
 }
+
+#[cfg(test)]
+pub mod test {
+    use crate::security::{Access, Permissions, Privilege};
+    use crate::selector::{PointHierarchy, Selector};
+    use crate::wave::{Scope, ScopeGrant, ScopeGrantAspect, ScopeGrantKind};
+    use std::collections::HashSet;
+    use std::str::FromStr;
+
+    fn grant(on: &str, kind: ScopeGrantKind, aspect: ScopeGrantAspect) -> ScopeGrant {
+        ScopeGrant {
+            on: Selector::from_str(on).unwrap(),
+            kind,
+            aspect,
+        }
+    }
+
+    #[test]
+    pub fn test_mask() {
+        let repo = PointHierarchy::from_str("localhost<Space>:repo<Repo>").unwrap();
+        let users = PointHierarchy::from_str("localhost<Space>:users<Base>").unwrap();
+
+        let mut grants = HashSet::new();
+        grants.insert(grant(
+            "localhost:repo",
+            ScopeGrantKind::Or,
+            ScopeGrantAspect::Perm(Permissions::from_str("cSd-Rwx").unwrap()),
+        ));
+        grants.insert(grant(
+            "localhost:**",
+            ScopeGrantKind::Or,
+            ScopeGrantAspect::Priv(Privilege::Single("property:email:read".to_string())),
+        ));
+        grants.insert(grant(
+            "localhost:**",
+            ScopeGrantKind::And,
+            ScopeGrantAspect::Perm(Permissions::from_str("CSD-rWX").unwrap()),
+        ));
+        let scope = Scope::Grants(grants);
+
+        // super is narrowed to the scope
+        let access = Access::Super.mask(&scope, &repo);
+        assert!(!access.has_super());
+        let permissions = access.permissions();
+        assert!(permissions.child.select);
+        assert!(!permissions.child.create);
+        // the And grant removes read granted by the Or grant
+        assert!(!permissions.particle.read);
+        assert!(access.check_privilege("property:email:read").is_ok());
+        assert!(access.check_privilege("property:password:read").is_err());
+
+        // no grant gives any permission on users
+        let access = Access::Super.mask(&scope, &users);
+        assert!(!access.permissions().child.select);
+
+        // a scope never adds to access
+        let access = Access::none().mask(&scope, &repo);
+        assert!(!access.permissions().child.select);
+        assert!(access.check_privilege("property:email:read").is_err());
+
+        assert!(Access::Super.mask(&Scope::Full, &repo).has_super());
+        assert!(Access::Super.mask(&Scope::None, &repo).has_super());
+        let access = Access::Super.mask(&Scope::Grants(HashSet::new()), &repo);
+        assert!(!access.permissions().particle.read);
+    }
+}
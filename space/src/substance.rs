@@ -24,7 +24,7 @@ use crate::wave::core::cmd::CmdMethod;
 use crate::wave::core::ext::ExtMethod;
 use crate::wave::core::http2::HttpMethod;
 use crate::wave::core::hyper::HypMethod;
-use crate::wave::core::{DirectedCore, HeaderMap, ReflectedCore};
+use crate::wave::core::{DirectedCore, HeaderMap, Method, ReflectedCore};
 use crate::wave::{PongCore, Wave};
 use starlane_macros::{Autobox, ToSubstance};
 use url::Url;
@@ -169,6 +169,17 @@ pub trait ToSubstance<S> {
     fn to_substance_ref(&self) -> Result<&S, ParseErrs>;
 }
 
+/// lets a handler accept a body of any kind with `InCtx<'_, Substance>`
+impl ToSubstance<Substance> for Substance {
+    fn to_substance(self) -> Result<Substance, ParseErrs> {
+        Ok(self)
+    }
+
+    fn to_substance_ref(&self) -> Result<&Substance, ParseErrs> {
+        Ok(self)
+    }
+}
+
 pub trait ChildSubstance {}
 
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, Hash)]
@@ -825,6 +836,24 @@ impl CallKind {
         })
     }
      */
+
+    pub fn method(&self) -> Method {
+        match self {
+            CallKind::Cmd(cmd) => Method::Cmd(cmd.method.clone()),
+            CallKind::Hyp(hyp) => Method::Hyp(hyp.method.clone()),
+            CallKind::Ext(ext) => Method::Ext(ext.method.clone()),
+            CallKind::Http(http) => Method::Http(http.method.clone()),
+        }
+    }
+
+    pub fn path(&self) -> &Subst<Tw<String>> {
+        match self {
+            CallKind::Cmd(cmd) => &cmd.path,
+            CallKind::Hyp(hyp) => &hyp.path,
+            CallKind::Ext(ext) => &ext.path,
+            CallKind::Http(http) => &http.path,
+        }
+    }
 }
 
 impl ToString for Call {
@@ -5,6 +5,7 @@ use std::collections::{HashMap, HashSet};
 use std::ops;
 use std::ops::{Deref, DerefMut};
 use std::sync::Arc;
+use std::time::Duration;
 
 use self::core::cmd::CmdMethod;
 use self::core::ext::ExtMethod;
@@ -453,6 +454,16 @@ impl Wave {
         }
     }
 
+    pub fn set_scope(&mut self, scope: Scope) {
+        match self {
+            Wave::Ping(ping) => ping.scope = scope,
+            Wave::Pong(pong) => pong.scope = scope,
+            Wave::Ripple(ripple) => ripple.scope = scope,
+            Wave::Echo(echo) => echo.scope = scope,
+            Wave::Signal(signal) => signal.scope = scope,
+        }
+    }
+
     pub fn set_to(&mut self, to: Surface) {
         match self {
             Wave::Ping(ping) => ping.to = to,
@@ -597,6 +608,10 @@ impl WaveId {
         Self { uuid, kind }
     }
 
+    pub fn uuid(&self) -> &Uuid {
+        &self.uuid
+    }
+
     pub fn to_short_string(&self) -> String {
         if self.uuid.to_string().len() > 8 {
             format!(
@@ -3214,13 +3229,33 @@ pub enum Retries {
     Min,
 }
 
+impl Retries {
+    /// how many times a directed wave will be resent after its reflection times out
+    pub fn attempts(&self) -> u32 {
+        match self {
+            Retries::None => 0,
+            Retries::Min => 1,
+            Retries::Medium => 3,
+            Retries::Max => 5,
+        }
+    }
+
+    /// exponential backoff to wait before the given retry `attempt` (starting at 1)
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let millis = 500u64.saturating_mul(1u64 << attempt.saturating_sub(1).min(6));
+        Duration::from_millis(millis)
+    }
+}
+
 impl Default for Retries {
     fn default() -> Self {
         Retries::None
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+/// Priority variants are declared from most to least urgent so the derived `Ord`
+/// sorts `Hyper` before `Low`
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, Hash, PartialOrd, Ord)]
 pub enum Priority {
     Hyper,
     Super,
@@ -17,6 +17,7 @@ pub enum CmdMethod {
     Init,
     Read,
     Update,
+    Delete,
     Bounce,
     Knock,
     Greet,
@@ -23,6 +23,9 @@ pub enum HypMethod {
     Transport,
     HyperWave,
     Search,
+    Watch,
+    Unwatch,
+    Event,
 }
 
 impl Default for HypMethod {
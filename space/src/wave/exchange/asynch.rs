@@ -103,6 +103,26 @@ impl ProtoTransmitter {
                 self.router.route(directed.to_wave()).await;
                 FromReflectedAggregate::from_reflected_aggregate(ReflectedAggregate::None)
             }
+            BounceBacks::Single => {
+                // a timed out reflection is resent with backoff as many times as
+                // the wave's Handling allows
+                let retries = directed.handling().retries.clone();
+                let mut attempt = 0;
+                loop {
+                    let reflected_rx = self.exchanger.exchange(&directed).await;
+                    self.router.route(directed.clone().to_wave()).await;
+                    let reflected_agg = reflected_rx.await?;
+                    // only a timeout raised by the exchanger is retried, a 408 reflected by
+                    // the recipient itself is returned as is
+                    let timed_out = self.exchanger.timed_out(directed.id());
+                    if attempt < retries.attempts() && timed_out {
+                        attempt += 1;
+                        tokio::time::sleep(retries.backoff(attempt)).await;
+                        continue;
+                    }
+                    return FromReflectedAggregate::from_reflected_aggregate(reflected_agg);
+                }
+            }
             _ => {
                 let reflected_rx = self.exchanger.exchange(&directed).await;
                 self.router.route(directed.to_wave()).await;
@@ -297,6 +317,8 @@ pub struct Exchanger {
     pub surface: Surface,
     pub multis: Arc<DashMap<WaveId, mpsc::Sender<ReflectedWave>>>,
     pub singles: Arc<DashMap<WaveId, oneshot::Sender<ReflectedAggregate>>>,
+    /// directed waves for which no reflection arrived in time
+    pub expired: Arc<DashSet<WaveId>>,
     pub timeouts: Timeouts,
     pub logger: Logger,
     #[cfg(test)]
@@ -310,6 +332,7 @@ impl Exchanger {
             surface,
             singles: Arc::new(DashMap::new()),
             multis: Arc::new(DashMap::new()),
+            expired: Arc::new(DashSet::new()),
             timeouts,
             logger,
             #[cfg(test)]
@@ -323,6 +346,7 @@ impl Exchanger {
             surface,
             singles: self.singles.clone(),
             multis: self.multis.clone(),
+            expired: self.expired.clone(),
             timeouts: self.timeouts.clone(),
             logger,
             #[cfg(test)]
@@ -330,6 +354,12 @@ impl Exchanger {
         }
     }
 
+    /// true (once) if the reflection of `directed` was a timeout raised by this exchanger
+    /// rather than a reflection from the recipient
+    pub fn timed_out(&self, directed: &WaveId) -> bool {
+        self.expired.remove(directed).is_some()
+    }
+
     pub async fn reflected(&self, reflect: ReflectedWave) -> Result<(), SpaceErr> {
         if let Some(multi) = self.multis.get(reflect.reflection_of()) {
             multi.value().send(reflect).await;
@@ -396,10 +426,12 @@ impl Exchanger {
             }
             BounceBacks::Single => {
                 let singles = self.singles.clone();
+                let expired = self.expired.clone();
                 tokio::spawn(async move {
                     tokio::time::sleep(Duration::from_secs(timeout)).await;
                     let id = reflected.reflection_of.as_ref().unwrap();
                     if let Some((_, tx)) = singles.remove(id) {
+                        expired.insert(id.clone());
                         reflected.status = Some(StatusCode::from_u16(408).unwrap());
                         reflected.body = Some(Substance::Empty);
                         reflected.intended = Some(reflection.intended);
@@ -527,3 +559,66 @@ impl RootInCtx {
         })
     }
 }
+
+#[cfg(test)]
+pub mod test {
+    use crate::loc::ToSurface;
+    use crate::point::Point;
+    use crate::settings::Timeouts;
+    use crate::substance::Substance;
+    use crate::wave::core::cmd::CmdMethod;
+    use crate::wave::core::http2::StatusCode;
+    use crate::wave::exchange::asynch::Exchanger;
+    use crate::wave::{BounceProto, DirectedProto};
+
+    #[tokio::test]
+    pub async fn test_timed_out() {
+        let timeouts = Timeouts {
+            high: 0,
+            med: 0,
+            low: 0,
+        };
+        let exchanger = Exchanger::new(Point::root().to_surface(), timeouts, Default::default());
+        let mut ping = DirectedProto::ping();
+        ping.from(Point::root().to_surface());
+        ping.to(Point::root().to_surface());
+        ping.method(CmdMethod::Read);
+        let ping = ping.build().unwrap();
+
+        exchanger.exchange(&ping).await.await.unwrap();
+        assert!(exchanger.timed_out(ping.id()));
+        // the timeout is only reported once
+        assert!(!exchanger.timed_out(ping.id()));
+    }
+
+    #[tokio::test]
+    pub async fn test_reflected_408_is_not_timed_out() {
+        let exchanger = Exchanger::new(
+            Point::root().to_surface(),
+            Default::default(),
+            Default::default(),
+        );
+        let mut ping = DirectedProto::ping();
+        ping.from(Point::root().to_surface());
+        ping.to(Point::root().to_surface());
+        ping.method(CmdMethod::Read);
+        let ping = ping.build().unwrap();
+
+        let rx = exchanger.exchange(&ping).await;
+        let mut pong = match ping.reflected_proto() {
+            BounceProto::Reflected(pong) => pong,
+            BounceProto::Absorbed => panic!("expected a ping to be reflected"),
+        };
+        pong.from(Point::root().to_surface());
+        pong.status = Some(StatusCode::from_u16(408).unwrap());
+        pong.body(Substance::Empty).unwrap();
+        pong.intended(Point::root().to_surface());
+        exchanger
+            .reflected(pong.build().unwrap())
+            .await
+            .unwrap();
+
+        rx.await.unwrap();
+        assert!(!exchanger.timed_out(ping.id()));
+    }
+}